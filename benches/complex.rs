@@ -119,6 +119,41 @@ fn build_deep_hierarchy(taffy: &mut taffy::node::Taffy) -> taffy::node::Node {
     taffy.new_with_children(taffy::style::FlexboxLayout { ..Default::default() }, &[node1, node2]).unwrap()
 }
 
+fn build_wide_hierarchy(taffy: &mut taffy::node::Taffy) -> taffy::node::Node {
+    let columns: Vec<_> = (0..64)
+        .map(|_| {
+            let leaves: Vec<_> = (0..64)
+                .map(|_| {
+                    taffy
+                        .new_with_children(
+                            taffy::style::FlexboxLayout {
+                                size: taffy::geometry::Size {
+                                    width: taffy::style::Dimension::Points(10.0),
+                                    height: taffy::style::Dimension::Points(10.0),
+                                },
+                                ..Default::default()
+                            },
+                            &[],
+                        )
+                        .unwrap()
+                })
+                .collect();
+            taffy
+                .new_with_children(
+                    taffy::style::FlexboxLayout {
+                        flex_direction: taffy::style::FlexDirection::Column,
+                        flex_wrap: taffy::style::FlexWrap::Wrap,
+                        ..Default::default()
+                    },
+                    &leaves,
+                )
+                .unwrap()
+        })
+        .collect();
+
+    taffy.new_with_children(taffy::style::FlexboxLayout { ..Default::default() }, &columns).unwrap()
+}
+
 fn taffy_benchmarks(c: &mut Criterion) {
     c.bench_function("deep hierarchy - build", |b| {
         b.iter(|| {
@@ -144,6 +179,14 @@ fn taffy_benchmarks(c: &mut Criterion) {
             taffy.compute_layout(root, taffy::geometry::Size::undefined()).unwrap()
         })
     });
+
+    c.bench_function("wide hierarchy - single", |b| {
+        b.iter(|| {
+            let mut taffy = taffy::node::Taffy::new();
+            let root = build_wide_hierarchy(&mut taffy);
+            taffy.compute_layout(root, taffy::geometry::Size::undefined()).unwrap()
+        })
+    });
 }
 
 criterion_group!(benches, taffy_benchmarks);
@@ -1,6 +1,6 @@
 use taffy::prelude::*;
 
-fn main() -> Result<(), taffy::error::InvalidNode> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut taffy = Taffy::new();
 
     // left
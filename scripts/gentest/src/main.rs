@@ -221,6 +221,7 @@ fn generate_node(ident: &str, node: &json::JsonValue) -> TokenStream {
     let position_type = match style["position_type"] {
         json::JsonValue::Short(ref value) => match value.as_ref() {
             "absolute" => quote!(position_type: taffy::style::PositionType::Absolute,),
+            "static" => quote!(position_type: taffy::style::PositionType::Static,),
             _ => quote!(),
         },
         _ => quote!(),
@@ -269,6 +270,7 @@ fn generate_node(ident: &str, node: &json::JsonValue) -> TokenStream {
             "flex-end" => quote!(align_items: taffy::style::AlignItems::FlexEnd,),
             "center" => quote!(align_items: taffy::style::AlignItems::Center,),
             "baseline" => quote!(align_items: taffy::style::AlignItems::Baseline,),
+            "last baseline" => quote!(align_items: taffy::style::AlignItems::LastBaseline,),
             _ => quote!(),
         },
         _ => quote!(),
@@ -280,6 +282,7 @@ fn generate_node(ident: &str, node: &json::JsonValue) -> TokenStream {
             "flex-end" => quote!(align_self: taffy::style::AlignSelf::FlexEnd,),
             "center" => quote!(align_self: taffy::style::AlignSelf::Center,),
             "baseline" => quote!(align_self: taffy::style::AlignSelf::Baseline,),
+            "last baseline" => quote!(align_self: taffy::style::AlignSelf::LastBaseline,),
             "stretch" => quote!(align_self: taffy::style::AlignSelf::Stretch,),
             _ => quote!(),
         },
@@ -458,6 +461,7 @@ fn generate_dimension(dimen: &json::object::Object) -> TokenStream {
     match unit {
         json::JsonValue::Short(ref unit) => match unit.as_ref() {
             "auto" => quote!(taffy::style::Dimension::Auto),
+            "content" => quote!(taffy::style::Dimension::Content),
             "points" => {
                 let value = value();
                 quote!(taffy::style::Dimension::Points(#value))
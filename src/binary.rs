@@ -0,0 +1,531 @@
+//! Compact conversion of whole trees of nodes to and from bytes, enabled by the `binary` feature
+//!
+//! The bytes start with [`MAGIC`] and the little-endian [`VERSION`] of the format, followed by the nodes of the tree
+//! in preorder. Each node is stored as every field of its style in declaration order, followed by its number of children.
+//! Enums take a single byte, numbers are little-endian `f32`s, and counts are LEB128 varints.
+use crate::error::BinaryError;
+use crate::geometry::{Rect, Size};
+use crate::node::{Node, Taffy};
+use crate::style::{
+    AlignContent, AlignItems, AlignSelf, BoxSizing, Dimension, Direction, Display, FlexDirection, FlexWrap,
+    FlexboxLayout, JustifyContent, PositionType, WritingMode,
+};
+use crate::sys::BuildHasher;
+
+/// The bytes that every encoded tree starts with
+const MAGIC: [u8; 4] = *b"TAFY";
+
+/// The version of the format, which is increased whenever a change would make older bytes decode differently
+const VERSION: u16 = 1;
+
+/// The encoded bytes that remain to be decoded
+struct Reader<'a> {
+    /// All of the encoded bytes
+    bytes: &'a [u8],
+    /// The offset of the next byte to decode
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Takes the next `len` bytes
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BinaryError> {
+        match self.bytes.get(self.offset..self.offset + len) {
+            Some(bytes) => {
+                self.offset += len;
+                Ok(bytes)
+            }
+            None => Err(BinaryError::Malformed { offset: self.bytes.len() }),
+        }
+    }
+
+    /// Takes the next byte
+    fn byte(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Takes the next varint
+    fn varint(&mut self) -> Result<usize, BinaryError> {
+        let offset = self.offset;
+        let mut value = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as usize).checked_shl(shift).ok_or(BinaryError::Malformed { offset })?;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BinaryError::Malformed { offset })
+    }
+}
+
+/// Appends `value` to the `bytes` as a varint
+fn write_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// A value that can be stored in the format
+trait Encode: Sized {
+    /// Appends this value to the `bytes`
+    fn encode(&self, bytes: &mut Vec<u8>);
+
+    /// Decodes a value from the next bytes of the `reader`
+    fn decode(reader: &mut Reader) -> Result<Self, BinaryError>;
+}
+
+/// Implements [`Encode`] for an enum without fields, storing each variant as the given byte
+macro_rules! encode_enum {
+    ($name:ident { $($variant:ident = $byte:literal),* $(,)? }) => {
+        impl Encode for $name {
+            fn encode(&self, bytes: &mut Vec<u8>) {
+                bytes.push(match self {
+                    $($name::$variant => $byte,)*
+                });
+            }
+
+            fn decode(reader: &mut Reader) -> Result<Self, BinaryError> {
+                let offset = reader.offset;
+                match reader.byte()? {
+                    $($byte => Ok($name::$variant),)*
+                    _ => Err(BinaryError::Malformed { offset }),
+                }
+            }
+        }
+    };
+}
+
+encode_enum!(AlignItems { FlexStart = 0, FlexEnd = 1, Center = 2, Baseline = 3, Stretch = 4, Start = 5, End = 6, LastBaseline = 7 });
+encode_enum!(AlignSelf { Auto = 0, FlexStart = 1, FlexEnd = 2, Center = 3, Baseline = 4, Stretch = 5, LastBaseline = 6 });
+encode_enum!(AlignContent { FlexStart = 0, FlexEnd = 1, Center = 2, Stretch = 3, SpaceBetween = 4, SpaceAround = 5 });
+encode_enum!(Display { Flex = 0, None = 1 });
+encode_enum!(FlexDirection { Row = 0, Column = 1, RowReverse = 2, ColumnReverse = 3 });
+encode_enum!(JustifyContent {
+    FlexStart = 0,
+    FlexEnd = 1,
+    Center = 2,
+    SpaceBetween = 3,
+    SpaceAround = 4,
+    SpaceEvenly = 5,
+    Start = 6,
+    End = 7,
+});
+encode_enum!(PositionType { Relative = 0, Absolute = 1, Static = 2 });
+encode_enum!(FlexWrap { NoWrap = 0, Wrap = 1, WrapReverse = 2 });
+encode_enum!(BoxSizing { BorderBox = 0, ContentBox = 1 });
+encode_enum!(WritingMode { HorizontalTb = 0, VerticalRl = 1, VerticalLr = 2 });
+encode_enum!(Direction { Ltr = 0, Rtl = 1 });
+
+impl Encode for f32 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, BinaryError> {
+        let bytes = reader.take(4)?;
+        Ok(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+impl Encode for Option<f32> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            None => bytes.push(0),
+            Some(value) => {
+                bytes.push(1);
+                value.encode(bytes);
+            }
+        }
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, BinaryError> {
+        let offset = reader.offset;
+        match reader.byte()? {
+            0 => Ok(None),
+            1 => Ok(Some(f32::decode(reader)?)),
+            _ => Err(BinaryError::Malformed { offset }),
+        }
+    }
+}
+
+impl Encode for Dimension {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Dimension::Undefined => bytes.push(0),
+            Dimension::Auto => bytes.push(1),
+            Dimension::Points(value) => {
+                bytes.push(2);
+                value.encode(bytes);
+            }
+            Dimension::Percent(value) => {
+                bytes.push(3);
+                value.encode(bytes);
+            }
+            Dimension::Content => bytes.push(4),
+        }
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, BinaryError> {
+        let offset = reader.offset;
+        match reader.byte()? {
+            0 => Ok(Dimension::Undefined),
+            1 => Ok(Dimension::Auto),
+            2 => Ok(Dimension::Points(f32::decode(reader)?)),
+            3 => Ok(Dimension::Percent(f32::decode(reader)?)),
+            4 => Ok(Dimension::Content),
+            _ => Err(BinaryError::Malformed { offset }),
+        }
+    }
+}
+
+impl<T: Encode> Encode for Rect<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.start.encode(bytes);
+        self.end.encode(bytes);
+        self.top.encode(bytes);
+        self.bottom.encode(bytes);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, BinaryError> {
+        Ok(Rect {
+            start: T::decode(reader)?,
+            end: T::decode(reader)?,
+            top: T::decode(reader)?,
+            bottom: T::decode(reader)?,
+        })
+    }
+}
+
+impl<T: Encode> Encode for Size<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.width.encode(bytes);
+        self.height.encode(bytes);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, BinaryError> {
+        Ok(Size { width: T::decode(reader)?, height: T::decode(reader)? })
+    }
+}
+
+impl Encode for FlexboxLayout {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        // Destructured so that a new field cannot be forgotten
+        let FlexboxLayout {
+            display,
+            position_type,
+            flex_direction,
+            writing_mode,
+            direction,
+            flex_wrap,
+            align_items,
+            align_self,
+            align_content,
+            justify_content,
+            position,
+            margin,
+            padding,
+            border,
+            gap,
+            flex_grow,
+            flex_shrink,
+            flex_basis,
+            size,
+            min_size,
+            max_size,
+            box_sizing,
+            aspect_ratio,
+        } = self;
+        display.encode(bytes);
+        position_type.encode(bytes);
+        flex_direction.encode(bytes);
+        writing_mode.encode(bytes);
+        direction.encode(bytes);
+        flex_wrap.encode(bytes);
+        align_items.encode(bytes);
+        align_self.encode(bytes);
+        align_content.encode(bytes);
+        justify_content.encode(bytes);
+        position.encode(bytes);
+        margin.encode(bytes);
+        padding.encode(bytes);
+        border.encode(bytes);
+        gap.encode(bytes);
+        flex_grow.encode(bytes);
+        flex_shrink.encode(bytes);
+        flex_basis.encode(bytes);
+        size.encode(bytes);
+        min_size.encode(bytes);
+        max_size.encode(bytes);
+        box_sizing.encode(bytes);
+        aspect_ratio.encode(bytes);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, BinaryError> {
+        Ok(FlexboxLayout {
+            display: Encode::decode(reader)?,
+            position_type: Encode::decode(reader)?,
+            flex_direction: Encode::decode(reader)?,
+            writing_mode: Encode::decode(reader)?,
+            direction: Encode::decode(reader)?,
+            flex_wrap: Encode::decode(reader)?,
+            align_items: Encode::decode(reader)?,
+            align_self: Encode::decode(reader)?,
+            align_content: Encode::decode(reader)?,
+            justify_content: Encode::decode(reader)?,
+            position: Encode::decode(reader)?,
+            margin: Encode::decode(reader)?,
+            padding: Encode::decode(reader)?,
+            border: Encode::decode(reader)?,
+            gap: Encode::decode(reader)?,
+            flex_grow: Encode::decode(reader)?,
+            flex_shrink: Encode::decode(reader)?,
+            flex_basis: Encode::decode(reader)?,
+            size: Encode::decode(reader)?,
+            min_size: Encode::decode(reader)?,
+            max_size: Encode::decode(reader)?,
+            box_sizing: Encode::decode(reader)?,
+            aspect_ratio: Encode::decode(reader)?,
+        })
+    }
+}
+
+impl<S: BuildHasher> Taffy<S> {
+    /// Encodes the tree below `root` (including `root` itself) into a compact binary format
+    ///
+    /// As with `Taffy::to_json`, only the structure of the tree and the style of each node are stored.
+    /// [`MeasureFunc`](crate::node::MeasureFunc)s are skipped, and a node with several parents is stored once per parent.
+    /// The bytes start with a header identifying the format and its version, which [`Taffy::from_bytes`] checks.
+    /// Returns [`BinaryError::CycleDetected`] if the tree contains a cycle, which would have no end.
+    pub fn to_bytes(&self, root: Node) -> Result<Vec<u8>, BinaryError> {
+        if let Some(node) = self.find_cycle(root)? {
+            return Err(BinaryError::CycleDetected { node });
+        }
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        // Nodes are written in preorder, with an explicit stack so that deep trees cannot overflow the call stack
+        let mut stack: Vec<Node> = vec![root];
+        while let Some(node) = stack.pop() {
+            self.style(node)?.encode(&mut bytes);
+            let children = self.children(node)?;
+            write_varint(&mut bytes, children.len());
+            stack.extend(children.iter().rev());
+        }
+        Ok(bytes)
+    }
+
+    /// Adds the tree stored in the next bytes of the `reader` to this [`Taffy`], and returns its root
+    ///
+    /// Every node is created after its children, so the nodes whose children are still being read are kept
+    /// on an explicit stack, which lets deeply nested input be decoded without overflowing the call stack.
+    fn decode_tree(&mut self, reader: &mut Reader) -> Result<Node, BinaryError> {
+        // Each entry is a node whose children are being read, along with their count and the children read so far
+        let mut stack: Vec<(FlexboxLayout, usize, Vec<Node>)> = Vec::new();
+        stack.push(Self::decode_header(reader)?);
+        loop {
+            match stack.last_mut() {
+                Some((_, count, children)) if children.len() < *count => {
+                    let header = Self::decode_header(reader)?;
+                    stack.push(header);
+                }
+                _ => {
+                    let (style, _, children) = stack.pop().expect("the stack is never empty here");
+                    let node = self.new_with_children(style, &children)?;
+                    match stack.last_mut() {
+                        Some((_, _, siblings)) => siblings.push(node),
+                        None => return Ok(node),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads the style and the number of children of the node stored in the next bytes of the `reader`
+    fn decode_header(reader: &mut Reader) -> Result<(FlexboxLayout, usize, Vec<Node>), BinaryError> {
+        let style = FlexboxLayout::decode(reader)?;
+        let offset = reader.offset;
+        let count = reader.varint()?;
+        // Every child takes more than one byte, so a larger count is rejected before anything is allocated for it
+        if count > reader.bytes.len() - reader.offset {
+            return Err(BinaryError::Malformed { offset });
+        }
+        Ok((style, count, Vec::with_capacity(count)))
+    }
+}
+
+impl Taffy {
+    /// Creates a new [`Taffy`] containing the tree stored in `bytes` by [`Taffy::to_bytes`], and returns it along with its root
+    ///
+    /// Every node is created with a fresh [`Node`] handle, and without a [`MeasureFunc`](crate::node::MeasureFunc).
+    /// Returns [`BinaryError::NotTaffyData`] if the `bytes` were not produced by [`Taffy::to_bytes`], and
+    /// [`BinaryError::UnsupportedVersion`] if they were produced by an incompatible version of it.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Taffy, Node), BinaryError> {
+        let mut reader = Reader { bytes, offset: 0 };
+        if reader.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+            return Err(BinaryError::NotTaffyData);
+        }
+        let version = reader.take(2).map(|version| u16::from_le_bytes([version[0], version[1]]))?;
+        if version != VERSION {
+            return Err(BinaryError::UnsupportedVersion { version });
+        }
+
+        let mut taffy = Taffy::new();
+        let root = taffy.decode_tree(&mut reader)?;
+        if reader.offset != bytes.len() {
+            return Err(BinaryError::Malformed { offset: reader.offset });
+        }
+        Ok((taffy, root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::BinaryError;
+    use crate::geometry::{Rect, Size};
+    use crate::node::{MeasureFunc, Node, Taffy};
+    use crate::style::{AlignItems, Dimension, FlexDirection, FlexWrap, FlexboxLayout, JustifyContent};
+
+    /// A small tree with a variety of styles, along with its root
+    fn tree() -> (Taffy, Node) {
+        let mut taffy = Taffy::new();
+        let leaves: Vec<_> = (1..=5)
+            .map(|i| {
+                taffy
+                    .new_with_children(
+                        FlexboxLayout {
+                            size: Size { width: Dimension::Points(i as f32 * 15.0), height: Dimension::Percent(0.3) },
+                            margin: Rect { start: Dimension::Points(3.0), ..Default::default() },
+                            flex_basis: if i == 3 { Dimension::Content } else { Dimension::Auto },
+                            flex_grow: i as f32,
+                            aspect_ratio: if i == 2 { Some(1.5) } else { None },
+                            ..Default::default()
+                        },
+                        &[],
+                    )
+                    .unwrap()
+            })
+            .collect();
+        let row = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::SpaceAround,
+                    size: Size { width: Dimension::Points(120.0), height: Dimension::Auto },
+                    ..Default::default()
+                },
+                &leaves,
+            )
+            .unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    size: Size { width: Dimension::Points(300.0), height: Dimension::Points(200.0) },
+                    ..Default::default()
+                },
+                &[row],
+            )
+            .unwrap();
+        (taffy, root)
+    }
+
+    /// Asserts that the styles and layouts of `left` and `right` and all of their descendants are identical
+    fn assert_same_trees(left: &Taffy, left_node: Node, right: &Taffy, right_node: Node) {
+        assert_eq!(left.style(left_node).unwrap(), right.style(right_node).unwrap());
+        assert_eq!(left.layout(left_node).unwrap().size, right.layout(right_node).unwrap().size);
+        assert_eq!(left.layout(left_node).unwrap().location, right.layout(right_node).unwrap().location);
+
+        let (left_children, right_children) = (left.children(left_node).unwrap(), right.children(right_node).unwrap());
+        assert_eq!(left_children.len(), right_children.len());
+        for (left_child, right_child) in left_children.into_iter().zip(right_children) {
+            assert_same_trees(left, left_child, right, right_child);
+        }
+    }
+
+    #[test]
+    fn round_trip_reproduces_tree() {
+        let (mut taffy, root) = tree();
+        let bytes = taffy.to_bytes(root).unwrap();
+        assert!(bytes.starts_with(b"TAFY\x01\x00"));
+
+        let (mut restored, restored_root) = Taffy::from_bytes(&bytes).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        restored.compute_layout(restored_root, Size::undefined()).unwrap();
+        assert_same_trees(&taffy, root, &restored, restored_root);
+        assert_eq!(restored.to_bytes(restored_root).unwrap(), bytes);
+    }
+
+    #[test]
+    fn measure_funcs_are_not_encoded() {
+        let mut taffy = Taffy::new();
+        let leaf =
+            taffy.new_leaf(FlexboxLayout::default(), MeasureFunc::fixed(Size { width: 10.0, height: 10.0 })).unwrap();
+
+        let (mut restored, restored_leaf) = Taffy::from_bytes(&taffy.to_bytes(leaf).unwrap()).unwrap();
+        restored.compute_layout(restored_leaf, Size::undefined()).unwrap();
+        assert_eq!(restored.layout(restored_leaf).unwrap().size, Size::zero());
+    }
+
+    #[test]
+    fn invalid_bytes_are_rejected() {
+        let (taffy, root) = tree();
+        let bytes = taffy.to_bytes(root).unwrap();
+
+        assert!(matches!(Taffy::from_bytes(b"not a tree"), Err(BinaryError::NotTaffyData)));
+        assert!(matches!(Taffy::from_bytes(b"TA"), Err(BinaryError::NotTaffyData)));
+
+        let mut newer = bytes.clone();
+        newer[4] = 2;
+        assert!(matches!(Taffy::from_bytes(&newer), Err(BinaryError::UnsupportedVersion { version: 2 })));
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(matches!(Taffy::from_bytes(truncated), Err(BinaryError::Malformed { .. })));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(
+            matches!(Taffy::from_bytes(&trailing), Err(BinaryError::Malformed { offset }) if offset == bytes.len())
+        );
+
+        let mut invalid_display = bytes;
+        invalid_display[6] = 9;
+        assert!(matches!(Taffy::from_bytes(&invalid_display), Err(BinaryError::Malformed { offset: 6 })));
+    }
+
+    #[test]
+    fn deep_trees_do_not_overflow_the_stack() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let leaf_bytes = taffy.to_bytes(leaf).unwrap();
+        let (header, style) = (&leaf_bytes[..6], &leaf_bytes[6..leaf_bytes.len() - 1]);
+
+        // A chain of nodes that each have a single child, far deeper than a recursive decoder could follow
+        let depth = 100_000;
+        let mut bytes = header.to_vec();
+        for _ in 0..depth {
+            bytes.extend_from_slice(style);
+            bytes.push(1);
+        }
+        bytes.extend_from_slice(style);
+        bytes.push(0);
+
+        let (restored, root) = Taffy::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.node_count(), depth + 1);
+        assert_eq!(restored.to_bytes(root).unwrap(), bytes);
+
+        // Cutting the chain short is reported rather than followed
+        assert!(matches!(Taffy::from_bytes(&bytes[..bytes.len() / 2]), Err(BinaryError::Malformed { .. })));
+    }
+
+    #[test]
+    fn cycles_are_not_encoded() {
+        let (mut taffy, root) = tree();
+        let leaf = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.add_child(root, leaf).unwrap();
+        taffy.add_child(leaf, root).unwrap();
+        assert!(matches!(taffy.to_bytes(root), Err(BinaryError::CycleDetected { .. })));
+    }
+}
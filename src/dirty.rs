@@ -0,0 +1,230 @@
+//! Size-aware dirtying, which avoids relaying out ancestors when a change does not affect the size of a node
+//!
+//! While enabled, dirtying a node no longer dirties its ancestors. Instead, the node is queued,
+//! and the next layout recomputes it using every set of constraints that its parent previously used.
+//! If each of these produces the same size as before, the parent could not observe the change,
+//! so only the node and its descendants are laid out again. Otherwise, the ancestors are dirtied as usual.
+use core::mem;
+
+use crate::forest::{Forest, NodeData};
+use crate::geometry::Size;
+use crate::node::NodeId;
+use crate::style::{AlignItems, FlexboxLayout, PositionType};
+use crate::sys::Vec;
+
+/// The most calls that are recorded for a single node before it stops being eligible for size-aware dirtying
+const MAX_RECORDED_CALLS: usize = 16;
+
+/// The inputs and result of a single request for the size of a node
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RecordedCall {
+    /// The size of the node, as provided by its parent
+    node_size: Size<Option<f32>>,
+    /// The size of the parent, as provided by its parent
+    parent_size: Size<Option<f32>>,
+    /// Was the node laid out, or only measured?
+    perform_layout: bool,
+    /// Which of the caches was used
+    main_size: bool,
+    /// The size that was returned
+    size: Size<f32>,
+}
+
+impl RecordedCall {
+    /// Were both calls made with identical inputs?
+    fn same_inputs(&self, other: &RecordedCall) -> bool {
+        self.node_size == other.node_size
+            && self.parent_size == other.parent_size
+            && self.perform_layout == other.perform_layout
+            && self.main_size == other.main_size
+    }
+}
+
+/// Does changing the style of a node from `old` to `new` only affect the layout of its children?
+///
+/// The parent of a node reads many of its style properties directly, so any other change must dirty the parent.
+pub(crate) fn only_affects_children(old: &FlexboxLayout, new: &FlexboxLayout) -> bool {
+    let old = FlexboxLayout {
+        flex_direction: new.flex_direction,
+        writing_mode: new.writing_mode,
+        direction: new.direction,
+        flex_wrap: new.flex_wrap,
+        align_items: new.align_items,
+        align_content: new.align_content,
+        justify_content: new.justify_content,
+        gap: new.gap,
+        ..*old
+    };
+    old == *new
+}
+
+impl NodeData {
+    /// Forgets every call recorded for this node
+    pub(crate) fn reset_recorded_calls(&mut self) {
+        self.recorded_calls = Some(Vec::new());
+    }
+}
+
+impl Forest {
+    /// Remembers that the size of the `node` was requested with the given inputs, and was found to be `size`
+    pub(crate) fn record_call(
+        &mut self,
+        node: NodeId,
+        node_size: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        perform_layout: bool,
+        main_size: bool,
+        size: Size<f32>,
+    ) {
+        if !self.size_aware_dirtying {
+            return;
+        }
+
+        let data = &mut self.nodes[node];
+        if let Some(calls) = &mut data.recorded_calls {
+            let call = RecordedCall { node_size, parent_size, perform_layout, main_size, size };
+            // Only the latest call with each set of inputs is kept, so that the calls can be replayed in order
+            calls.retain(|recorded| !recorded.same_inputs(&call));
+            if calls.len() < MAX_RECORDED_CALLS {
+                calls.push(call);
+            } else {
+                data.recorded_calls = None;
+            }
+        }
+    }
+
+    /// Marks only the `node` as dirty, and queues it to be checked during the next layout
+    pub(crate) fn mark_dirty_size_aware(&mut self, node: NodeId) {
+        let data = &mut self.nodes[node];
+        let calls = data.recorded_calls.take();
+        data.mark_dirty(&mut self.dirty_count);
+        data.recorded_calls = calls;
+
+        if !self.pending.contains(&node) {
+            self.pending.push(node);
+        }
+    }
+
+    /// Dirties the ancestors of every queued node, as size-aware dirtying is being turned off
+    pub(crate) fn flush_pending(&mut self) {
+        for node in mem::take(&mut self.pending) {
+            self.mark_dirty_with_ancestors(node);
+        }
+    }
+
+    /// Lays out each of the queued nodes again, dirtying its ancestors if its size has changed
+    pub(crate) fn layout_pending(&mut self) {
+        for node in mem::take(&mut self.pending) {
+            self.layout_pending_node(node);
+        }
+    }
+
+    /// Lays out queued nodes again, as with [`Forest::layout_pending`], as long as their subtrees fit in the `budget`
+    ///
+    /// The `budget` is reduced by the number of nodes in the subtree of each node that is laid out in place.
+    /// The first node that would be laid out always is, even if it does not fit, so that some progress is made.
+    /// Returns how many nodes were laid out. Nodes that are left over stay queued, in order.
+    pub(crate) fn layout_pending_within(&mut self, budget: &mut usize) -> usize {
+        let mut laid_out = 0;
+        while let Some(&node) = self.pending.first() {
+            let cost = if self.would_relayout_in_place(node) { self.subtree_len(node) } else { 0 };
+            if cost > *budget && laid_out > 0 {
+                break;
+            }
+            *budget = budget.saturating_sub(cost);
+            laid_out += cost;
+            self.pending.remove(0);
+            self.layout_pending_node(node);
+        }
+        laid_out
+    }
+
+    /// Would the queued `node` be laid out again by itself, rather than as part of its ancestors or not at all?
+    fn would_relayout_in_place(&self, node: NodeId) -> bool {
+        self.nodes[node].is_dirty && !self.has_dirty_ancestor(node) && self.can_relayout_in_place(node)
+    }
+
+    /// Returns the number of nodes in the tree below the `node`, including the `node` itself
+    fn subtree_len(&self, node: NodeId) -> usize {
+        let mut count = 0;
+        let mut stack: Vec<NodeId> = Vec::new();
+        stack.push(node);
+        while let Some(id) = stack.pop() {
+            count += 1;
+            stack.extend(self.children[id].iter().copied());
+        }
+        count
+    }
+
+    /// Lays out the queued `node` again, dirtying its ancestors if its size has changed
+    fn layout_pending_node(&mut self, node: NodeId) {
+        // The node was already laid out as part of one of its ancestors
+        if !self.nodes[node].is_dirty {
+            return;
+        }
+
+        // One of the ancestors will be laid out again, which will lay out this node too
+        if self.has_dirty_ancestor(node) {
+            return;
+        }
+
+        if !self.can_relayout_in_place(node) {
+            self.mark_dirty_with_ancestors(node);
+            return;
+        }
+
+        let calls = self.nodes[node].recorded_calls.clone().unwrap_or_default();
+        let unchanged = calls.iter().all(|call| {
+            let size =
+                self.compute_preliminary(node, call.node_size, call.parent_size, call.perform_layout, call.main_size);
+            size == call.size
+        });
+
+        if unchanged {
+            for child in self.children[node].clone() {
+                Self::round_layout(&mut self.nodes, &self.children, child, self.scale_factor, 0.0, 0.0);
+            }
+        } else {
+            self.mark_dirty_with_ancestors(node);
+        }
+    }
+
+    /// Is any ancestor of the `node` dirty?
+    fn has_dirty_ancestor(&self, node: NodeId) -> bool {
+        self.ancestors(node).any(|ancestor| self.nodes[ancestor].is_dirty)
+    }
+
+    /// Can the `node` be laid out again without its ancestors?
+    ///
+    /// This requires a single chain of ancestors, a full record of how the parent sized the node,
+    /// and that no ancestor aligns its children by their baselines, as baselines depend on descendants.
+    fn can_relayout_in_place(&self, node: NodeId) -> bool {
+        if self.parents[node].len() != 1 {
+            return false;
+        }
+        if !matches!(&self.nodes[node].recorded_calls, Some(calls) if !calls.is_empty()) {
+            return false;
+        }
+        // The absolutely positioned children of a static node are placed by one of its ancestors
+        if self.nodes[node].style.position_type == PositionType::Static {
+            return false;
+        }
+
+        let mut current = node;
+        for _ in 0..self.nodes.len() {
+            let parent = match self.parents[current].as_slice() {
+                [] => return true,
+                [parent] => *parent,
+                _ => return false,
+            };
+            let parent_style = &self.nodes[parent].style;
+            if matches!(parent_style.align_items, AlignItems::Baseline | AlignItems::LastBaseline)
+                || self.children[parent].iter().any(|child| self.nodes[*child].style.is_baseline_aligned(parent_style))
+            {
+                return false;
+            }
+            current = parent;
+        }
+        false
+    }
+}
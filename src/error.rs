@@ -34,6 +34,13 @@ pub enum InvalidChild {
     InvalidParentNode(Node),
     /// The child [`Node`] was not found in the [`Taffy`](crate::Taffy) instance.
     InvalidChildNode(Node),
+    /// The `ancestor` [`Node`] is not an ancestor of `node`
+    NotAnAncestor {
+        /// The node whose ancestors were searched
+        node: Node,
+        /// The node that was expected among them
+        ancestor: Node,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -49,9 +56,227 @@ impl Display for InvalidChild {
                 write!(f, "Parent Node {:?} is not in the Taffy instance", parent)
             }
             InvalidChild::InvalidChildNode(child) => write!(f, "Child Node {:?} is not in the Taffy instance", child),
+            InvalidChild::NotAnAncestor { node, ancestor } => {
+                write!(f, "Node {:?} is not an ancestor of node {:?}", ancestor, node)
+            }
         }
     }
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for InvalidChild {}
+
+/// An error that occurs while setting the style of a [`Node`]
+#[derive(Debug)]
+pub enum StyleError {
+    /// The [`Node`] was not found in the [`Taffy`](crate::Taffy) instance
+    InvalidNode(Node),
+    /// The numeric value of `field` was NaN or infinite
+    NonFinite {
+        /// The name of the offending field of the [`FlexboxLayout`](crate::style::FlexboxLayout), such as `"margin.top"`
+        field: &'static str,
+    },
+}
+
+impl From<InvalidNode> for StyleError {
+    fn from(error: InvalidNode) -> Self {
+        StyleError::InvalidNode(error.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for StyleError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            StyleError::InvalidNode(node) => write!(f, "Node {:?} is not in the Taffy instance", node),
+            StyleError::NonFinite { field } => write!(f, "Style field {} is not finite", field),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StyleError {}
+
+/// An error that occurs while computing the layout of a tree of [`Node`]s
+#[derive(Debug)]
+pub enum LayoutError {
+    /// The [`Node`] was not found in the [`Taffy`](crate::Taffy) instance
+    InvalidNode(Node),
+    /// The tree exceeds one of the limits set on the [`Taffy`](crate::Taffy) instance, so it was not laid out
+    LimitExceeded {
+        /// The name of the exceeded limit, either `"max_depth"` or `"max_nodes_per_pass"`
+        limit: &'static str,
+    },
+    /// The tree contains a cycle, so laying it out would never finish
+    CycleDetected {
+        /// A node of the cycle, which is one of its own ancestors
+        node: Node,
+    },
+    /// The numeric value of `field` of a style that was to be applied before the layout was NaN or infinite
+    NonFinite {
+        /// The name of the offending field of the [`FlexboxLayout`](crate::style::FlexboxLayout), such as `"margin.top"`
+        field: &'static str,
+    },
+}
+
+impl From<InvalidNode> for LayoutError {
+    fn from(error: InvalidNode) -> Self {
+        LayoutError::InvalidNode(error.0)
+    }
+}
+
+impl From<StyleError> for LayoutError {
+    fn from(error: StyleError) -> Self {
+        match error {
+            StyleError::InvalidNode(node) => LayoutError::InvalidNode(node),
+            StyleError::NonFinite { field } => LayoutError::NonFinite { field },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            LayoutError::InvalidNode(node) => write!(f, "Node {:?} is not in the Taffy instance", node),
+            LayoutError::LimitExceeded { limit } => write!(f, "The tree exceeds the {} limit", limit),
+            LayoutError::CycleDetected { node } => write!(f, "Node {:?} is its own ancestor", node),
+            LayoutError::NonFinite { field } => write!(f, "Style field {} is not finite", field),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutError {}
+
+/// An error that occurs while converting a tree of [`Node`]s to or from JSON
+#[cfg(all(feature = "serde", feature = "std"))]
+#[derive(Debug)]
+pub enum JsonError {
+    /// The [`Node`] was not found in the [`Taffy`](crate::Taffy) instance
+    InvalidNode(Node),
+    /// The numeric value of `field` was NaN or infinite in one of the styles
+    NonFinite {
+        /// The name of the offending field of the [`FlexboxLayout`](crate::style::FlexboxLayout)
+        field: &'static str,
+    },
+    /// The tree to be serialized contains a cycle, so it has no finite serialization
+    CycleDetected {
+        /// A node of the cycle, which is one of its own ancestors
+        node: Node,
+    },
+    /// The JSON could not be produced or parsed
+    Json(serde_json::Error),
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl From<InvalidNode> for JsonError {
+    fn from(error: InvalidNode) -> Self {
+        JsonError::InvalidNode(error.0)
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl From<StyleError> for JsonError {
+    fn from(error: StyleError) -> Self {
+        match error {
+            StyleError::InvalidNode(node) => JsonError::InvalidNode(node),
+            StyleError::NonFinite { field } => JsonError::NonFinite { field },
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl From<serde_json::Error> for JsonError {
+    fn from(error: serde_json::Error) -> Self {
+        JsonError::Json(error)
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            JsonError::InvalidNode(node) => write!(f, "Node {:?} is not in the Taffy instance", node),
+            JsonError::NonFinite { field } => write!(f, "Style field {} is not finite", field),
+            JsonError::CycleDetected { node } => write!(f, "Node {:?} is its own ancestor", node),
+            JsonError::Json(error) => write!(f, "Invalid JSON: {}", error),
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonError::InvalidNode(_) | JsonError::NonFinite { .. } | JsonError::CycleDetected { .. } => None,
+            JsonError::Json(error) => Some(error),
+        }
+    }
+}
+
+/// An error that occurs while converting a tree of [`Node`]s to or from bytes
+#[cfg(feature = "binary")]
+#[derive(Debug)]
+pub enum BinaryError {
+    /// The [`Node`] was not found in the [`Taffy`](crate::Taffy) instance
+    InvalidNode(Node),
+    /// The numeric value of `field` was NaN or infinite in one of the styles
+    NonFinite {
+        /// The name of the offending field of the [`FlexboxLayout`](crate::style::FlexboxLayout)
+        field: &'static str,
+    },
+    /// The bytes do not start with the header written by [`Taffy::to_bytes`](crate::Taffy::to_bytes)
+    NotTaffyData,
+    /// The bytes were written in a different version of the format, which this version cannot read
+    UnsupportedVersion {
+        /// The version of the format that the bytes were written in
+        version: u16,
+    },
+    /// The bytes are truncated, have trailing data, or contain an invalid value
+    Malformed {
+        /// The offset of the first byte that could not be decoded
+        offset: usize,
+    },
+    /// The tree to be encoded contains a cycle, so it has no finite encoding
+    CycleDetected {
+        /// A node of the cycle, which is one of its own ancestors
+        node: Node,
+    },
+}
+
+#[cfg(feature = "binary")]
+impl From<InvalidNode> for BinaryError {
+    fn from(error: InvalidNode) -> Self {
+        BinaryError::InvalidNode(error.0)
+    }
+}
+
+#[cfg(feature = "binary")]
+impl From<StyleError> for BinaryError {
+    fn from(error: StyleError) -> Self {
+        match error {
+            StyleError::InvalidNode(node) => BinaryError::InvalidNode(node),
+            StyleError::NonFinite { field } => BinaryError::NonFinite { field },
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+impl Display for BinaryError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            BinaryError::InvalidNode(node) => write!(f, "Node {:?} is not in the Taffy instance", node),
+            BinaryError::NonFinite { field } => write!(f, "Style field {} is not finite", field),
+            BinaryError::NotTaffyData => write!(f, "The bytes do not contain a Taffy tree"),
+            BinaryError::UnsupportedVersion { version } => {
+                write!(f, "Version {} of the format is not supported", version)
+            }
+            BinaryError::Malformed { offset } => write!(f, "The bytes could not be decoded at offset {}", offset),
+            BinaryError::CycleDetected { node } => write!(f, "Node {:?} is its own ancestor", node),
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+impl std::error::Error for BinaryError {}
@@ -4,13 +4,13 @@
 use core::f32;
 
 use crate::forest::{Forest, NodeData};
-use crate::geometry::{Point, Rect, Size};
+use crate::geometry::{AvailableSpace, Point, Rect, Size};
 use crate::layout::{Cache, Layout};
 use crate::math::MaybeMath;
-use crate::node::{MeasureFunc, NodeId};
+use crate::node::{CacheMode, NodeId};
 use crate::style::{AlignContent, AlignSelf, Dimension, Display, FlexWrap, JustifyContent, PositionType};
 use crate::style::{FlexDirection, FlexboxLayout};
-use crate::sys::{abs, round, ChildrenVec, Vec};
+use crate::sys::{abs, ceil, round, ChildrenVec, Vec};
 
 /// The intermediate results of a flexbox calculation for a single item
 struct FlexItem {
@@ -53,6 +53,8 @@ struct FlexItem {
 
     /// The position of the bottom edge of this item
     baseline: f32,
+    /// The position of the last baseline of this item, which is further down than its `baseline` if it has several lines
+    last_baseline: f32,
 
     /// A temporary value for the main offset
     ///
@@ -76,6 +78,15 @@ struct FlexLine<'a> {
     offset_cross: f32,
 }
 
+/// The baselines that the baseline-aligned items of a flex line are aligned to
+#[derive(Debug, Clone, Copy)]
+struct LineBaselines {
+    /// The largest first baseline of the items
+    first: f32,
+    /// The largest distance between the last baseline of an item aligned by it and its cross-end edge
+    last_descent: f32,
+}
+
 /// Values that can be cached during the flexbox algorithm
 struct AlgoConstants {
     /// The direction of the current segment being layed out
@@ -94,6 +105,9 @@ struct AlgoConstants {
     /// The padding of this section
     padding_border: Rect<f32>,
 
+    /// The gaps between the columns and rows of this section
+    gap: Size<f32>,
+
     /// The size of the internal node
     node_inner_size: Size<Option<f32>>,
     /// The size of the surrounding container
@@ -102,62 +116,161 @@ struct AlgoConstants {
     inner_container_size: Size<f32>,
 }
 
+impl AlgoConstants {
+    /// The total size of the gaps between `count` items along the main axis
+    fn main_gaps(&self, count: usize) -> f32 {
+        self.gap.main(self.dir) * count.saturating_sub(1) as f32
+    }
+
+    /// The main-axis space left over in the `line` after placing its items, before any auto margins absorb it
+    fn free_space(&self, line: &FlexLine) -> f32 {
+        let used_space: f32 = self.main_gaps(line.items.len())
+            + line.items.iter().map(|child| child.outer_target_size.main(self.dir)).sum::<f32>();
+        self.inner_container_size.main(self.dir) - used_space
+    }
+
+    /// The total size of the gaps between `count` lines along the cross axis
+    fn cross_gaps(&self, count: usize) -> f32 {
+        self.gap.cross(self.dir) * count.saturating_sub(1) as f32
+    }
+}
+
 impl Forest {
     /// Computes the layout of this [`Forest`] according to the flexbox algorithm
-    pub(crate) fn compute(&mut self, root: NodeId, size: Size<Option<f32>>) {
+    ///
+    /// The percentage sizes of the `root` are resolved against the `percentage_base`.
+    pub(crate) fn compute(&mut self, root: NodeId, size: Size<Option<f32>>, percentage_base: Size<Option<f32>>) {
+        self.layout_root = Some(root);
+        let preliminary_size = self.compute_root_size(root, size, percentage_base, true);
+        self.layout_root = None;
+
+        self.nodes[root].layout = Layout { order: 0, size: preliminary_size, location: Point::zero() };
+        self.nodes[root].alignment_offset = Point::zero();
+        self.nodes[root].growth_delta = 0.0;
+        self.nodes[root].margin = self.nodes[root].style.margin.map(|m| m.resolve(size.width).unwrap_or(0.0));
+
+        Self::round_layout(&mut self.nodes, &self.children, root, self.scale_factor, 0.0, 0.0);
+    }
+
+    /// Computes only the size of the `root`, without positioning any of its descendants
+    ///
+    /// The rounded size is stored in the layout of the `root`, and every other layout is left untouched.
+    /// As no layout is completed, the dirty flag of each node is left as it was.
+    pub(crate) fn compute_size(&mut self, root: NodeId, size: Size<Option<f32>>) {
+        self.nodes[root].layout.size = self.measure_root(root, size);
+    }
+
+    /// Computes the rounded size of the `root` without positioning any of its descendants or storing any layout
+    ///
+    /// As no layout is completed, the dirty flag of each node is left as it was.
+    pub(crate) fn measure_root(&mut self, root: NodeId, size: Size<Option<f32>>) -> Size<f32> {
+        let dirty: Vec<bool> = self.nodes.iter().map(|data| data.is_dirty).collect();
+        let preliminary_size = self.compute_root_size(root, size, size, false);
+        for (data, is_dirty) in self.nodes.iter_mut().zip(dirty) {
+            data.set_dirty(is_dirty, &mut self.dirty_count);
+        }
+
+        let snap = |value: f32| (round(value * self.scale_factor) / self.scale_factor).max(0.0);
+        preliminary_size.map(snap)
+    }
+
+    /// Computes the size of the `root`, applying its own minimum and maximum sizes
+    ///
+    /// The percentages of these sizes are resolved against the `percentage_base`.
+    fn compute_root_size(
+        &mut self,
+        root: NodeId,
+        size: Size<Option<f32>>,
+        percentage_base: Size<Option<f32>>,
+        perform_layout: bool,
+    ) -> Size<f32> {
         let style = self.nodes[root].style;
         let has_root_min_max = style.min_size.width.is_defined()
             || style.min_size.height.is_defined()
             || style.max_size.width.is_defined()
             || style.max_size.height.is_defined();
 
-        let preliminary_size = if has_root_min_max {
-            let first_pass = self.compute_preliminary(root, style.size.resolve(size), size, false, true);
+        let node_size = style.resolve_box_size(style.size, percentage_base);
+
+        if has_root_min_max {
+            let first_pass = self.compute_preliminary(root, node_size, size, false, true);
+            let min_size = style.resolve_box_size(style.min_size, percentage_base);
+            let max_size = style.resolve_box_size(style.max_size, percentage_base);
 
             self.compute_preliminary(
                 root,
                 Size {
-                    width: first_pass
-                        .width
-                        .maybe_max(style.min_size.width.resolve(size.width))
-                        .maybe_min(style.max_size.width.resolve(size.width))
-                        .into(),
-                    height: first_pass
-                        .height
-                        .maybe_max(style.min_size.height.resolve(size.height))
-                        .maybe_min(style.max_size.height.resolve(size.height))
-                        .into(),
+                    width: first_pass.width.maybe_max(min_size.width).maybe_min(max_size.width).into(),
+                    height: first_pass.height.maybe_max(min_size.height).maybe_min(max_size.height).into(),
                 },
                 size,
-                true,
+                perform_layout,
                 true,
             )
         } else {
-            self.compute_preliminary(root, style.size.resolve(size), size, true, true)
-        };
-
-        self.nodes[root].layout = Layout { order: 0, size: preliminary_size, location: Point::zero() };
-
-        Self::round_layout(&mut self.nodes, &self.children, root, 0.0, 0.0);
+            self.compute_preliminary(root, node_size, size, perform_layout, true)
+        }
     }
 
     /// Rounds the calculated [`NodeData`] according to the spec
-    fn round_layout(nodes: &mut [NodeData], children: &[ChildrenVec<NodeId>], root: NodeId, abs_x: f32, abs_y: f32) {
+    ///
+    /// Values are rounded to the nearest multiple of `1 / scale_factor`, so that they land on the physical pixel grid.
+    /// Negative sizes, which can be produced by a misbehaving [`MeasureFunc`](crate::node::MeasureFunc) or by negative styles, are clamped to zero.
+    pub(crate) fn round_layout(
+        nodes: &mut [NodeData],
+        children: &[ChildrenVec<NodeId>],
+        root: NodeId,
+        scale_factor: f32,
+        abs_x: f32,
+        abs_y: f32,
+    ) {
+        let snap = |value: f32| round(value * scale_factor) / scale_factor;
         let layout = &mut nodes[root].layout;
         let abs_x = abs_x + layout.location.x;
         let abs_y = abs_y + layout.location.y;
 
-        layout.location.x = round(layout.location.x);
-        layout.location.y = round(layout.location.y);
+        layout.location.x = snap(layout.location.x);
+        layout.location.y = snap(layout.location.y);
 
-        layout.size.width = round(layout.size.width);
-        layout.size.height = round(layout.size.height);
+        layout.size.width = snap(layout.size.width).max(0.0);
+        layout.size.height = snap(layout.size.height).max(0.0);
 
         for child in &children[root] {
-            Self::round_layout(nodes, children, *child, abs_x, abs_y);
+            Self::round_layout(nodes, children, *child, scale_factor, abs_x, abs_y);
         }
     }
 
+    /// Rounds a size returned by a [`MeasureFunc`](crate::node::MeasureFunc) up to the next multiple of `1 / scale_factor`, if measure snapping is enabled
+    fn snap_measured_size(&self, size: Size<f32>) -> Size<f32> {
+        if self.measure_snapping {
+            size.map(|value| ceil(value * self.scale_factor) / self.scale_factor)
+        } else {
+            size
+        }
+    }
+
+    /// Asks the [`MeasureFunc`](crate::node::MeasureFunc) of the `node` for its min-content size along the main axis of `dir`,
+    /// given its `cross` size if it is known
+    ///
+    /// The min-content size is what the measure function reports when given no space at all along the main axis.
+    /// It is called directly, as a node whose size is known on both axes would otherwise not be measured at all.
+    /// The result kept by [`CacheMode::OncePermanent`] is used instead, if there is one.
+    fn measure_min_content(&mut self, node: NodeId, dir: FlexDirection, cross: Option<f32>) -> f32 {
+        let measure = match (&self.nodes[node].measure, self.nodes[node].permanent_measure) {
+            (_, Some(size)) => return self.snap_measured_size(size).main(dir),
+            (Some(measure), None) => measure,
+            (None, None) => return 0.0,
+        };
+        let mut available = Size::square(AvailableSpace::MaxContent);
+        available.set_main(dir, AvailableSpace::Definite(0.0));
+        available.set_cross(dir, cross.into());
+        let size = self.snap_measured_size(measure.measure(available));
+        self.measure_count += 1;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.observe(node, |observer, node| observer.on_measure(node, available, size));
+        size.main(dir)
+    }
+
     /// Saves intermediate results to a [`Cache`]
     fn cache(&mut self, node: NodeId, main_size: bool) -> &mut Option<Cache> {
         if main_size {
@@ -177,8 +290,9 @@ impl Forest {
         perform_layout: bool,
         main_size: bool,
     ) -> Option<Size<f32>> {
+        let measure_version = self.nodes[node].measure_version;
         if let Some(ref cache) = self.cache(node, main_size) {
-            if cache.perform_layout || !perform_layout {
+            if cache.measure_version == measure_version && (cache.perform_layout || !perform_layout) {
                 let width_compatible = if let Some(width) = node_size.width {
                     abs(width - cache.size.width) < f32::EPSILON
                 } else {
@@ -204,6 +318,37 @@ impl Forest {
         None
     }
 
+    /// The main size of the `child` of the `node`, as determined by its aspect ratio and its cross size, if both are known
+    ///
+    /// The cross size is known if it is set in the style of the `child`, or if the `child` is stretched to a definite size.
+    /// The cross size is clamped by the min and max cross sizes of the `child` before being transferred,
+    /// so that a min or max cross size also limits the main size the ratio produces.
+    /// The aspect ratio in the style of the `child` takes precedence over the intrinsic one of its [`MeasureFunc`](crate::node::MeasureFunc).
+    fn transferred_main_size(&self, node: NodeId, child: &FlexItem, constants: &AlgoConstants) -> Option<f32> {
+        let child_data = &self.nodes[child.node];
+        let ratio = child_data.style.aspect_ratio.or_else(|| {
+            let measure = child_data.measure.as_ref()?;
+            child_data.intrinsic_aspect_ratio.or_else(|| measure.aspect_ratio())
+        })?;
+
+        let node_style = &self.nodes[node].style;
+        let cross = child.size.cross(constants.dir).or_else(|| {
+            // Stretched items of a single-line container take on its definite cross size
+            if node_style.flex_wrap == FlexWrap::NoWrap
+                && child_data.style.align_self(node_style) == AlignSelf::Stretch
+                && child_data.style.cross_margin_start(constants.dir) != Dimension::Auto
+                && child_data.style.cross_margin_end(constants.dir) != Dimension::Auto
+            {
+                constants.node_inner_size.cross(constants.dir).maybe_sub(child.margin.cross_axis_sum(constants.dir))
+            } else {
+                None
+            }
+        })?;
+        let cross = cross.maybe_max(child.min_size.cross(constants.dir)).maybe_min(child.max_size.cross(constants.dir));
+
+        Some(if constants.is_row { cross * ratio } else { cross / ratio })
+    }
+
     /// Compute constants that can be reused during the flexbox algorithm.
     #[inline]
     fn compute_constants(
@@ -212,10 +357,13 @@ impl Forest {
         node_size: Size<Option<f32>>,
         parent_size: Size<Option<f32>>,
     ) -> AlgoConstants {
-        let dir = self.nodes[node].style.flex_direction;
+        let style = &self.nodes[node].style;
+        let dir = style.writing_mode.physical_direction(style.flex_direction);
         let is_row = dir.is_row();
         let is_column = dir.is_column();
-        let is_wrap_reverse = self.nodes[node].style.flex_wrap == FlexWrap::WrapReverse;
+        // Lines that stack against the physical cross axis are laid out as if they wrapped in reverse
+        let is_wrap_reverse =
+            (style.flex_wrap == FlexWrap::WrapReverse) != style.writing_mode.reverses_cross_axis(style.flex_direction);
 
         let margin = self.nodes[node].style.margin.map(|n| n.resolve(parent_size.width).unwrap_or(0.0));
         let padding = self.nodes[node].style.padding.map(|n| n.resolve(parent_size.width).unwrap_or(0.0));
@@ -233,6 +381,11 @@ impl Forest {
             height: node_size.height.maybe_sub(padding_border.vertical_axis_sum()),
         };
 
+        let gap = Size {
+            width: self.nodes[node].style.gap.width.resolve(node_inner_size.width).unwrap_or(0.0),
+            height: self.nodes[node].style.gap.height.resolve(node_inner_size.height).unwrap_or(0.0),
+        };
+
         let container_size = Size::zero();
         let inner_container_size = Size::zero();
 
@@ -244,6 +397,7 @@ impl Forest {
             margin,
             border,
             padding_border,
+            gap,
             node_inner_size,
             container_size,
             inner_container_size,
@@ -264,11 +418,14 @@ impl Forest {
             .filter(|(_, style)| style.display != Display::None)
             .map(|(child, child_style)| FlexItem {
                 node: *child,
-                size: child_style.size.resolve(constants.node_inner_size),
-                min_size: child_style.min_size.resolve(constants.node_inner_size),
-                max_size: child_style.max_size.resolve(constants.node_inner_size),
+                size: child_style.resolve_box_size(child_style.size, constants.node_inner_size),
+                min_size: child_style.resolve_box_size(child_style.min_size, constants.node_inner_size),
+                max_size: child_style.resolve_box_size(child_style.max_size, constants.node_inner_size),
 
-                position: child_style.position.zip_size(constants.node_inner_size, |p, s| p.resolve(s)),
+                position: match child_style.position_type {
+                    PositionType::Static => Rect { start: None, end: None, top: None, bottom: None },
+                    _ => child_style.position.zip_size(constants.node_inner_size, |p, s| p.resolve(s)),
+                },
                 margin: child_style.margin.map(|m| m.resolve(constants.node_inner_size.width).unwrap_or(0.0)),
                 padding: child_style.padding.map(|p| p.resolve(constants.node_inner_size.width).unwrap_or(0.0)),
                 border: child_style.border.map(|b| b.resolve(constants.node_inner_size.width).unwrap_or(0.0)),
@@ -284,6 +441,7 @@ impl Forest {
                 outer_target_size: Size::zero(),
 
                 baseline: 0.0,
+                last_baseline: 0.0,
 
                 offset_main: 0.0,
                 offset_cross: 0.0,
@@ -357,7 +515,6 @@ impl Forest {
     fn determine_flex_base_size(
         &mut self,
         node: NodeId,
-        node_size: Size<Option<f32>>,
         constants: &AlgoConstants,
         available_space: Size<Option<f32>>,
         flex_items: &mut Vec<FlexItem>,
@@ -379,12 +536,10 @@ impl Forest {
             //    then the flex base size is calculated from its inner
             //    cross size and the flex item’s intrinsic aspect ratio.
 
-            if let Some(ratio) = child_style.aspect_ratio {
-                if let Some(cross) = node_size.cross(constants.dir) {
-                    if child_style.flex_basis == Dimension::Auto {
-                        child.flex_basis = cross * ratio;
-                        continue;
-                    }
+            if matches!(child_style.flex_basis, Dimension::Auto | Dimension::Content) {
+                if let Some(main) = self.transferred_main_size(node, child, constants) {
+                    child.flex_basis = main;
+                    continue;
                 }
             }
 
@@ -429,12 +584,18 @@ impl Forest {
                 child.size.height
             };
 
+            let mut size = Size { width, height };
+            // A flex basis of content sizes the item as if its main size were auto
+            if child_style.flex_basis == Dimension::Content {
+                size.set_main(constants.dir, None);
+            }
+
             child.flex_basis = self
                 .compute_preliminary(
                     child.node,
                     Size {
-                        width: width.maybe_min(child.max_size.width),
-                        height: height.maybe_min(child.max_size.height),
+                        width: size.width.maybe_min(child.max_size.width),
+                        height: size.height.maybe_min(child.max_size.height),
                     },
                     available_space,
                     false,
@@ -456,9 +617,11 @@ impl Forest {
             // The following logic was developed not from the spec but by trail and error looking into how
             // webkit handled various scenarios. Can probably be solved better by passing in
             // min-content max-content constraints from the top
+            // Items with an aspect ratio can also be shrunk down to the size transferred through their ratio
             let min_main = self
                 .compute_preliminary(child.node, Size::undefined(), available_space, false, false)
                 .main(constants.dir)
+                .maybe_min(self.transferred_main_size(node, child, constants))
                 .maybe_max(child.min_size.main(constants.dir))
                 .maybe_min(child.size.main(constants.dir))
                 .into();
@@ -513,7 +676,8 @@ impl Forest {
                     .iter()
                     .enumerate()
                     .find(|&(idx, child)| {
-                        line_length += child.hypothetical_outer_size.main(constants.dir);
+                        let gap = if idx == 0 { 0.0 } else { constants.gap.main(constants.dir) };
+                        line_length += gap + child.hypothetical_outer_size.main(constants.dir);
                         if let Some(main) = available_space.main(constants.dir) {
                             line_length > main && idx != 0
                         } else {
@@ -547,8 +711,9 @@ impl Forest {
         //    use the flex grow factor for the rest of this algorithm; otherwise, use the
         //    flex shrink factor.
 
+        let total_gap = constants.main_gaps(line.items.len());
         let used_flex_factor: f32 =
-            line.items.iter().map(|child| child.hypothetical_outer_size.main(constants.dir)).sum();
+            total_gap + line.items.iter().map(|child| child.hypothetical_outer_size.main(constants.dir)).sum::<f32>();
         let growing = used_flex_factor < constants.node_inner_size.main(constants.dir).unwrap_or(0.0);
         let shrinking = !growing;
 
@@ -563,13 +728,18 @@ impl Forest {
             // TODO - This is not found by reading the spec. Maybe this can be done in some other place
             // instead. This was found by trail and error fixing tests to align with webkit output.
             if constants.node_inner_size.main(constants.dir).is_none() && constants.is_row {
+                let mut size = child.size;
+                // A flex basis of content sizes the item as if its main size were auto
+                if self.nodes[child.node].style.flex_basis == Dimension::Content {
+                    size.set_main(constants.dir, None);
+                }
                 child.target_size.set_main(
                     constants.dir,
                     self.compute_preliminary(
                         child.node,
                         Size {
-                            width: child.size.width.maybe_max(child.min_size.width).maybe_min(child.max_size.width),
-                            height: child.size.height.maybe_max(child.min_size.height).maybe_min(child.max_size.height),
+                            width: size.width.maybe_max(child.min_size.width).maybe_min(child.max_size.width),
+                            height: size.height.maybe_max(child.min_size.height).maybe_min(child.max_size.height),
                         },
                         available_space,
                         false,
@@ -604,14 +774,15 @@ impl Forest {
         //    and subtract this from the flex container’s inner main size. For frozen items,
         //    use their outer target main size; for other items, use their outer flex base size.
 
-        let used_space: f32 = line
-            .items
-            .iter()
-            .map(|child| {
-                child.margin.main_axis_sum(constants.dir)
-                    + if child.frozen { child.target_size.main(constants.dir) } else { child.flex_basis }
-            })
-            .sum();
+        let used_space: f32 = total_gap
+            + line
+                .items
+                .iter()
+                .map(|child| {
+                    child.margin.main_axis_sum(constants.dir)
+                        + if child.frozen { child.target_size.main(constants.dir) } else { child.flex_basis }
+                })
+                .sum::<f32>();
 
         let initial_free_space = constants.node_inner_size.main(constants.dir).maybe_sub(used_space).unwrap_or(0.0);
 
@@ -631,14 +802,15 @@ impl Forest {
             //    value is less than the magnitude of the remaining free space, use this
             //    as the remaining free space.
 
-            let used_space: f32 = line
-                .items
-                .iter()
-                .map(|child| {
-                    child.margin.main_axis_sum(constants.dir)
-                        + if child.frozen { child.target_size.main(constants.dir) } else { child.flex_basis }
-                })
-                .sum();
+            let used_space: f32 = total_gap
+                + line
+                    .items
+                    .iter()
+                    .map(|child| {
+                        child.margin.main_axis_sum(constants.dir)
+                            + if child.frozen { child.target_size.main(constants.dir) } else { child.flex_basis }
+                    })
+                    .sum::<f32>();
 
             let mut unfrozen: Vec<&mut FlexItem> = line.items.iter_mut().filter(|child| !child.frozen).collect();
 
@@ -715,7 +887,16 @@ impl Forest {
                 // webkit handled various scenarios. Can probably be solved better by passing in
                 // min-content max-content constraints from the top. Need to figure out correct thing to do here as
                 // just piling on more conditionals.
-                let min_main = if constants.is_row && self.nodes[child.node].measure.is_none() {
+                let min_main = if child.target_size.main(constants.dir) < child.flex_basis
+                    && self.nodes[child.node].measure.is_some()
+                    && self.nodes[child.node].style.min_size.main(constants.dir) == Dimension::Auto
+                {
+                    // The automatic minimum size of measured content is its min-content size.
+                    // It can only matter once the item has shrunk, so other items skip the extra measurement
+                    self.measure_min_content(child.node, constants.dir, child.size.cross(constants.dir))
+                        .maybe_min(child.size.main(constants.dir))
+                        .into()
+                } else if constants.is_row && self.nodes[child.node].measure.is_none() {
                     self.compute_preliminary(child.node, Size::undefined(), available_space, false, false)
                         .width
                         .maybe_min(child.size.width)
@@ -832,6 +1013,17 @@ impl Forest {
             }
         }
 
+        /// Recursively calculates the last baseline for children, following the last child down to a leaf
+        fn calc_last_baseline(db: &Forest, node: NodeId, layout: &Layout) -> f32 {
+            match db.children[node].last() {
+                None => layout.size.height,
+                Some(&child) => {
+                    let child_layout = &db.nodes[child].layout;
+                    child_layout.location.y + calc_last_baseline(db, child, child_layout)
+                }
+            }
+        }
+
         for line in flex_lines {
             for child in line.items.iter_mut() {
                 let preliminary_size = self.compute_preliminary(
@@ -860,15 +1052,13 @@ impl Forest {
                     false,
                 );
 
-                child.baseline = calc_baseline(
-                    self,
-                    child.node,
-                    &Layout {
-                        order: self.children[node].iter().position(|n| *n == child.node).unwrap() as u32,
-                        size: preliminary_size,
-                        location: Point::zero(),
-                    },
-                );
+                let layout = Layout {
+                    order: self.children[node].iter().position(|n| *n == child.node).unwrap() as u32,
+                    size: preliminary_size,
+                    location: Point::zero(),
+                };
+                child.baseline = calc_baseline(self, child.node, &layout);
+                child.last_baseline = calc_last_baseline(self, child.node, &layout);
             }
         }
     }
@@ -920,19 +1110,26 @@ impl Forest {
                 //       previous two steps and zero.
 
                 let max_baseline: f32 = line.items.iter().map(|child| child.baseline).fold(0.0, |acc, x| acc.max(x));
+                // Items aligned by their last baseline all extend this far below it
+                let max_last_descent =
+                    self.max_last_descent(line, node, constants.dir, |child| child.hypothetical_outer_size);
                 line.cross_size = line
                     .items
                     .iter()
                     .map(|child| {
                         let child_style = &self.nodes[child.node].style;
-                        if child_style.align_self(&self.nodes[node].style) == AlignSelf::Baseline
-                            && child_style.cross_margin_start(constants.dir) != Dimension::Auto
+                        let collected = child_style.cross_margin_start(constants.dir) != Dimension::Auto
                             && child_style.cross_margin_end(constants.dir) != Dimension::Auto
-                            && child_style.cross_size(constants.dir) == Dimension::Auto
-                        {
-                            max_baseline - child.baseline + child.hypothetical_outer_size.cross(constants.dir)
-                        } else {
-                            child.hypothetical_outer_size.cross(constants.dir)
+                            && child_style.cross_size(constants.dir) == Dimension::Auto;
+                        match child_style.align_self(&self.nodes[node].style) {
+                            AlignSelf::Baseline if collected => {
+                                max_baseline - child.baseline + child.hypothetical_outer_size.cross(constants.dir)
+                            }
+                            // Columns align last baselines as flex-end, so they need no extra room
+                            AlignSelf::LastBaseline if collected && constants.is_row => {
+                                child.last_baseline + max_last_descent
+                            }
+                            _ => child.hypothetical_outer_size.cross(constants.dir),
                         }
                     })
                     .fold(0.0, |acc, x| acc.max(x));
@@ -956,7 +1153,8 @@ impl Forest {
         constants: &AlgoConstants,
     ) {
         if self.nodes[node].style.align_content == AlignContent::Stretch && node_size.cross(constants.dir).is_some() {
-            let total_cross: f32 = flex_lines.iter().map(|line| line.cross_size).sum();
+            let total_cross: f32 =
+                constants.cross_gaps(flex_lines.len()) + flex_lines.iter().map(|line| line.cross_size).sum::<f32>();
             let inner_cross =
                 (node_size.cross(constants.dir).maybe_sub(constants.padding_border.cross_axis_sum(constants.dir)))
                     .unwrap_or(0.0);
@@ -1028,8 +1226,7 @@ impl Forest {
         constants: &AlgoConstants,
     ) {
         for line in flex_lines {
-            let used_space: f32 = line.items.iter().map(|child| child.outer_target_size.main(constants.dir)).sum();
-            let free_space = constants.inner_container_size.main(constants.dir) - used_space;
+            let free_space = constants.free_space(line);
             let mut num_auto_margins = 0;
 
             for child in line.items.iter_mut() {
@@ -1069,7 +1266,11 @@ impl Forest {
                 let justify_item = |(i, child): (usize, &mut FlexItem)| {
                     let is_first = i == 0;
 
-                    child.offset_main = match self.nodes[node].style.justify_content {
+                    child.offset_main = match self.nodes[node]
+                        .style
+                        .justify_content
+                        .resolve(self.nodes[node].style.flex_direction, self.nodes[node].style.direction)
+                    {
                         JustifyContent::FlexStart => {
                             if layout_reverse && is_first {
                                 free_space
@@ -1106,6 +1307,7 @@ impl Forest {
                             }
                         }
                         JustifyContent::SpaceEvenly => free_space / (num_items + 1) as f32,
+                        JustifyContent::Start | JustifyContent::End => 0.0, // Should never happen
                     };
                 };
 
@@ -1139,7 +1341,10 @@ impl Forest {
     ) {
         for line in flex_lines {
             let line_cross_size = line.cross_size;
-            let max_baseline: f32 = line.items.iter_mut().map(|child| child.baseline).fold(0.0, |acc, x| acc.max(x));
+            let baselines = LineBaselines {
+                first: line.items.iter().map(|child| child.baseline).fold(0.0, |acc, x| acc.max(x)),
+                last_descent: self.max_last_descent(line, node, constants.dir, |child| child.outer_target_size),
+            };
 
             for child in line.items.iter_mut() {
                 let free_space = line_cross_size - child.outer_target_size.cross(constants.dir);
@@ -1174,7 +1379,7 @@ impl Forest {
                         child,
                         child_style,
                         free_space,
-                        max_baseline,
+                        baselines,
                         constants,
                     );
                 }
@@ -1195,7 +1400,7 @@ impl Forest {
         child: &mut FlexItem,
         child_style: &FlexboxLayout,
         free_space: f32,
-        max_baseline: f32,
+        baselines: LineBaselines,
         constants: &AlgoConstants,
     ) -> f32 {
         match child_style.align_self(&self.nodes[node].style) {
@@ -1217,7 +1422,7 @@ impl Forest {
             AlignSelf::Center => free_space / 2.0,
             AlignSelf::Baseline => {
                 if constants.is_row {
-                    max_baseline - child.baseline
+                    baselines.first - child.baseline
                 } else {
                     // baseline alignment only makes sense if the constants.direction is row
                     // we treat it as flex-start alignment in columns.
@@ -1235,9 +1440,35 @@ impl Forest {
                     0.0
                 }
             }
+            AlignSelf::LastBaseline => {
+                let flex_end = if constants.is_wrap_reverse { 0.0 } else { free_space };
+                if constants.is_row {
+                    let descent = child.outer_target_size.cross(constants.dir) - child.last_baseline;
+                    flex_end - (baselines.last_descent - descent)
+                } else {
+                    // As with first baselines, this only makes sense in rows, so columns fall back to flex-end alignment
+                    flex_end
+                }
+            }
         }
     }
 
+    /// The largest distance between the last baseline and the cross-end edge of the items of the `line`
+    /// that are aligned by their last baseline, where `outer_size` is the outer size of an item
+    fn max_last_descent(
+        &self,
+        line: &FlexLine,
+        node: NodeId,
+        dir: FlexDirection,
+        outer_size: impl Fn(&FlexItem) -> Size<f32>,
+    ) -> f32 {
+        line.items
+            .iter()
+            .filter(|child| self.nodes[child.node].style.align_self(&self.nodes[node].style) == AlignSelf::LastBaseline)
+            .map(|child| outer_size(child).cross(dir) - child.last_baseline)
+            .fold(0.0, |acc, x| acc.max(x))
+    }
+
     /// Determine the flex container’s used cross size.
     ///
     /// # [9.6. Cross-Axis Alignment](https://www.w3.org/TR/css-flexbox-1/#cross-alignment)
@@ -1254,7 +1485,8 @@ impl Forest {
         node_size: Size<Option<f32>>,
         constants: &mut AlgoConstants,
     ) -> f32 {
-        let total_cross_size: f32 = flex_lines.iter().map(|line| line.cross_size).sum();
+        let total_cross_size: f32 =
+            constants.cross_gaps(flex_lines.len()) + flex_lines.iter().map(|line| line.cross_size).sum::<f32>();
 
         constants.container_size.set_cross(
             constants.dir,
@@ -1342,8 +1574,26 @@ impl Forest {
     fn final_layout_pass(&mut self, node: NodeId, flex_lines: &mut [FlexLine], constants: &AlgoConstants) {
         let mut total_offset_cross = constants.padding_border.cross_start(constants.dir);
 
+        // The sizes of the items are fixed at this point, so their subtrees can be laid out independently.
+        // The sequential pass below then retrieves the results from the cache.
+        #[cfg(feature = "parallel")]
+        {
+            let subtrees: Vec<_> = flex_lines
+                .iter()
+                .flat_map(|line| line.items.iter())
+                .map(|child| {
+                    (child.node, child.target_size.map(|s| s.into()), constants.container_size.map(|s| s.into()))
+                })
+                .collect();
+            self.layout_subtrees_in_parallel(&subtrees);
+        }
+
+        // The distance that the alignment properties have moved the current line, and the current item within its line
+        let mut total_alignment_cross = 0.0;
+
         let layout_line = |line: &mut FlexLine| {
             let mut total_offset_main = constants.padding_border.main_start(constants.dir);
+            let mut total_alignment_main = 0.0;
             let line_offset_cross = line.offset_cross;
 
             let layout_item = |child: &mut FlexItem| {
@@ -1377,9 +1627,19 @@ impl Forest {
                     },
                 };
 
+                total_alignment_main += child.offset_main;
+                let alignment_cross = total_alignment_cross + line_offset_cross + child.offset_cross;
+                self.nodes[child.node].alignment_offset = Point {
+                    x: if constants.is_row { total_alignment_main } else { alignment_cross },
+                    y: if constants.is_column { total_alignment_main } else { alignment_cross },
+                };
+                self.nodes[child.node].growth_delta = child.target_size.main(constants.dir) - child.flex_basis;
+                self.nodes[child.node].margin = child.margin;
+
                 total_offset_main += child.offset_main
                     + child.margin.main_axis_sum(constants.dir)
-                    + preliminary_size.main(constants.dir);
+                    + preliminary_size.main(constants.dir)
+                    + constants.gap.main(constants.dir);
             };
 
             if constants.dir.is_reverse() {
@@ -1388,7 +1648,8 @@ impl Forest {
                 line.items.iter_mut().for_each(layout_item);
             }
 
-            total_offset_cross += line_offset_cross + line.cross_size;
+            total_offset_cross += line_offset_cross + line.cross_size + constants.gap.cross(constants.dir);
+            total_alignment_cross += line_offset_cross;
         };
 
         if constants.is_wrap_reverse {
@@ -1425,34 +1686,30 @@ impl Forest {
             let end = child_position_end.maybe_add(child_margin_end);
 
             // Y-axis
+            // Like every other margin, vertical margins are resolved against the width of the container
             let child_position_top = child_style.position.top.resolve(container_height);
-            let child_margin_top = child_style.margin.top.resolve(container_height);
+            let child_margin_top = child_style.margin.top.resolve(container_width);
             let top = child_position_top.maybe_add(child_margin_top);
 
             let child_position_bottom = child_style.position.bottom.resolve(container_height);
-            let child_margin_bottom = child_style.margin.bottom.resolve(container_height);
+            let child_margin_bottom = child_style.margin.bottom.resolve(container_width);
             let bottom = child_position_bottom.maybe_add(child_margin_bottom);
 
             let (start_main, end_main) = if constants.is_row { (start, end) } else { (top, bottom) };
             let (start_cross, end_cross) = if constants.is_row { (top, bottom) } else { (start, end) };
 
-            let mut width = child_style
-                .size
-                .width
-                .resolve(container_width)
-                .maybe_max(child_style.min_size.width.resolve(container_width))
-                .maybe_min(child_style.max_size.width.resolve(container_width));
+            let container_size = Size { width: container_width, height: container_height };
+            let size = child_style.resolve_box_size(child_style.size, container_size);
+            let min_size = child_style.resolve_box_size(child_style.min_size, container_size);
+            let max_size = child_style.resolve_box_size(child_style.max_size, container_size);
+
+            let mut width = size.width.maybe_max(min_size.width).maybe_min(max_size.width);
 
             if width.is_none() && start.is_some() && end.is_some() {
                 width = container_width.maybe_sub(start).maybe_sub(end);
             }
 
-            let mut height: Option<f32> = child_style
-                .size
-                .height
-                .resolve(container_height)
-                .maybe_max(child_style.min_size.height.resolve(container_height))
-                .maybe_min(child_style.max_size.height.resolve(container_height));
+            let mut height: Option<f32> = size.height.maybe_max(min_size.height).maybe_min(max_size.height);
 
             if height.is_none() && top.is_some() && bottom.is_some() {
                 height = container_height.maybe_sub(top).maybe_sub(bottom);
@@ -1466,40 +1723,48 @@ impl Forest {
                 false,
             );
 
+            let inner_min_size = child_style.resolve_box_size(child_style.min_size, constants.node_inner_size);
+            let inner_max_size = child_style.resolve_box_size(child_style.max_size, constants.node_inner_size);
+
             let free_main_space = constants.container_size.main(constants.dir)
                 - preliminary_size
                     .main(constants.dir)
-                    .maybe_max(
-                        child_style.min_main_size(constants.dir).resolve(constants.node_inner_size.main(constants.dir)),
-                    )
-                    .maybe_min(
-                        child_style.max_main_size(constants.dir).resolve(constants.node_inner_size.main(constants.dir)),
-                    );
+                    .maybe_max(inner_min_size.main(constants.dir))
+                    .maybe_min(inner_max_size.main(constants.dir));
 
             let free_cross_space = constants.container_size.cross(constants.dir)
                 - preliminary_size
                     .cross(constants.dir)
-                    .maybe_max(
-                        child_style
-                            .min_cross_size(constants.dir)
-                            .resolve(constants.node_inner_size.cross(constants.dir)),
-                    )
-                    .maybe_min(
-                        child_style
-                            .max_cross_size(constants.dir)
-                            .resolve(constants.node_inner_size.cross(constants.dir)),
-                    );
+                    .maybe_max(inner_min_size.cross(constants.dir))
+                    .maybe_min(inner_max_size.cross(constants.dir));
+
+            let is_aligned_main = start_main.is_none() && end_main.is_none();
+            let is_aligned_cross = start_cross.is_none() && end_cross.is_none();
 
             let offset_main = if start_main.is_some() {
                 start_main.unwrap_or(0.0) + constants.border.main_start(constants.dir)
             } else if end_main.is_some() {
                 free_main_space - end_main.unwrap_or(0.0) - constants.border.main_end(constants.dir)
             } else {
-                match self.nodes[node].style.justify_content {
-                    JustifyContent::SpaceBetween | JustifyContent::FlexStart => {
+                // Absolutely positioned children are not placed in reverse, so the start edge is the main start,
+                // unless the direction swaps it
+                let style = &self.nodes[node].style;
+                let justify_content = match style.justify_content {
+                    JustifyContent::Start if style.direction.reverses_main_axis(style.flex_direction) => {
+                        JustifyContent::End
+                    }
+                    JustifyContent::End if style.direction.reverses_main_axis(style.flex_direction) => {
+                        JustifyContent::Start
+                    }
+                    other => other,
+                };
+                match justify_content {
+                    JustifyContent::SpaceBetween | JustifyContent::FlexStart | JustifyContent::Start => {
                         constants.padding_border.main_start(constants.dir)
                     }
-                    JustifyContent::FlexEnd => free_main_space - constants.padding_border.main_end(constants.dir),
+                    JustifyContent::FlexEnd | JustifyContent::End => {
+                        free_main_space - constants.padding_border.main_end(constants.dir)
+                    }
                     JustifyContent::SpaceEvenly | JustifyContent::SpaceAround | JustifyContent::Center => {
                         free_main_space / 2.0
                     }
@@ -1528,7 +1793,8 @@ impl Forest {
                         }
                     }
                     AlignSelf::Center => free_cross_space / 2.0,
-                    AlignSelf::Baseline => free_cross_space / 2.0, // Treat as center for now until we have baseline support
+                    // Treat as center for now until we have baseline support
+                    AlignSelf::Baseline | AlignSelf::LastBaseline => free_cross_space / 2.0,
                     AlignSelf::Stretch => {
                         if constants.is_wrap_reverse {
                             free_cross_space - constants.padding_border.cross_end(constants.dir)
@@ -1547,11 +1813,140 @@ impl Forest {
                     y: if constants.is_column { offset_main } else { offset_cross },
                 },
             };
+
+            // Without any insets, the item is placed by the alignment properties relative to the start of the content box
+            let alignment_main =
+                if is_aligned_main { offset_main - constants.padding_border.main_start(constants.dir) } else { 0.0 };
+            let alignment_cross =
+                if is_aligned_cross { offset_cross - constants.padding_border.cross_start(constants.dir) } else { 0.0 };
+            self.nodes[child].alignment_offset = Point {
+                x: if constants.is_row { alignment_main } else { alignment_cross },
+                y: if constants.is_column { alignment_main } else { alignment_cross },
+            };
+            // Absolutely positioned items do not take part in flexing
+            self.nodes[child].growth_delta = 0.0;
+            self.nodes[child].margin = Rect {
+                start: child_margin_start.unwrap_or(0.0),
+                end: child_margin_end.unwrap_or(0.0),
+                top: child_margin_top.unwrap_or(0.0),
+                bottom: child_margin_bottom.unwrap_or(0.0),
+            };
+        }
+    }
+
+    /// Places the absolutely positioned descendants whose containing block is the `node` relative to it
+    ///
+    /// These are the absolutely positioned children of static descendants, which are reached through static nodes only.
+    /// Each of them was already placed at its static position by its parent, so along each axis without insets
+    /// it stays there, while along each axis with an inset it is sized and placed relative to the padding box of the `node`.
+    /// A static `node` leaves them to its own containing block, unless the layout was started from it.
+    fn perform_absolute_layout_on_static_descendants(&mut self, node: NodeId, constants: &AlgoConstants) {
+        if self.nodes[node].style.position_type == PositionType::Static
+            && !self.parents[node].is_empty()
+            && self.layout_root != Some(node)
+        {
+            return;
+        }
+
+        let container_width: Option<f32> = constants.container_size.width.into();
+        let container_height: Option<f32> = constants.container_size.height.into();
+        let container_size = Size { width: container_width, height: container_height };
+
+        // Each static node to search, along with its location relative to the `node`
+        let mut stack: Vec<(NodeId, Point<f32>)> = Vec::new();
+        let is_searched =
+            |data: &NodeData| data.style.position_type == PositionType::Static && data.style.display != Display::None;
+        for child in self.children[node].iter() {
+            if is_searched(&self.nodes[*child]) {
+                stack.push((*child, self.nodes[*child].layout.location));
+            }
+        }
+
+        while let Some((parent, offset)) = stack.pop() {
+            for index in 0..self.children[parent].len() {
+                let child = self.children[parent][index];
+                let child_style = self.nodes[child].style;
+                let location = self.nodes[child].layout.location;
+                if is_searched(&self.nodes[child]) {
+                    stack.push((child, Point { x: offset.x + location.x, y: offset.y + location.y }));
+                    continue;
+                }
+                if child_style.position_type != PositionType::Absolute || child_style.display == Display::None {
+                    continue;
+                }
+
+                let start = child_style
+                    .position
+                    .start
+                    .resolve(container_width)
+                    .maybe_add(child_style.margin.start.resolve(container_width));
+                let end = child_style
+                    .position
+                    .end
+                    .resolve(container_width)
+                    .maybe_add(child_style.margin.end.resolve(container_width));
+                let top = child_style
+                    .position
+                    .top
+                    .resolve(container_height)
+                    .maybe_add(child_style.margin.top.resolve(container_width));
+                let bottom = child_style
+                    .position
+                    .bottom
+                    .resolve(container_height)
+                    .maybe_add(child_style.margin.bottom.resolve(container_width));
+                let is_inset_x = start.is_some() || end.is_some();
+                let is_inset_y = top.is_some() || bottom.is_some();
+                if !is_inset_x && !is_inset_y {
+                    continue;
+                }
+
+                let size = child_style.resolve_box_size(child_style.size, container_size);
+                let min_size = child_style.resolve_box_size(child_style.min_size, container_size);
+                let max_size = child_style.resolve_box_size(child_style.max_size, container_size);
+                let mut width = size.width.maybe_max(min_size.width).maybe_min(max_size.width);
+                if width.is_none() && start.is_some() && end.is_some() {
+                    width = container_width.maybe_sub(start).maybe_sub(end);
+                }
+                let mut height = size.height.maybe_max(min_size.height).maybe_min(max_size.height);
+                if height.is_none() && top.is_some() && bottom.is_some() {
+                    height = container_height.maybe_sub(top).maybe_sub(bottom);
+                }
+
+                let size = self.compute_preliminary(child, Size { width, height }, container_size, true, false);
+
+                let x = match (start, end) {
+                    (Some(start), _) => start + constants.border.start - offset.x,
+                    (None, Some(end)) => {
+                        constants.container_size.width - size.width - end - constants.border.end - offset.x
+                    }
+                    (None, None) => location.x,
+                };
+                let y = match (top, bottom) {
+                    (Some(top), _) => top + constants.border.top - offset.y,
+                    (None, Some(bottom)) => {
+                        constants.container_size.height - size.height - bottom - constants.border.bottom - offset.y
+                    }
+                    (None, None) => location.y,
+                };
+
+                let layout = &mut self.nodes[child].layout;
+                layout.size = size;
+                layout.location = Point { x, y };
+                let alignment_offset = &mut self.nodes[child].alignment_offset;
+                if is_inset_x {
+                    alignment_offset.x = 0.0;
+                }
+                if is_inset_y {
+                    alignment_offset.y = 0.0;
+                }
+                self.nodes[child].margin = child_style.margin.map(|m| m.resolve(container_width).unwrap_or(0.0));
+            }
         }
     }
 
     /// Compute a preliminary size for an item
-    fn compute_preliminary(
+    pub(crate) fn compute_preliminary(
         &mut self,
         node: NodeId,
         node_size: Size<Option<f32>>,
@@ -1559,11 +1954,36 @@ impl Forest {
         perform_layout: bool,
         main_size: bool,
     ) -> Size<f32> {
-        self.nodes[node].is_dirty = false;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.observe(node, |observer, node| observer.on_node_enter(node));
+        self.layout_depth += 1;
+        self.max_layout_depth = self.max_layout_depth.max(self.layout_depth);
+        let size = self.compute_node(node, node_size, parent_size, perform_layout, main_size);
+        self.layout_depth -= 1;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.record_call(node, node_size, parent_size, perform_layout, main_size, size);
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.observe(node, |observer, node| observer.on_node_exit(node, size));
+        size
+    }
+
+    /// Compute a preliminary size for an item, without recording the request
+    fn compute_node(
+        &mut self,
+        node: NodeId,
+        node_size: Size<Option<f32>>,
+        parent_size: Size<Option<f32>>,
+        perform_layout: bool,
+        main_size: bool,
+    ) -> Size<f32> {
+        self.nodes[node].set_dirty(false, &mut self.dirty_count);
 
         // First we check if we have a result for the given input
-        if let Some(cached_size) = self.compute_from_cache(node, node_size, parent_size, perform_layout, main_size) {
-            return cached_size;
+        if self.nodes[node].measure_cache_mode != CacheMode::Never {
+            if let Some(cached_size) = self.compute_from_cache(node, node_size, parent_size, perform_layout, main_size)
+            {
+                return cached_size;
+            }
         }
 
         // Define some general constants we will need for the remainder of the algorithm.
@@ -1571,18 +1991,44 @@ impl Forest {
 
         // If this is a leaf node we can skip a lot of this function in some cases
         if self.children[node].is_empty() {
+            if perform_layout {
+                self.nodes[node].free_space = 0.0;
+                self.nodes[node].inner_size = Size::zero();
+            }
+
             if node_size.width.is_some() && node_size.height.is_some() {
                 return node_size.map(|s| s.unwrap_or(0.0));
             }
 
-            if let Some(ref measure) = self.nodes[node].measure {
-                let converted_size = match measure {
-                    MeasureFunc::Raw(measure) => measure(node_size),
-                    #[cfg(any(feature = "std", feature = "alloc"))]
-                    MeasureFunc::Boxed(measure) => measure(node_size),
-                };
-                *self.cache(node, main_size) =
-                    Some(Cache { node_size, parent_size, perform_layout, size: converted_size });
+            if let Some(measure) = self.nodes[node].measure.as_ref().or(self.measure_fallback.as_ref()) {
+                if let Some(size) = self.nodes[node].permanent_measure {
+                    return self.snap_measured_size(size);
+                }
+
+                // A hint stands in for the unconstrained measurement, so the measure function is only called with known dimensions
+                let hint =
+                    self.nodes[node].size_hint.filter(|_| node_size.width.is_none() && node_size.height.is_none());
+                let available = node_size.map(AvailableSpace::from);
+                let converted_size = hint.unwrap_or_else(|| measure.measure(available));
+                if hint.is_none() {
+                    self.measure_count += 1;
+                    if self.nodes[node].measure_cache_mode == CacheMode::OncePermanent {
+                        self.nodes[node].permanent_measure = Some(converted_size);
+                    }
+                }
+                let converted_size = self.snap_measured_size(converted_size);
+                self.nodes[node].measured_size = Some(converted_size);
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                if hint.is_none() {
+                    self.observe(node, |observer, node| observer.on_measure(node, available, converted_size));
+                }
+                *self.cache(node, main_size) = Some(Cache {
+                    node_size,
+                    parent_size,
+                    perform_layout,
+                    measure_version: self.nodes[node].measure_version,
+                    size: converted_size,
+                });
                 return converted_size;
             }
 
@@ -1604,12 +2050,11 @@ impl Forest {
         // 2. Determine the available main and cross space for the flex items.
         let available_space = Self::determine_available_space(node_size, parent_size, &constants);
 
-        let has_baseline_child = flex_items
-            .iter()
-            .any(|child| self.nodes[child.node].style.align_self(&self.nodes[node].style) == AlignSelf::Baseline);
+        let has_baseline_child =
+            flex_items.iter().any(|child| self.nodes[child.node].style.is_baseline_aligned(&self.nodes[node].style));
 
         // 3. Determine the flex base size and hypothetical main size of each item.
-        self.determine_flex_base_size(node, node_size, &constants, available_space, &mut flex_items);
+        self.determine_flex_base_size(node, &constants, available_space, &mut flex_items);
 
         // TODO: Add step 4 according to spec: https://www.w3.org/TR/css-flexbox-1/#algo-main-container
         // 9.3. Main Size Determination
@@ -1628,7 +2073,8 @@ impl Forest {
             constants.dir,
             node_size.main(constants.dir).unwrap_or({
                 let longest_line = flex_lines.iter().fold(f32::MIN, |acc, line| {
-                    let length: f32 = line.items.iter().map(|item| item.outer_target_size.main(constants.dir)).sum();
+                    let length: f32 = constants.main_gaps(line.items.len())
+                        + line.items.iter().map(|item| item.outer_target_size.main(constants.dir)).sum::<f32>();
                     acc.max(length)
                 });
 
@@ -1699,10 +2145,24 @@ impl Forest {
         // If our caller does not care about performing layout we are done now.
         if !perform_layout {
             let container_size = constants.container_size;
-            *self.cache(node, main_size) = Some(Cache { node_size, parent_size, perform_layout, size: container_size });
+            *self.cache(node, main_size) = Some(Cache {
+                node_size,
+                parent_size,
+                perform_layout,
+                measure_version: self.nodes[node].measure_version,
+                size: container_size,
+            });
             return container_size;
         }
 
+        // The line with the least free space determines how much space is left in the container
+        self.nodes[node].free_space = flex_lines
+            .iter()
+            .map(|line| constants.free_space(line))
+            .reduce(f32::min)
+            .unwrap_or_else(|| constants.inner_container_size.main(constants.dir));
+        self.nodes[node].inner_size = constants.inner_container_size;
+
         // 16. Align all flex lines per align-content.
         self.align_flex_lines_per_align_content(&mut flex_lines, node, &constants, total_cross_size);
 
@@ -1711,26 +2171,46 @@ impl Forest {
 
         // Before returning we perform absolute layout on all absolutely positioned children
         self.perform_absolute_layout_on_absolute_children(node, &constants);
+        self.perform_absolute_layout_on_static_descendants(node, &constants);
 
         /// Lay out all hidden nodes recursively
         ///
         /// Each hidden node has zero size and is placed at the origin
-        fn hidden_layout(nodes: &mut [NodeData], children: &[ChildrenVec<NodeId>], node: NodeId, order: u32) {
+        fn hidden_layout(
+            nodes: &mut [NodeData],
+            children: &[ChildrenVec<NodeId>],
+            dirty_count: &mut usize,
+            node: NodeId,
+            order: u32,
+        ) {
+            // Hidden nodes are fully laid out by this, so they are no longer dirty
+            nodes[node].set_dirty(false, dirty_count);
             nodes[node].layout = Layout { order, size: Size::zero(), location: Point::zero() };
+            nodes[node].alignment_offset = Point::zero();
+            nodes[node].free_space = 0.0;
+            nodes[node].growth_delta = 0.0;
+            nodes[node].margin = Rect::splat(0.0);
+            nodes[node].inner_size = Size::zero();
 
             for (order, child) in children[node].iter().enumerate() {
-                hidden_layout(nodes, children, *child, order as _);
+                hidden_layout(nodes, children, dirty_count, *child, order as _);
             }
         }
 
         for (order, child) in self.children[node].iter().enumerate() {
             if self.nodes[*child].style.display == Display::None {
-                hidden_layout(&mut self.nodes, &self.children, *child, order as _);
+                hidden_layout(&mut self.nodes, &self.children, &mut self.dirty_count, *child, order as _);
             }
         }
 
         let container_size = constants.container_size;
-        *self.cache(node, main_size) = Some(Cache { node_size, parent_size, perform_layout, size: container_size });
+        *self.cache(node, main_size) = Some(Cache {
+            node_size,
+            parent_size,
+            perform_layout,
+            measure_version: self.nodes[node].measure_version,
+            size: container_size,
+        });
 
         container_size
     }
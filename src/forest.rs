@@ -1,11 +1,20 @@
 //! Forest - a struct-of-arrays data structure for storing node trees.
 //!
 //! Backing data structure for `Taffy` structs.
-use crate::geometry::Size;
+use core::mem;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::dirty::{only_affects_children, RecordedCall};
+use crate::geometry::{Point, Rect, Size};
 use crate::layout::{Cache, Layout};
-use crate::node::{MeasureFunc, NodeId};
-use crate::style::FlexboxLayout;
-use crate::sys::{new_vec_with_capacity, ChildrenVec, ParentsVec, Vec};
+use crate::node::{CacheMode, MeasureFunc, NodeId};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::node::{LayoutObserver, Node};
+use crate::style::{Dimension, Display, FlexboxLayout};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::sys::Box;
+use crate::sys::{new_vec_with_capacity, round, ChildrenVec, ParentsVec, Vec};
 
 /// Layout information for a given [`Node`](crate::node::Node)
 ///
@@ -23,6 +32,46 @@ pub(crate) struct NodeData {
     pub(crate) other_layout_cache: Option<Cache>,
     /// Does this node's layout need to be recomputed?
     pub(crate) is_dirty: bool,
+    /// Every distinct request for the size of this node since it was last dirtied with its ancestors
+    ///
+    /// This is [`None`] if too many requests were made for them to be recorded.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) recorded_calls: Option<Vec<RecordedCall>>,
+    /// The available space that this node was last laid out with as a root
+    pub(crate) last_available_space: Option<Size<Option<f32>>>,
+    /// How far the alignment properties of its parent moved this node during the last layout
+    pub(crate) alignment_offset: Point<f32>,
+    /// The main-axis space left over after placing the children of this node during the last layout
+    pub(crate) free_space: f32,
+    /// How much flexing grew (or, if negative, shrank) this node from its flex basis during the last layout
+    pub(crate) growth_delta: f32,
+    /// The margins of this node as resolved during the last layout, with auto margins taking the space they were given
+    pub(crate) margin: Rect<f32>,
+    /// The size of the content box that the children of this node were laid out in during the last layout
+    ///
+    /// This is zero for nodes without children.
+    pub(crate) inner_size: Size<f32>,
+    /// The size most recently reported by the [`MeasureFunc`] of this node
+    pub(crate) measured_size: Option<Size<f32>>,
+    /// How the results of the [`MeasureFunc`] of this node are reused
+    pub(crate) measure_cache_mode: CacheMode,
+    /// The first size reported by the [`MeasureFunc`] of this node, kept with [`CacheMode::OncePermanent`]
+    pub(crate) permanent_measure: Option<Size<f32>>,
+    /// A counter that is part of the key of every cached size of this node, so changing it invalidates them
+    pub(crate) measure_version: u32,
+    /// The size used in place of calling the [`MeasureFunc`] when no dimension of the node is known, if any
+    pub(crate) size_hint: Option<Size<f32>>,
+    /// The intrinsic aspect ratio of the content measured by the [`MeasureFunc`] of this node, set with
+    /// [`Taffy::set_intrinsic_aspect_ratio`](crate::node::Taffy::set_intrinsic_aspect_ratio)
+    pub(crate) intrinsic_aspect_ratio: Option<f32>,
+    /// Has the list of children of this node changed since [`Forest::take_structurally_changed`] last reported it?
+    ///
+    /// This is atomic so that it can be cleared while the forest is only borrowed.
+    pub(crate) structurally_changed: AtomicBool,
+    /// The last pass of [`Forest::mark_dirty_with_ancestors`] that reached this node
+    pub(crate) dirty_pass: u64,
+    /// The location relative to the root of its tree and the size of this node, as last reported as damage
+    pub(crate) damage_rect: Option<(Point<f32>, Size<f32>)>,
 }
 
 impl NodeData {
@@ -36,13 +85,30 @@ impl NodeData {
             other_layout_cache: None,
             layout: Layout::new(),
             is_dirty: true,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            recorded_calls: Some(Vec::new()),
+            last_available_space: None,
+            alignment_offset: Point::zero(),
+            free_space: 0.0,
+            growth_delta: 0.0,
+            margin: Rect::splat(0.0),
+            inner_size: Size::zero(),
+            measured_size: None,
+            measure_cache_mode: CacheMode::Always,
+            permanent_measure: None,
+            measure_version: 0,
+            size_hint: None,
+            intrinsic_aspect_ratio: None,
+            structurally_changed: AtomicBool::new(false),
+            dirty_pass: 0,
+            damage_rect: None,
         }
     }
 
     /// Create the data for a new node
     // TODO: why is this different from new_leaf?
     #[must_use]
-    fn new(style: FlexboxLayout) -> Self {
+    pub(crate) fn new(style: FlexboxLayout) -> Self {
         Self {
             style,
             measure: None,
@@ -50,6 +116,23 @@ impl NodeData {
             other_layout_cache: None,
             layout: Layout::new(),
             is_dirty: true,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            recorded_calls: Some(Vec::new()),
+            last_available_space: None,
+            alignment_offset: Point::zero(),
+            free_space: 0.0,
+            growth_delta: 0.0,
+            margin: Rect::splat(0.0),
+            inner_size: Size::zero(),
+            measured_size: None,
+            measure_cache_mode: CacheMode::Always,
+            permanent_measure: None,
+            measure_version: 0,
+            size_hint: None,
+            intrinsic_aspect_ratio: None,
+            structurally_changed: AtomicBool::new(false),
+            dirty_pass: 0,
+            damage_rect: None,
         }
     }
 
@@ -57,13 +140,109 @@ impl NodeData {
     ///
     /// This clears any cached data and signals that the data must be recomputed.
     #[inline]
-    fn mark_dirty(&mut self) {
+    pub(crate) fn mark_dirty(&mut self, dirty_count: &mut usize) {
         self.main_size_layout_cache = None;
         self.other_layout_cache = None;
-        self.is_dirty = true;
+        self.set_dirty(true, dirty_count);
+    }
+
+    /// Sets the dirty flag of this node, keeping the `dirty_count` of its forest in step
+    #[inline]
+    pub(crate) fn set_dirty(&mut self, is_dirty: bool, dirty_count: &mut usize) {
+        if self.is_dirty != is_dirty {
+            self.is_dirty = is_dirty;
+            if is_dirty {
+                *dirty_count += 1;
+            } else {
+                *dirty_count -= 1;
+            }
+        }
+    }
+}
+
+/// The parts of a [`NodeData`] that are overwritten by dirtying the node and laying it out
+#[derive(Debug, Clone)]
+pub(crate) struct DirtyState {
+    /// See [`NodeData::is_dirty`]
+    is_dirty: bool,
+    /// See [`NodeData::main_size_layout_cache`]
+    main_size_layout_cache: Option<Cache>,
+    /// See [`NodeData::other_layout_cache`]
+    other_layout_cache: Option<Cache>,
+    /// See [`NodeData::recorded_calls`]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    recorded_calls: Option<Vec<RecordedCall>>,
+    /// See [`NodeData::layout`]
+    layout: Layout,
+    /// See [`NodeData::last_available_space`]
+    last_available_space: Option<Size<Option<f32>>>,
+    /// See [`NodeData::alignment_offset`]
+    alignment_offset: Point<f32>,
+    /// See [`NodeData::free_space`]
+    free_space: f32,
+    /// See [`NodeData::growth_delta`]
+    growth_delta: f32,
+    /// See [`NodeData::margin`]
+    margin: Rect<f32>,
+    /// See [`NodeData::inner_size`]
+    inner_size: Size<f32>,
+    /// See [`NodeData::measured_size`]
+    measured_size: Option<Size<f32>>,
+    /// See [`NodeData::permanent_measure`]
+    permanent_measure: Option<Size<f32>>,
+}
+
+impl NodeData {
+    /// Copies the dirty flag of this node, along with its caches and the results of its last layout
+    pub(crate) fn dirty_state(&self) -> DirtyState {
+        DirtyState {
+            is_dirty: self.is_dirty,
+            main_size_layout_cache: self.main_size_layout_cache.clone(),
+            other_layout_cache: self.other_layout_cache.clone(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            recorded_calls: self.recorded_calls.clone(),
+            layout: self.layout,
+            last_available_space: self.last_available_space,
+            alignment_offset: self.alignment_offset,
+            free_space: self.free_space,
+            growth_delta: self.growth_delta,
+            margin: self.margin,
+            inner_size: self.inner_size,
+            measured_size: self.measured_size,
+            permanent_measure: self.permanent_measure,
+        }
+    }
+
+    /// Puts back a state copied by [`NodeData::dirty_state`]
+    pub(crate) fn restore_dirty_state(&mut self, state: DirtyState, dirty_count: &mut usize) {
+        self.set_dirty(state.is_dirty, dirty_count);
+        self.main_size_layout_cache = state.main_size_layout_cache;
+        self.other_layout_cache = state.other_layout_cache;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            self.recorded_calls = state.recorded_calls;
+        }
+        self.layout = state.layout;
+        self.last_available_space = state.last_available_space;
+        self.alignment_offset = state.alignment_offset;
+        self.free_space = state.free_space;
+        self.growth_delta = state.growth_delta;
+        self.margin = state.margin;
+        self.inner_size = state.inner_size;
+        self.measured_size = state.measured_size;
+        self.permanent_measure = state.permanent_measure;
     }
 }
 
+/// A [`LayoutObserver`], along with the [`Node`] that each [`NodeId`] belonged to when the current layout started
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub(crate) struct Observer {
+    /// The observer itself
+    pub(crate) inner: Box<dyn LayoutObserver>,
+    /// The [`Node`] of each [`NodeId`], indexed by the [`NodeId`]
+    pub(crate) nodes: Vec<Node>,
+}
+
 /// A collection of UI layout trees used to store [`NodeData`] associated with specific [`Nodes`](crate::node::Node)
 pub(crate) struct Forest {
     /// The [`NodeData`] for each node stored in this forest
@@ -76,6 +255,38 @@ pub(crate) struct Forest {
     ///
     /// The indexes in the outer vector correspond to the position of the child [`NodeData`]
     pub(crate) parents: Vec<ParentsVec<NodeId>>,
+    /// The number of physical pixels per logical pixel, used to round layouts to the physical pixel grid
+    pub(crate) scale_factor: f32,
+    /// Should independent subtrees be laid out in parallel?
+    #[cfg(feature = "parallel")]
+    pub(crate) parallel: bool,
+    /// Should changes that leave the style or children of a node as they were be ignored, rather than dirtying it?
+    pub(crate) content_cache: bool,
+    /// The measure function used for leaves that do not have their own
+    pub(crate) measure_fallback: Option<MeasureFunc>,
+    /// Should the sizes returned by measure functions be rounded up to the physical pixel grid?
+    pub(crate) measure_snapping: bool,
+    /// Should dirtying a node avoid dirtying its ancestors, unless its size changes?
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) size_aware_dirtying: bool,
+    /// The nodes that were dirtied without their ancestors, and have not been laid out since
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) pending: Vec<NodeId>,
+    /// The observer that is notified of each node visited during layout, if any
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) observer: Option<Observer>,
+    /// The number of passes of [`Forest::mark_dirty_with_ancestors`] so far
+    pub(crate) dirty_passes: u64,
+    /// The number of nested node visits of the layout in progress
+    pub(crate) layout_depth: usize,
+    /// The deepest nesting of node visits reached by the last layout
+    pub(crate) max_layout_depth: usize,
+    /// The number of calls to measure functions made by the last layout
+    pub(crate) measure_count: usize,
+    /// The number of nodes whose dirty flag is set
+    pub(crate) dirty_count: usize,
+    /// The node that the layout in progress was started from, if any
+    pub(crate) layout_root: Option<NodeId>,
 }
 
 impl Forest {
@@ -86,6 +297,32 @@ impl Forest {
             nodes: new_vec_with_capacity(capacity),
             children: new_vec_with_capacity(capacity),
             parents: new_vec_with_capacity(capacity),
+            scale_factor: 1.0,
+            content_cache: false,
+            measure_fallback: None,
+            measure_snapping: false,
+            #[cfg(feature = "parallel")]
+            parallel: true,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            size_aware_dirtying: false,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            pending: Vec::new(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            observer: None,
+            dirty_passes: 0,
+            layout_depth: 0,
+            max_layout_depth: 0,
+            measure_count: 0,
+            dirty_count: 0,
+            layout_root: None,
+        }
+    }
+
+    /// Notifies the observer, if there is one, of an event concerning the `node`
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) fn observe(&mut self, node: NodeId, event: impl FnOnce(&mut dyn LayoutObserver, Node)) {
+        if let Some(observer) = &mut self.observer {
+            event(&mut *observer.inner, observer.nodes[node]);
         }
     }
 
@@ -93,6 +330,7 @@ impl Forest {
     pub(crate) fn new_leaf(&mut self, style: FlexboxLayout, measure: MeasureFunc) -> NodeId {
         let id = self.nodes.len();
         self.nodes.push(NodeData::new_leaf(style, measure));
+        self.dirty_count += 1;
         self.children.push(new_vec_with_capacity(0));
         self.parents.push(new_vec_with_capacity(1));
         id
@@ -105,6 +343,7 @@ impl Forest {
             self.parents[*child].push(id);
         }
         self.nodes.push(NodeData::new(style));
+        self.dirty_count += 1;
         self.children.push(children);
         self.parents.push(new_vec_with_capacity(1));
         id
@@ -114,16 +353,30 @@ impl Forest {
     pub(crate) fn add_child(&mut self, parent: NodeId, child: NodeId) {
         self.parents[child].push(parent);
         self.children[parent].push(child);
+        self.mark_structurally_changed(parent);
         self.mark_dirty(parent)
     }
 
+    /// Records that the list of children of the `node` has changed
+    pub(crate) fn mark_structurally_changed(&mut self, node: NodeId) {
+        *self.nodes[node].structurally_changed.get_mut() = true;
+    }
+
+    /// Returns every node whose list of children has changed since the last call, in order, clearing their flags
+    pub(crate) fn take_structurally_changed(&self) -> Vec<NodeId> {
+        (0..self.nodes.len()).filter(|id| self.nodes[*id].structurally_changed.swap(false, Ordering::Relaxed)).collect()
+    }
+
     /// Removes all nodes and resets the data structure
     ///
     /// The capacity is retained.
     pub(crate) fn clear(&mut self) {
         self.nodes.clear();
+        self.dirty_count = 0;
         self.children.clear();
         self.parents.clear();
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.pending.clear();
     }
 
     /// Removes the specified `node`
@@ -131,7 +384,24 @@ impl Forest {
     /// The last existing node is moved to its previous position, in order to ensure compactness.
     /// Returns the previous [`NodeId`] of the moved node, if one was moved.
     pub(crate) fn swap_remove(&mut self, node: NodeId) -> Option<NodeId> {
-        self.nodes.swap_remove(node);
+        for index in 0..self.parents[node].len() {
+            let parent = self.parents[node][index];
+            self.mark_structurally_changed(parent);
+        }
+        if self.nodes.swap_remove(node).is_dirty {
+            self.dirty_count -= 1;
+        }
+
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            let last = self.nodes.len();
+            self.pending.retain(|pending| *pending != node);
+            for pending in &mut self.pending {
+                if *pending == last {
+                    *pending = node;
+                }
+            }
+        }
 
         // Now the last element is swapped in at index `node`.
         if self.nodes.is_empty() {
@@ -201,9 +471,9 @@ impl Forest {
     /// Breaks the link between the `parent` node and the `child` node
     ///
     /// The `child`'s data is not removed.
-    pub(crate) fn remove_child(&mut self, parent: NodeId, child: NodeId) -> NodeId {
-        let index = self.children[parent].iter().position(|n| *n == child).unwrap();
-        self.remove_child_at_index(parent, index)
+    pub(crate) fn remove_child(&mut self, parent: NodeId, child: NodeId) -> Option<NodeId> {
+        let index = self.children[parent].iter().position(|n| *n == child)?;
+        Some(self.remove_child_at_index(parent, index))
     }
 
     /// Breaks the link between the `parent` node and the n-th child node
@@ -211,32 +481,565 @@ impl Forest {
     /// The child's data is not removed.
     pub(crate) fn remove_child_at_index(&mut self, parent: NodeId, child_index: usize) -> NodeId {
         let child = self.children[parent].remove(child_index);
-        self.parents[child].retain(|p| *p != parent);
+        self.unlink_parent(child, parent);
+        self.mark_structurally_changed(parent);
         self.mark_dirty(parent);
         child
     }
 
+    /// Changes the children of the `parent` to the `target`, only touching the links of the children that enter or leave
+    ///
+    /// Children that are kept, even if they move, keep their links to the `parent`.
+    /// The `parent` is only dirtied, once, if its children actually change.
+    pub(crate) fn reconcile_children(&mut self, parent: NodeId, target: ChildrenVec<NodeId>) {
+        if self.children[parent] == target {
+            return;
+        }
+
+        let mut current: Vec<NodeId> = new_vec_with_capacity(self.children[parent].len());
+        current.extend(self.children[parent].iter().copied());
+        current.sort_unstable();
+        let mut wanted: Vec<NodeId> = new_vec_with_capacity(target.len());
+        wanted.extend(target.iter().copied());
+        wanted.sort_unstable();
+
+        // Walk both sorted lists together, so that each kept occurrence of a child is matched exactly once
+        let (mut i, mut j) = (0, 0);
+        while i < current.len() || j < wanted.len() {
+            match (current.get(i), wanted.get(j)) {
+                (Some(old), Some(new)) if old == new => {
+                    i += 1;
+                    j += 1;
+                }
+                (Some(old), None) => {
+                    self.unlink_parent(*old, parent);
+                    i += 1;
+                }
+                (Some(old), Some(new)) if old < new => {
+                    self.unlink_parent(*old, parent);
+                    i += 1;
+                }
+                (_, Some(new)) => {
+                    self.parents[*new].push(parent);
+                    j += 1;
+                }
+                (None, None) => break,
+            }
+        }
+
+        self.children[parent] = target;
+        self.mark_structurally_changed(parent);
+        self.mark_dirty(parent);
+    }
+
+    /// Removes a single link from the `child` to the `parent`
+    ///
+    /// A child that is listed several times by the same parent has one link for each,
+    /// so that removing one of its entries leaves the others linked.
+    pub(crate) fn unlink_parent(&mut self, child: NodeId, parent: NodeId) {
+        if let Some(index) = self.parents[child].iter().position(|p| *p == parent) {
+            self.parents[child].remove(index);
+        }
+    }
+
+    /// Replaces the style of the `node`, and marks it as needing layout recalculation
+    pub(crate) fn set_style(&mut self, node: NodeId, style: FlexboxLayout) {
+        if self.content_cache && self.nodes[node].style == style {
+            return;
+        }
+
+        // Unlike other changes, most style changes are visible to the parent even if the size stays the same
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if self.size_aware_dirtying && !only_affects_children(&self.nodes[node].style, &style) {
+            self.nodes[node].style = style;
+            self.mark_dirty_with_ancestors(node);
+            return;
+        }
+
+        self.nodes[node].style = style;
+        self.mark_dirty(node);
+    }
+
+    /// Sets the style of each of the given nodes, then dirties them and their ancestors in a single sweep
+    ///
+    /// Each ancestor is only visited once, however many of its descendants were changed.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_styles(&mut self, mut styles: Vec<(NodeId, FlexboxLayout)>) {
+        if self.content_cache {
+            styles.retain(|(node, style)| self.nodes[*node].style != *style);
+        }
+
+        // Size-aware dirtying decides for each node whether its ancestors are affected
+        if self.size_aware_dirtying {
+            for (node, style) in styles {
+                self.set_style(node, style);
+            }
+            return;
+        }
+
+        let mut visited: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<NodeId> = Vec::with_capacity(styles.len());
+        for (node, style) in styles {
+            self.nodes[node].style = style;
+            stack.push(node);
+        }
+
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            self.nodes[node].mark_dirty(&mut self.dirty_count);
+            self.nodes[node].reset_recorded_calls();
+            stack.extend(self.parents[node].iter().copied());
+        }
+    }
+
     /// Marks the `node` as needing layout recalculation
     ///
     /// Any cached layout information is cleared.
+    /// Unless size-aware dirtying is enabled, all of the ancestors of the `node` are marked too.
     pub(crate) fn mark_dirty(&mut self, node: NodeId) {
-        /// Performs a recursive depth-first search up the tree until the root node is reached
-        ///
-        ///  WARNING: this will stack-overflow if the tree contains a cycle
-        fn mark_dirty_recursive(nodes: &mut Vec<NodeData>, parents: &[ParentsVec<NodeId>], node_id: NodeId) {
-            nodes[node_id].mark_dirty();
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if self.size_aware_dirtying {
+            self.mark_dirty_size_aware(node);
+            return;
+        }
+
+        self.mark_dirty_with_ancestors(node);
+    }
+
+    /// Marks the `node` and all of its ancestors as needing layout recalculation
+    ///
+    /// Any cached layout information is cleared.
+    pub(crate) fn mark_dirty_with_ancestors(&mut self, node: NodeId) {
+        // Each node is visited once per pass, so shared ancestors are not revisited and a cycle cannot be followed forever
+        self.dirty_passes += 1;
+        let pass = self.dirty_passes;
+
+        let mut stack: Vec<NodeId> = new_vec_with_capacity(1);
+        stack.push(node);
+        while let Some(id) = stack.pop() {
+            let data = &mut self.nodes[id];
+            if data.dirty_pass == pass {
+                continue;
+            }
+            data.dirty_pass = pass;
+            data.mark_dirty(&mut self.dirty_count);
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            data.reset_recorded_calls();
+
+            stack.extend(self.parents[id].iter().copied());
+        }
+    }
+
+    /// Invalidates every cached size of the `node`, and marks its ancestors as dirty
+    ///
+    /// Unlike [`Forest::mark_dirty`], the caches of the `node` are kept, but can no longer be used.
+    pub(crate) fn bump_measure_version(&mut self, node: NodeId) {
+        let data = &mut self.nodes[node];
+        data.measure_version = data.measure_version.wrapping_add(1);
+        data.permanent_measure = None;
+        data.set_dirty(true, &mut self.dirty_count);
+        for parent in self.parents[node].clone() {
+            self.mark_dirty(parent);
+        }
+    }
+
+    /// Marks every node in the forest as dirty
+    pub(crate) fn mark_all_dirty(&mut self) {
+        for data in &mut self.nodes {
+            data.mark_dirty(&mut self.dirty_count);
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            data.reset_recorded_calls();
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.pending.clear();
+    }
+
+    /// Returns the ancestors of the `node`, starting with its parent and ending with the root of its tree
+    ///
+    /// Where a node has several parents, the first one is followed.
+    pub(crate) fn ancestors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut current = node;
+        // A node has at most one fewer distinct ancestors than there are nodes, so this bound only stops a cycle
+        // from being followed forever, and the node and its ancestors together never outnumber the nodes
+        (1..self.nodes.len()).map_while(move |_| {
+            current = *self.parents[current].first()?;
+            Some(current)
+        })
+    }
+
+    /// Returns the location of the `node` relative to the root of its tree
+    ///
+    /// Where a node has several parents, the first one is followed.
+    pub(crate) fn absolute_location(&self, node: NodeId) -> Point<f32> {
+        let mut location = self.nodes[node].layout.location;
+        for ancestor in self.ancestors(node) {
+            location.x += self.nodes[ancestor].layout.location.x;
+            location.y += self.nodes[ancestor].layout.location.y;
+        }
+        location
+    }
+
+    /// The location of the `node` relative to its `ancestor`, or [`None`] if the `ancestor` is not one of its ancestors
+    ///
+    /// Where a node has several parents, the first one is followed. A node is located at the origin relative to itself.
+    pub(crate) fn location_relative_to(&self, node: NodeId, ancestor: NodeId) -> Option<Point<f32>> {
+        let mut location = Point::zero();
+        for current in core::iter::once(node).chain(self.ancestors(node)) {
+            if current == ancestor {
+                return Some(location);
+            }
+            location.x += self.nodes[current].layout.location.x;
+            location.y += self.nodes[current].layout.location.y;
+        }
+        None
+    }
+
+    /// Is the `node` laid out, rather than hidden by its own [`Display::None`] or that of one of its ancestors?
+    ///
+    /// Where a node has several parents, the first one is followed.
+    pub(crate) fn is_laid_out(&self, node: NodeId) -> bool {
+        core::iter::once(node).chain(self.ancestors(node)).all(|id| self.nodes[id].style.display != Display::None)
+    }
+
+    /// Returns the root of the tree that contains the `node`, which is the `node` itself if it has no parent
+    ///
+    /// Where a node has several parents, the first one is followed.
+    pub(crate) fn tree_root(&self, node: NodeId) -> NodeId {
+        self.ancestors(node).last().unwrap_or(node)
+    }
+
+    /// Does the content of the `node` extend beyond its own layout, along each axis?
+    ///
+    /// The content of a measured node is the size reported by its [`MeasureFunc`], snapped to the pixel grid like
+    /// the layout itself. The content of any other node is its children, which overflow if they extend past any edge.
+    pub(crate) fn overflows(&self, node: NodeId) -> Size<bool> {
+        let data = &self.nodes[node];
+        let size = data.layout.size;
+
+        if let (Some(_), Some(measured)) = (&data.measure, data.measured_size) {
+            let snap = |value: f32| round(value * self.scale_factor) / self.scale_factor;
+            return Size { width: snap(measured.width) > size.width, height: snap(measured.height) > size.height };
+        }
+
+        let mut overflows = Size { width: false, height: false };
+        for child in &self.children[node] {
+            let Layout { location, size: child_size, .. } = self.nodes[*child].layout;
+            overflows.width |= location.x < 0.0 || location.x + child_size.width > size.width;
+            overflows.height |= location.y < 0.0 || location.y + child_size.height > size.height;
+        }
+        overflows
+    }
 
-            for parent in &parents[node_id] {
-                mark_dirty_recursive(nodes, parents, *parent);
+    /// Returns every node in the tree below `root` (including `root` itself) whose location relative to the root
+    /// or size has changed since the last call, along with its layout translated to be relative to the root
+    ///
+    /// Nodes that have not been reported before are always included. Nodes with several parents are only visited once.
+    pub(crate) fn collect_damage(&mut self, root: NodeId) -> Vec<(NodeId, Layout)> {
+        let mut damage = Vec::new();
+        let mut visited: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<(NodeId, Point<f32>)> = new_vec_with_capacity(self.nodes.len());
+        stack.push((root, self.absolute_location(root)));
+
+        while let Some((id, location)) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+
+            let data = &mut self.nodes[id];
+            let rect = (location, data.layout.size);
+            if data.damage_rect != Some(rect) {
+                data.damage_rect = Some(rect);
+                damage.push((id, Layout { location, ..data.layout }));
+            }
+
+            for child in self.children[id].iter().rev() {
+                let offset = self.nodes[*child].layout.location;
+                stack.push((*child, Point { x: location.x + offset.x, y: location.y + offset.y }));
             }
         }
 
-        mark_dirty_recursive(&mut self.nodes, &self.parents, node);
+        damage
+    }
+
+    /// Returns each leaf in the tree below `root` (including `root` itself), along with its layout translated to be
+    /// relative to the root of its tree
+    ///
+    /// Leaves are returned in preorder. Nodes hidden by [`Display::None`] are skipped along with their descendants,
+    /// and nodes with several parents are only visited once.
+    pub(crate) fn leaf_layouts(&self, root: NodeId) -> Vec<(NodeId, Layout)> {
+        let mut leaves = Vec::new();
+        let mut visited: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<(NodeId, Point<f32>)> = new_vec_with_capacity(self.nodes.len());
+        stack.push((root, self.absolute_location(root)));
+
+        while let Some((id, location)) = stack.pop() {
+            if visited[id] || self.nodes[id].style.display == Display::None {
+                continue;
+            }
+            visited[id] = true;
+
+            if self.children[id].is_empty() {
+                leaves.push((id, Layout { location, ..self.nodes[id].layout }));
+            }
+
+            for child in self.children[id].iter().rev() {
+                let offset = self.nodes[*child].layout.location;
+                stack.push((*child, Point { x: location.x + offset.x, y: location.y + offset.y }));
+            }
+        }
+
+        leaves
+    }
+
+    /// Returns each node in the tree below `root` (including `root` itself), along with its layout translated to be
+    /// relative to the `root`
+    ///
+    /// Nodes are returned in preorder. Nodes hidden by [`Display::None`] are skipped along with their descendants,
+    /// and nodes with several parents are only visited once.
+    pub(crate) fn flatten(&self, root: NodeId) -> Vec<(NodeId, Layout)> {
+        let mut layouts = Vec::new();
+        let mut visited: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<(NodeId, Point<f32>)> = new_vec_with_capacity(self.nodes.len());
+        stack.push((root, Point::zero()));
+
+        while let Some((id, location)) = stack.pop() {
+            if visited[id] || self.nodes[id].style.display == Display::None {
+                continue;
+            }
+            visited[id] = true;
+            layouts.push((id, Layout { location, ..self.nodes[id].layout }));
+
+            for child in self.children[id].iter().rev() {
+                let offset = self.nodes[*child].layout.location;
+                stack.push((*child, Point { x: location.x + offset.x, y: location.y + offset.y }));
+            }
+        }
+
+        layouts
+    }
+
+    /// Would laying out the `node` as a root in the available `size` change any stored layout?
+    ///
+    /// Negative available sizes are clamped to zero, as in [`Forest::compute_layout`].
+    pub(crate) fn needs_layout(&self, node: NodeId, size: Size<Option<f32>>) -> bool {
+        // Size-aware dirtying leaves the ancestors of a dirtied node clean until the next layout
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if !self.pending.is_empty() {
+            return true;
+        }
+
+        let size = size.map(|dimension| dimension.map(|value| value.max(0.0)));
+        self.nodes[node].is_dirty || self.nodes[node].last_available_space != Some(size)
     }
 
     /// Computes the layout of the `node` and its children
+    ///
+    /// Negative available sizes are clamped to zero.
     pub(crate) fn compute_layout(&mut self, node: NodeId, size: Size<Option<f32>>) {
+        self.compute_layout_with_percentage_base(node, size, size)
+    }
+
+    /// Computes the layout of the `node` and its children, resolving the percentage sizes of the `node` against `percentage_base`
+    ///
+    /// Negative available sizes and bases are clamped to zero.
+    pub(crate) fn compute_layout_with_percentage_base(
+        &mut self,
+        node: NodeId,
+        size: Size<Option<f32>>,
+        percentage_base: Size<Option<f32>>,
+    ) {
         // TODO: It's not clear why this method is distinct
-        self.compute(node, size)
+        self.max_layout_depth = 0;
+        self.measure_count = 0;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.layout_pending();
+
+        let clamp = |size: Size<Option<f32>>| size.map(|dimension| dimension.map(|value| value.max(0.0)));
+        let size = clamp(size);
+        self.nodes[node].last_available_space = Some(size);
+        self.compute(node, size, clamp(percentage_base))
+    }
+
+    /// Returns a node below `root` (possibly `root` itself) that is one of its own ancestors, if there is one
+    ///
+    /// Laying out a tree that contains a cycle would never finish, so this must be checked before anything else.
+    /// Each node is searched once, so this is linear in the size of the tree.
+    pub(crate) fn find_cycle(&self, root: NodeId) -> Option<NodeId> {
+        let mut on_path: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        let mut searched: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        // Each entry is a node on the current path, along with the index of the next of its children to search
+        let mut stack: Vec<(NodeId, usize)> = new_vec_with_capacity(self.nodes.len());
+        on_path[root] = true;
+        stack.push((root, 0));
+
+        while let Some(&(id, index)) = stack.last() {
+            match self.children[id].get(index) {
+                Some(&child) => {
+                    if let Some(last) = stack.last_mut() {
+                        last.1 += 1;
+                    }
+                    if on_path[child] {
+                        return Some(child);
+                    }
+                    if !searched[child] {
+                        on_path[child] = true;
+                        stack.push((child, 0));
+                    }
+                }
+                None => {
+                    on_path[id] = false;
+                    searched[id] = true;
+                    stack.pop();
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the name of the first limit that laying out the tree below `root` would exceed, if any
+    ///
+    /// The depth counts the levels of the tree, so a lone root has a depth of 1. A node is counted once for every
+    /// path from the `root` that reaches it, as a node with several parents is laid out once for each of them.
+    /// The walk stops as soon as a limit is exceeded, so it is bounded by the limits themselves.
+    pub(crate) fn exceeded_limit(&self, root: NodeId, max_depth: usize, max_nodes: usize) -> Option<&'static str> {
+        if max_depth == usize::MAX && max_nodes == usize::MAX {
+            return None;
+        }
+
+        let mut count = 0;
+        let mut stack: Vec<(NodeId, usize)> = new_vec_with_capacity(self.nodes.len());
+        stack.push((root, 1));
+        while let Some((id, depth)) = stack.pop() {
+            if depth > max_depth {
+                return Some("max_depth");
+            }
+            count += 1;
+            if count > max_nodes {
+                return Some("max_nodes_per_pass");
+            }
+            stack.extend(self.children[id].iter().map(|child| (*child, depth + 1)));
+        }
+        None
+    }
+
+    /// Returns the number of dirty nodes in the tree below `root` that can be reached through dirty nodes only
+    ///
+    /// These are the nodes that laying out the tree has to compute again, as the rest reuse their cached layouts.
+    pub(crate) fn dirty_len(&self, root: NodeId) -> usize {
+        let mut count = 0;
+        let mut stack: Vec<NodeId> = new_vec_with_capacity(self.nodes.len());
+        stack.push(root);
+        while let Some(id) = stack.pop() {
+            if self.nodes[id].is_dirty {
+                count += 1;
+                stack.extend(self.children[id].iter().copied());
+            }
+        }
+        count
+    }
+
+    /// Computes the size of the `node`, without positioning its children
+    ///
+    /// Negative available sizes are clamped to zero.
+    pub(crate) fn compute_sizes(&mut self, node: NodeId, size: Size<Option<f32>>) {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.layout_pending();
+
+        self.compute_size(node, size.map(|dimension| dimension.map(|value| value.max(0.0))))
+    }
+
+    /// Computes the size that the `node` would have with unlimited space, without changing any layout
+    pub(crate) fn max_content_size(&mut self, node: NodeId) -> Size<f32> {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.layout_pending();
+
+        self.measure_root(node, Size::undefined())
+    }
+
+    /// Computes the layout that the `node` would have with the given `style`, then restores its original style
+    ///
+    /// The layouts, caches and dirty flags of every node are put back as they were, so the tree is left unchanged.
+    pub(crate) fn compute_hypothetical(
+        &mut self,
+        node: NodeId,
+        style: FlexboxLayout,
+        size: Size<Option<f32>>,
+    ) -> Layout {
+        // Laying out the `node` overwrites the layouts and caches of its subtree, and may lay out queued nodes elsewhere,
+        // so everything is saved first and put back afterwards
+        let states: Vec<DirtyState> = self.nodes.iter().map(NodeData::dirty_state).collect();
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        let pending = self.pending.clone();
+        let (max_layout_depth, measure_count) = (self.max_layout_depth, self.measure_count);
+
+        let original = mem::replace(&mut self.nodes[node].style, style);
+        self.nodes[node].mark_dirty(&mut self.dirty_count);
+        self.compute_layout(node, size);
+        let layout = self.nodes[node].layout;
+        self.nodes[node].style = original;
+
+        for (data, state) in self.nodes.iter_mut().zip(states) {
+            data.restore_dirty_state(state, &mut self.dirty_count);
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            self.pending = pending;
+        }
+        self.max_layout_depth = max_layout_depth;
+        self.measure_count = measure_count;
+        layout
+    }
+
+    /// Computes the layout of the tree below the `node` as if neither it nor any of its descendants had a minimum or maximum size
+    ///
+    /// The computed layouts are kept, but the tree and the ancestors of the `node` are left dirty,
+    /// so the next layout applies the constraints again.
+    pub(crate) fn compute_layout_unconstrained(&mut self, node: NodeId, size: Size<Option<f32>>) {
+        let mut originals: Vec<(NodeId, Size<Dimension>, Size<Dimension>)> = new_vec_with_capacity(self.nodes.len());
+        let mut visited: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<NodeId> = new_vec_with_capacity(self.nodes.len());
+        stack.push(node);
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            let style = &mut self.nodes[id].style;
+            originals.push((id, mem::take(&mut style.min_size), mem::take(&mut style.max_size)));
+            stack.extend(self.children[id].iter().copied());
+        }
+        self.nodes[node].mark_dirty(&mut self.dirty_count);
+        self.mark_descendants_dirty(node);
+
+        self.compute_layout(node, size);
+
+        for (id, min_size, max_size) in originals {
+            self.nodes[id].style.min_size = min_size;
+            self.nodes[id].style.max_size = max_size;
+        }
+        self.mark_descendants_dirty(node);
+        self.mark_dirty_with_ancestors(node);
+    }
+
+    /// Marks every node below the `node` as dirty, visiting each of them only once
+    fn mark_descendants_dirty(&mut self, node: NodeId) {
+        let mut visited: Vec<bool> = self.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<NodeId> = new_vec_with_capacity(self.nodes.len());
+        stack.extend(self.children[node].iter().copied());
+
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            self.nodes[id].mark_dirty(&mut self.dirty_count);
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            self.nodes[id].reset_recorded_calls();
+            stack.extend(self.children[id].iter().copied());
+        }
     }
 }
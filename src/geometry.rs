@@ -29,6 +29,12 @@ pub struct Rect<T> {
 }
 
 impl<T> Rect<T> {
+    /// Creates a new [`Rect`] from the value of each of its sides
+    #[must_use]
+    pub const fn new(start: T, end: T, top: T, bottom: T) -> Self {
+        Self { start, end, top, bottom }
+    }
+
     /// Applies the function `f` to all four sides of the [`Rect`]
     ///
     /// This is used to transform a `Rect<T>` into a `Rect<R>`.
@@ -111,6 +117,12 @@ impl<T> Rect<T>
 where
     T: Copy + Clone,
 {
+    /// Creates a new [`Rect`] with the same `value` on all four sides
+    #[must_use]
+    pub const fn splat(value: T) -> Self {
+        Self { start: value, end: value, top: value, bottom: value }
+    }
+
     /// The `start` or `top` value of the [`Rect`], from the perspective of the main layout axis
     pub(crate) fn main_start(&self, direction: FlexDirection) -> T {
         if direction.is_row() {
@@ -148,6 +160,85 @@ where
     }
 }
 
+/// One of the two physical axes of a layout, regardless of the flex direction
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AbsoluteAxis {
+    /// The axis along which the width is measured
+    Horizontal,
+    /// The axis along which the height is measured
+    Vertical,
+}
+
+impl AbsoluteAxis {
+    /// The axis perpendicular to this one
+    #[must_use]
+    pub fn other(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+}
+
+/// The space available to a node along one axis, as given to its [`MeasureFunc`](crate::node::MeasureFunc)
+///
+/// The layout algorithm itself keeps available space as an `Option<f32>`, where `None` asks for the max-content size,
+/// and converts it with [`From`] before calling a measure function. It does not currently ask for
+/// [`AvailableSpace::MinContent`]: the min-content size of an item is found by giving it no space along the main axis.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AvailableSpace {
+    /// The node must fit within the given number of points
+    Definite(f32),
+    /// The node should be as narrow as it can be without overflowing its content
+    MinContent,
+    /// The node should be as wide as its content without wrapping
+    MaxContent,
+}
+
+impl AvailableSpace {
+    /// The definite amount of space, or `None` for either intrinsic size
+    #[must_use]
+    pub fn into_option(self) -> Option<f32> {
+        match self {
+            Self::Definite(value) => Some(value),
+            Self::MinContent | Self::MaxContent => None,
+        }
+    }
+
+    /// The definite amount of space, or `default` for either intrinsic size
+    #[must_use]
+    pub fn unwrap_or(self, default: f32) -> f32 {
+        self.into_option().unwrap_or(default)
+    }
+
+    /// Is this a request for the min-content or max-content size, rather than a definite amount of space?
+    #[must_use]
+    pub fn is_intrinsic(self) -> bool {
+        !matches!(self, Self::Definite(_))
+    }
+}
+
+impl From<f32> for AvailableSpace {
+    fn from(value: f32) -> Self {
+        Self::Definite(value)
+    }
+}
+
+impl From<Option<f32>> for AvailableSpace {
+    /// `None` becomes [`AvailableSpace::MaxContent`], matching how the layout algorithm uses it
+    fn from(value: Option<f32>) -> Self {
+        value.map_or(Self::MaxContent, Self::Definite)
+    }
+}
+
+impl From<AvailableSpace> for Option<f32> {
+    fn from(value: AvailableSpace) -> Self {
+        value.into_option()
+    }
+}
+
 /// The width and height of a [`Rect`]
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -168,6 +259,12 @@ impl Size<()> {
 }
 
 impl<T> Size<T> {
+    /// Creates a new [`Size`] with the provided `width` and `height`
+    #[must_use]
+    pub const fn new(width: T, height: T) -> Self {
+        Self { width, height }
+    }
+
     /// Applies the function `f` to both the width and height
     ///
     /// This is used to transform a `Rect<T>` into a `Rect<R>`.
@@ -178,48 +275,56 @@ impl<T> Size<T> {
         Size { width: f(self.width), height: f(self.height) }
     }
 
+    /// Gets the extent along the given `axis`: the width for [`AbsoluteAxis::Horizontal`], or the height otherwise
+    pub fn get(self, axis: AbsoluteAxis) -> T {
+        match axis {
+            AbsoluteAxis::Horizontal => self.width,
+            AbsoluteAxis::Vertical => self.height,
+        }
+    }
+
+    /// Sets the extent along the given `axis`: the width for [`AbsoluteAxis::Horizontal`], or the height otherwise
+    pub fn set(&mut self, axis: AbsoluteAxis, value: T) {
+        match axis {
+            AbsoluteAxis::Horizontal => self.width = value,
+            AbsoluteAxis::Vertical => self.height = value,
+        }
+    }
+
     /// Sets the extent of the main layout axis
     ///
     /// Whether this is the width or height depends on the `direction` provided
     pub(crate) fn set_main(&mut self, direction: FlexDirection, value: T) {
-        if direction.is_row() {
-            self.width = value
-        } else {
-            self.height = value
-        }
+        self.set(direction.main_axis(), value)
     }
 
     /// Sets the extent of the cross layout axis
     ///
     /// Whether this is the width or height depends on the `direction` provided
     pub(crate) fn set_cross(&mut self, direction: FlexDirection, value: T) {
-        if direction.is_row() {
-            self.height = value
-        } else {
-            self.width = value
-        }
+        self.set(direction.cross_axis(), value)
     }
 
     /// Gets the extent of the main layout axis
     ///
     /// Whether this is the width or height depends on the `direction` provided
     pub(crate) fn main(self, direction: FlexDirection) -> T {
-        if direction.is_row() {
-            self.width
-        } else {
-            self.height
-        }
+        self.get(direction.main_axis())
     }
 
     /// Gets the extent of the cross layout axis
     ///
     /// Whether this is the width or height depends on the `direction` provided
     pub(crate) fn cross(self, direction: FlexDirection) -> T {
-        if direction.is_row() {
-            self.height
-        } else {
-            self.width
-        }
+        self.get(direction.cross_axis())
+    }
+}
+
+impl<T: Copy> Size<T> {
+    /// Creates a new [`Size`] whose width and height are both `side`
+    #[must_use]
+    pub const fn square(side: T) -> Self {
+        Self { width: side, height: side }
     }
 }
 
@@ -1264,6 +1264,7 @@ use vec::Vec;
 ///
 /// A list of all Methods and Traits available for `FnvIndexMap` can be found in
 /// the [`taffy::indexmap::IndexMap`](./struct.IndexMap.html) documentation.
+#[allow(dead_code)]
 pub type FnvIndexMap<K, V, const N: usize> = IndexMap<K, V, BuildHasherDefault<FnvHasher>, N>;
 
 #[derive(Clone, Copy, Eq, PartialEq)]
@@ -1537,6 +1538,17 @@ impl<K, V, S, const N: usize> IndexMap<K, V, BuildHasherDefault<S>, N> {
     }
 }
 
+impl<K, V, S, const N: usize> IndexMap<K, V, S, N> {
+    /// Creates an empty `IndexMap` that uses the given `build_hasher`.
+    pub fn with_hasher(build_hasher: S) -> Self {
+        // Const assert
+        sealed::greater_than_1::<N>();
+        sealed::power_of_two::<N>();
+
+        IndexMap { build_hasher, core: CoreMap::new() }
+    }
+}
+
 impl<K, V, S, const N: usize> IndexMap<K, V, S, N>
 where
     K: Eq + Hash,
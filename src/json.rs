@@ -0,0 +1,156 @@
+//! Conversion of whole trees of nodes to and from JSON, enabled by the `serde` feature
+use crate::error::JsonError;
+use crate::node::{Node, Taffy};
+use crate::style::FlexboxLayout;
+use crate::sys::BuildHasher;
+
+/// The serialized form of a single node and all of its descendants
+#[derive(Serialize, Deserialize)]
+struct SerializedNode {
+    /// The style of the node
+    style: FlexboxLayout,
+    /// The children of the node, in order
+    #[serde(default)]
+    children: Vec<SerializedNode>,
+}
+
+impl<S: BuildHasher> Taffy<S> {
+    /// Serializes the tree below `root` (including `root` itself) to JSON
+    ///
+    /// Only the structure of the tree and the style of each node are stored.
+    /// [`MeasureFunc`](crate::node::MeasureFunc)s cannot be serialized and are skipped,
+    /// and a node with several parents is stored once per parent.
+    /// Returns [`JsonError::CycleDetected`] if the tree contains a cycle, which would have no end.
+    pub fn to_json(&self, root: Node) -> Result<String, JsonError> {
+        if let Some(node) = self.find_cycle(root)? {
+            return Err(JsonError::CycleDetected { node });
+        }
+        Ok(serde_json::to_string(&self.serialize_node(root)?)?)
+    }
+
+    /// Converts the `node` and its descendants into their serialized form
+    fn serialize_node(&self, node: Node) -> Result<SerializedNode, JsonError> {
+        let children =
+            self.children(node)?.into_iter().map(|child| self.serialize_node(child)).collect::<Result<_, _>>()?;
+        Ok(SerializedNode { style: *self.style(node)?, children })
+    }
+
+    /// Adds the `serialized` node and its descendants to this [`Taffy`], and returns the new node
+    fn deserialize_node(&mut self, serialized: &SerializedNode) -> Result<Node, JsonError> {
+        let children =
+            serialized.children.iter().map(|child| self.deserialize_node(child)).collect::<Result<Vec<_>, _>>()?;
+        Ok(self.new_with_children(serialized.style, &children)?)
+    }
+}
+
+impl Taffy {
+    /// Creates a new [`Taffy`] containing the tree stored in `json` by [`Taffy::to_json`], and returns it along with its root
+    ///
+    /// Every node is created with a fresh [`Node`] handle, and without a [`MeasureFunc`](crate::node::MeasureFunc).
+    pub fn from_json(json: &str) -> Result<(Taffy, Node), JsonError> {
+        let serialized: SerializedNode = serde_json::from_str(json)?;
+        let mut taffy = Taffy::new();
+        let root = taffy.deserialize_node(&serialized)?;
+        Ok((taffy, root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::{Rect, Size};
+    use crate::node::{MeasureFunc, Node, Taffy};
+    use crate::style::{AlignItems, Dimension, FlexDirection, FlexWrap, FlexboxLayout, JustifyContent};
+
+    /// Asserts that the layouts of `left` and `right` and all of their descendants are identical
+    fn assert_same_layouts(left: &Taffy, left_node: Node, right: &Taffy, right_node: Node) {
+        let (left_layout, right_layout) = (left.layout(left_node).unwrap(), right.layout(right_node).unwrap());
+        assert_eq!(left_layout.size, right_layout.size);
+        assert_eq!(left_layout.location, right_layout.location);
+        assert_eq!(left_layout.order, right_layout.order);
+
+        let (left_children, right_children) = (left.children(left_node).unwrap(), right.children(right_node).unwrap());
+        assert_eq!(left_children.len(), right_children.len());
+        for (left_child, right_child) in left_children.into_iter().zip(right_children) {
+            assert_same_layouts(left, left_child, right, right_child);
+        }
+    }
+
+    #[test]
+    fn round_trip_reproduces_layout() {
+        let mut taffy = Taffy::new();
+        let leaves: Vec<_> = (1..=5)
+            .map(|i| {
+                taffy
+                    .new_with_children(
+                        FlexboxLayout {
+                            size: Size { width: Dimension::Points(i as f32 * 15.0), height: Dimension::Percent(0.3) },
+                            margin: Rect { start: Dimension::Points(3.0), ..Default::default() },
+                            flex_grow: i as f32,
+                            ..Default::default()
+                        },
+                        &[],
+                    )
+                    .unwrap()
+            })
+            .collect();
+        let row = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::SpaceAround,
+                    size: Size { width: Dimension::Points(120.0), height: Dimension::Auto },
+                    ..Default::default()
+                },
+                &leaves,
+            )
+            .unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    size: Size { width: Dimension::Points(300.0), height: Dimension::Points(200.0) },
+                    ..Default::default()
+                },
+                &[row],
+            )
+            .unwrap();
+
+        let json = taffy.to_json(root).unwrap();
+        let (mut restored, restored_root) = Taffy::from_json(&json).unwrap();
+        assert_eq!(restored.style(restored_root).unwrap(), taffy.style(root).unwrap());
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        restored.compute_layout(restored_root, Size::undefined()).unwrap();
+        assert_same_layouts(&taffy, root, &restored, restored_root);
+    }
+
+    #[test]
+    fn measure_funcs_are_not_serialized() {
+        let mut taffy = Taffy::new();
+        let leaf =
+            taffy.new_leaf(FlexboxLayout::default(), MeasureFunc::fixed(Size { width: 10.0, height: 10.0 })).unwrap();
+
+        let (mut restored, restored_leaf) = Taffy::from_json(&taffy.to_json(leaf).unwrap()).unwrap();
+        restored.compute_layout(restored_leaf, Size::undefined()).unwrap();
+        assert_eq!(restored.layout(restored_leaf).unwrap().size, Size::zero());
+    }
+
+    #[test]
+    fn invalid_json_is_rejected() {
+        assert!(Taffy::from_json("{\"children\": []}").is_err());
+        assert!(Taffy::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn cycles_are_not_serialized() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[leaf]).unwrap();
+        taffy.add_child(leaf, root).unwrap();
+        assert!(matches!(taffy.to_json(root), Err(crate::error::JsonError::CycleDetected { .. })));
+
+        taffy.remove_child(leaf, root).unwrap();
+        assert!(taffy.to_json(root).is_ok());
+    }
+}
@@ -9,6 +9,7 @@ pub struct Layout {
     ///
     /// Nodes with a higher order should be rendered on top of those with a lower order.
     /// This is effectively a topological sort of each tree.
+    /// It is the index of the node among the children of its parent, in the order of [`Taffy::children`](crate::node::Taffy::children).
     pub order: u32,
     /// The width and height of the node
     pub size: Size<f32>,
@@ -22,6 +23,52 @@ impl Layout {
     pub(crate) fn new() -> Self {
         Self { order: 0, size: Size::zero(), location: Point::zero() }
     }
+
+    /// Interpolates linearly between the layouts `a` and `b`, for animating between them
+    ///
+    /// The location and size are interpolated component-wise, with `t` clamped to the range `[0, 1]`:
+    /// `a` is returned at 0, and `b` at 1. The order cannot be interpolated, so it is taken from whichever layout is nearer.
+    #[must_use]
+    pub fn lerp(a: &Layout, b: &Layout, t: f32) -> Layout {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Layout {
+            order: if t < 0.5 { a.order } else { b.order },
+            size: Size { width: lerp(a.size.width, b.size.width), height: lerp(a.size.height, b.size.height) },
+            location: Point { x: lerp(a.location.x, b.location.x), y: lerp(a.location.y, b.location.y) },
+        }
+    }
+
+    /// Returns the location and size of the node as `[x, y, width, height]`
+    #[must_use]
+    pub fn as_xywh(&self) -> [f32; 4] {
+        [self.location.x, self.location.y, self.size.width, self.size.height]
+    }
+}
+
+/// A [`Layout`] with a fixed, C-compatible representation, for passing layouts across an FFI boundary
+///
+/// The order of the fields is part of the ABI, and will not change.
+#[repr(C)]
+#[derive(Copy, Debug, Clone, PartialEq)]
+pub struct LayoutC {
+    /// The horizontal position of the node, relative to its parent
+    pub x: f32,
+    /// The vertical position of the node, relative to its parent
+    pub y: f32,
+    /// The width of the node
+    pub width: f32,
+    /// The height of the node
+    pub height: f32,
+    /// The relative ordering of the node, as in [`Layout::order`]
+    pub order: u32,
+}
+
+impl From<Layout> for LayoutC {
+    fn from(layout: Layout) -> Self {
+        let [x, y, width, height] = layout.as_xywh();
+        Self { x, y, width, height, order: layout.order }
+    }
 }
 
 /// Cached intermediate layout results
@@ -33,6 +80,8 @@ pub(crate) struct Cache {
     pub(crate) parent_size: Size<Option<f32>>,
     /// Whether or not layout should be recomputed
     pub(crate) perform_layout: bool,
+    /// The measure version of the node when the size was cached
+    pub(crate) measure_version: u32,
 
     /// The cached size of the item
     pub(crate) size: Size<f32>,
@@ -25,10 +25,20 @@ pub mod node;
 pub mod prelude;
 pub mod style;
 
+#[cfg(feature = "binary")]
+mod binary;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod dirty;
 mod flexbox;
 mod forest;
 #[cfg(all(not(feature = "alloc"), not(feature = "std")))]
 mod indexmap;
+#[cfg(all(feature = "serde", feature = "std"))]
+mod json;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "std")]
+mod svg;
 mod sys;
 
 pub use crate::node::Taffy;
@@ -2,29 +2,151 @@
 //!
 //! Layouts are composed of multiple nodes, which live in a forest-like data structure.
 use crate::error;
-use crate::forest::Forest;
-use crate::geometry::Size;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::forest::Observer;
+use crate::forest::{DirtyState, Forest};
+use crate::geometry::{AbsoluteAxis, AvailableSpace, Point, Rect, Size};
 use crate::layout::Layout;
-use crate::style::FlexboxLayout;
+use crate::style::{Dimension, Display, FlexboxLayout};
+pub use crate::sys::DefaultHashBuilder;
+use crate::sys::{new_map_with_capacity_and_hasher, new_vec_with_capacity, BuildHasher, ChildrenVec, Map, Vec};
 #[cfg(any(feature = "std", feature = "alloc"))]
-use crate::sys::Box;
-use crate::sys::{new_map_with_capacity, ChildrenVec, Map, Vec};
-use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::sys::{Arc, Box};
+#[cfg(feature = "small-ids")]
+use core::sync::atomic::AtomicU32 as AtomicRawId;
+#[cfg(not(feature = "small-ids"))]
+use core::sync::atomic::AtomicUsize as AtomicRawId;
+use core::sync::atomic::Ordering;
 
 /// A function type that can be used in a [`MeasureFunc`]
 ///
 /// This trait is automatically implemented for all types (including closures) that define a function with the appropriate type signature.
-pub trait Measurable: Send + Sync + Fn(Size<Option<f32>>) -> Size<f32> {}
+/// The function is given the [`AvailableSpace`] along each axis, and returns the size of the content.
+pub trait Measurable: Send + Sync + Fn(Size<AvailableSpace>) -> Size<f32> {}
 
-impl<F: Send + Sync + Fn(Size<Option<f32>>) -> Size<f32>> Measurable for F {}
+impl<F: Send + Sync + Fn(Size<AvailableSpace>) -> Size<f32>> Measurable for F {}
 
 /// A function that can be used to compute the intrinsic size of a node
 pub enum MeasureFunc {
     /// Stores an unboxed function
-    Raw(fn(Size<Option<f32>>) -> Size<f32>),
+    Raw(fn(Size<AvailableSpace>) -> Size<f32>),
     /// Stores a boxed function
     #[cfg(any(feature = "std", feature = "alloc"))]
     Boxed(Box<dyn Measurable>),
+    /// Stores a reference-counted function, which can be shared between many nodes without boxing it for each of them
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    Shared(Arc<dyn Measurable>),
+    /// Always reports the stored size, regardless of the available space
+    Fixed(Size<f32>),
+}
+
+impl MeasureFunc {
+    /// Creates a [`MeasureFunc`] that ignores its input and always reports the provided `size`
+    ///
+    /// This is useful for content with an intrinsic size, such as images or fixed-size widgets.
+    /// The ratio of the width to the height of the `size` is also used as the intrinsic aspect ratio of the node.
+    #[must_use]
+    pub fn fixed(size: Size<f32>) -> Self {
+        Self::Fixed(size)
+    }
+
+    /// Calls this measure function with the `available` space, and returns the size it reports
+    pub(crate) fn measure(&self, available: Size<AvailableSpace>) -> Size<f32> {
+        match self {
+            MeasureFunc::Raw(measure) => measure(available),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            MeasureFunc::Boxed(measure) => measure(available),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            MeasureFunc::Shared(measure) => measure(available),
+            MeasureFunc::Fixed(size) => *size,
+        }
+    }
+
+    /// The intrinsic aspect ratio implied by this measure function, calculated as width divided by height
+    ///
+    /// Only [`MeasureFunc::Fixed`] implies a ratio, as long as its height is positive.
+    /// Other measure functions report theirs through [`Taffy::set_intrinsic_aspect_ratio`].
+    pub(crate) fn aspect_ratio(&self) -> Option<f32> {
+        match self {
+            MeasureFunc::Fixed(size) if size.height > 0.0 => Some(size.width / size.height),
+            _ => None,
+        }
+    }
+}
+
+/// Controls how the results of the [`MeasureFunc`] of a node are reused, set with [`Taffy::set_measure_cache_mode`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CacheMode {
+    /// Results are reused while the node stays clean and is given compatible constraints, as with any other node
+    Always,
+    /// The first result is reused forever, whatever constraints the node is given later
+    ///
+    /// The stored result is only forgotten by [`Taffy::set_measure`], [`Taffy::mark_dirty`] or
+    /// [`Taffy::set_measure_cache_mode`] on the node itself. Changing its style, the scale factor, or the size
+    /// of its parent does not measure it again, so this must only be used for content that never changes size,
+    /// such as a static label: text that would wrap differently in a narrower parent keeps its first size.
+    OncePermanent,
+    /// Results are never reused, so the [`MeasureFunc`] is called every time the node is sized
+    Never,
+}
+
+impl Default for CacheMode {
+    fn default() -> Self {
+        Self::Always
+    }
+}
+
+/// How far [`Taffy::compute_layout_budgeted`] got within its budget
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LayoutStatus {
+    /// The layout of the tree is up to date
+    Complete,
+    /// Some of the work is left, and can be finished with [`Taffy::resume_layout`]
+    Partial {
+        /// The layout that was started, to pass to [`Taffy::resume_layout`]
+        resume_token: ResumeToken,
+    },
+}
+
+/// A layout started by [`Taffy::compute_layout_budgeted`] that has yet to be finished
+///
+/// The work done so far is kept in the [`Taffy`] itself, so a token only records which layout to continue.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ResumeToken {
+    /// The root of the tree being laid out
+    node: Node,
+    /// The available space it is being laid out in
+    size: Size<Option<f32>>,
+}
+
+/// The dirty flags of the nodes of a [`Taffy`], taken by [`Taffy::snapshot_dirty`] and put back by [`Taffy::restore_dirty`]
+///
+/// The caches and layouts of each node are kept along with its flag, as a clean node must match its layout.
+#[derive(Clone, Debug)]
+pub struct DirtySnapshot {
+    /// The state of each node that existed when the snapshot was taken
+    nodes: Vec<(Node, DirtyState)>,
+    /// The nodes that were queued by size-aware dirtying
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pending: Vec<Node>,
+}
+
+/// Callbacks that are invoked while the layout of a [`Taffy`] is computed, for profiling and instrumentation
+///
+/// Every method does nothing by default, so only the events of interest need to be implemented.
+/// Subtrees that are laid out in parallel with the `parallel` feature are not reported.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub trait LayoutObserver: Send + Sync {
+    /// Called when the algorithm starts computing the size of the `node`, including when it is found in the cache
+    fn on_node_enter(&mut self, _node: Node) {}
+
+    /// Called after the [`MeasureFunc`] of the `node` was called with the `input` constraints, and returned `output`
+    fn on_measure(&mut self, _node: Node, _input: Size<AvailableSpace>, _output: Size<f32>) {}
+
+    /// Called when the algorithm has finished computing the size of the `node`, which was found to be `size`
+    ///
+    /// Each call matches the most recent [`LayoutObserver::on_node_enter`] for the same node that has not yet exited.
+    fn on_node_exit(&mut self, _node: Node, _size: Size<f32>) {}
 }
 
 /// Global taffy instance id allocator.
@@ -40,18 +162,36 @@ pub struct Node {
     local: Id,
 }
 
+impl Node {
+    /// The identifier of this node within its [`Taffy`], which is unique among the nodes it has allocated
+    #[cfg(feature = "std")]
+    pub(crate) fn local_id(self) -> RawId {
+        self.local.0
+    }
+}
+
 /// A forest of UI [`Nodes`](`Node`), suitable for UI layout
-pub struct Taffy {
+///
+/// Each [`Node`] is looked up in a map that hashes it with `S`, which can be replaced with [`Taffy::with_hasher`].
+pub struct Taffy<S = DefaultHashBuilder> {
     /// The ID of the root node
     id: Id,
     /// A monotonically-increasing index that tracks the [`Id`] of the next node
     allocator: Allocator,
     /// A map from Node -> NodeId
-    nodes_to_ids: Map<Node, NodeId>,
+    nodes_to_ids: Map<Node, NodeId, S>,
     /// A map from NodeId -> Node
-    ids_to_nodes: Map<NodeId, Node>,
+    ids_to_nodes: Map<NodeId, Node, S>,
     /// An efficient data structure that stores the node trees
     forest: Forest,
+    /// The node designated as the root of the main tree, if any
+    root: Option<Node>,
+    /// The most levels a tree can have and still be laid out
+    max_depth: usize,
+    /// The most nodes a tree can contain and still be laid out
+    max_nodes_per_pass: usize,
+    /// Should setting a style lay out its tree again straight away?
+    eager_layout: bool,
 }
 
 impl Default for Taffy {
@@ -71,15 +211,53 @@ impl Taffy {
 
     /// Creates a new [`Taffy`] that can store `capacity` nodes before reallocation
     pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, DefaultHashBuilder::default())
+    }
+
+    /// Creates a new [`Taffy`] with the given `instance_id`, instead of one taken from a global counter
+    ///
+    /// The ids of nodes are allocated from zero within each instance, so this makes every [`Node`] reproducible,
+    /// for example in snapshot tests that print them. Nodes can only be told apart from the nodes of
+    /// another instance by the instance id, so two instances that are used together should not share one.
+    ///
+    /// # Panics
+    ///
+    /// With the `small-ids` feature, panics if the `instance_id` does not fit in a `u32`.
+    #[must_use]
+    pub fn with_instance_id(instance_id: usize) -> Self {
+        #[cfg(feature = "small-ids")]
+        let instance_id = RawId::try_from(instance_id).expect("instance ids must fit in a u32 with small-ids");
+        Self { id: Id(instance_id), ..Self::with_capacity(16) }
+    }
+}
+
+impl<S: BuildHasher + Clone> Taffy<S> {
+    /// Creates a new [`Taffy`] that hashes nodes with the `hasher`, instead of the [`DefaultHashBuilder`]
+    ///
+    /// A deterministic hasher makes every run reproducible, a fast one such as FxHash speeds up node lookups,
+    /// and a keyed one such as SipHash resists collisions crafted from untrusted input.
+    /// The default capacity of a [`Taffy`] is 16 nodes.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_capacity_and_hasher(16, hasher)
+    }
+
+    /// Creates a new [`Taffy`] that can store `capacity` nodes before reallocation, and hashes nodes with the `hasher`
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
         Self {
             id: INSTANCE_ALLOCATOR.allocate(),
             allocator: Allocator::new(),
-            nodes_to_ids: new_map_with_capacity(capacity),
-            ids_to_nodes: new_map_with_capacity(capacity),
+            nodes_to_ids: new_map_with_capacity_and_hasher(capacity, hasher.clone()),
+            ids_to_nodes: new_map_with_capacity_and_hasher(capacity, hasher),
             forest: Forest::with_capacity(capacity),
+            root: None,
+            max_depth: usize::MAX,
+            max_nodes_per_pass: usize::MAX,
+            eager_layout: false,
         }
     }
+}
 
+impl<S: BuildHasher> Taffy<S> {
     /// Allocates memory for a new node, and returns a matching generated [`Node`]
     fn allocate_node(&mut self) -> Node {
         let local = self.allocator.allocate();
@@ -93,15 +271,48 @@ impl Taffy {
     }
 
     /// Returns the `NodeId` of the provided node within the forest
+    ///
+    /// With the `debug-checks` feature, this panics instead of returning an error,
+    /// so that the use of a stale [`Node`] is reported where it happens.
     fn find_node(&self, node: Node) -> Result<NodeId, error::InvalidNode> {
         match self.nodes_to_ids.get(&node) {
             Some(id) => Ok(*id),
+            #[cfg(feature = "debug-checks")]
+            None => panic!("{}", self.describe_invalid_node(node)),
+            #[cfg(not(feature = "debug-checks"))]
             None => Err(error::InvalidNode(node)),
         }
     }
 
+    /// Explains why the `node` could not be found in this [`Taffy`]
+    #[cfg(feature = "debug-checks")]
+    fn describe_invalid_node(&self, node: Node) -> String {
+        let hint = if node.instance != self.id {
+            format!("it belongs to a different Taffy instance (id {})", node.instance.0)
+        } else if node.local.0 < self.allocator.last_id.load(Ordering::Relaxed) {
+            "it belongs to this Taffy instance, but has been removed or cleared".to_string()
+        } else {
+            "it belongs to this Taffy instance, but was never allocated by it".to_string()
+        };
+        format!(
+            "Node with local id {} of Taffy instance {} is not in Taffy instance {}: {}",
+            node.local.0, node.instance.0, self.id.0, hint
+        )
+    }
+
+    /// Checks that every numeric field of the `style` is finite
+    fn validate_style(style: &FlexboxLayout) -> Result<(), error::StyleError> {
+        match style.non_finite_field() {
+            Some(field) => Err(error::StyleError::NonFinite { field }),
+            None => Ok(()),
+        }
+    }
+
     /// Adds a new leaf node, which does not have any children
-    pub fn new_leaf(&mut self, style: FlexboxLayout, measure: MeasureFunc) -> Result<Node, error::InvalidNode> {
+    ///
+    /// Returns [`StyleError::NonFinite`](error::StyleError::NonFinite) if any numeric field of the `style` is NaN or infinite.
+    pub fn new_leaf(&mut self, style: FlexboxLayout, measure: MeasureFunc) -> Result<Node, error::StyleError> {
+        Self::validate_style(&style)?;
         let node = self.allocate_node();
         let id = self.forest.new_leaf(style, measure);
         self.add_node(node, id);
@@ -109,7 +320,10 @@ impl Taffy {
     }
 
     /// Adds a new node, which may have any number of `children`
-    pub fn new_with_children(&mut self, style: FlexboxLayout, children: &[Node]) -> Result<Node, error::InvalidNode> {
+    ///
+    /// Returns [`StyleError::NonFinite`](error::StyleError::NonFinite) if any numeric field of the `style` is NaN or infinite.
+    pub fn new_with_children(&mut self, style: FlexboxLayout, children: &[Node]) -> Result<Node, error::StyleError> {
+        Self::validate_style(&style)?;
         let node = self.allocate_node();
         let children = children
             .iter()
@@ -127,6 +341,7 @@ impl Taffy {
         self.nodes_to_ids.clear();
         self.ids_to_nodes.clear();
         self.forest.clear();
+        self.root = None;
     }
 
     /// Remove a specific [`Node`] from the tree
@@ -137,6 +352,9 @@ impl Taffy {
 
         self.nodes_to_ids.remove(&node);
         self.ids_to_nodes.remove(&id);
+        if self.root == Some(node) {
+            self.root = None;
+        }
 
         if let Some(new_id) = self.forest.swap_remove(id) {
             let new = self.ids_to_nodes.remove(&new_id).unwrap();
@@ -147,10 +365,65 @@ impl Taffy {
         Ok(id)
     }
 
+    /// Removes each of the `nodes` from the tree, as with [`Taffy::remove`]
+    ///
+    /// Every node is checked before any is removed, so nothing is removed if one of them is invalid.
+    /// The nodes are removed from the highest id to the lowest, so that no node to be removed is moved before its turn.
+    /// Returns the id of each of the `nodes`, in the same order.
+    pub fn remove_many(&mut self, nodes: &[Node]) -> Result<Vec<usize>, error::InvalidNode> {
+        let mut ids = new_vec_with_capacity(nodes.len());
+        for node in nodes {
+            ids.push(self.find_node(*node)?);
+        }
+
+        let mut sorted: Vec<(NodeId, Node)> = ids.iter().copied().zip(nodes.iter().copied()).collect();
+        sorted.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+        let mut previous = None;
+        for (id, node) in sorted {
+            // A node that is listed twice is only removed once
+            if previous != Some(id) {
+                self.remove(node)?;
+                previous = Some(id);
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Designates the `node` as the root of the main tree, for use with [`Taffy::compute_layout_root`] and [`Taffy::layout_root`]
+    ///
+    /// The designation is removed if the `node` is removed.
+    pub fn set_root(&mut self, node: Node) -> Result<(), error::InvalidNode> {
+        self.find_node(node)?;
+        self.root = Some(node);
+        Ok(())
+    }
+
+    /// Returns the node designated as the root of the main tree, if any
+    #[must_use]
+    pub fn root(&self) -> Option<Node> {
+        self.root
+    }
+
+    /// Returns the number of nodes currently stored in the tree
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.forest.nodes.len()
+    }
+
+    /// Returns the number of nodes that can be stored in the tree before reallocation
+    ///
+    /// This is always at least [`Taffy::node_count`]; removed nodes do not reduce it.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.forest.nodes.capacity()
+    }
+
     /// Sets the [`MeasureFunc`] of the associated node
     pub fn set_measure(&mut self, node: Node, measure: Option<MeasureFunc>) -> Result<(), error::InvalidNode> {
         let id = self.find_node(node)?;
         self.forest.nodes[id].measure = measure;
+        self.forest.nodes[id].permanent_measure = None;
         self.forest.mark_dirty(id);
         Ok(())
     }
@@ -164,10 +437,31 @@ impl Taffy {
         Ok(())
     }
 
+    /// Adds a new leaf node, and appends it to the `children` of the supplied `parent`
+    ///
+    /// The `parent` and the `style` are both validated before the leaf is created.
+    pub fn append_new_leaf(
+        &mut self,
+        parent: Node,
+        style: FlexboxLayout,
+        measure: MeasureFunc,
+    ) -> Result<Node, error::StyleError> {
+        let parent_id = self.find_node(parent)?;
+        Self::validate_style(&style)?;
+        let node = self.allocate_node();
+        let id = self.forest.new_leaf(style, measure);
+        self.add_node(node, id);
+        self.forest.add_child(parent_id, id);
+        Ok(node)
+    }
+
     /// Directly sets the `children` of the supplied `parent`
     pub fn set_children(&mut self, parent: Node, children: &[Node]) -> Result<(), error::InvalidNode> {
         let node_id = self.find_node(parent)?;
         let children_id = children.iter().map(|child| self.find_node(*child)).collect::<Result<ChildrenVec<_>, _>>()?;
+        if self.forest.content_cache && self.forest.children[node_id] == children_id {
+            return Ok(());
+        }
 
         // Remove node as parent from all its current children.
         for child in &self.forest.children[node_id] {
@@ -180,19 +474,72 @@ impl Taffy {
         }
         self.forest.children[node_id] = children_id;
 
+        self.forest.mark_structurally_changed(node_id);
         self.forest.mark_dirty(node_id);
         Ok(())
     }
 
+    /// Replaces all of the children of the supplied `parent` with `new_root_children`, and returns the children it detached
+    ///
+    /// The detached children and their descendants are kept in the forest, so the caller can reuse or remove them.
+    /// Old children that are also among `new_root_children` stay attached, and are not returned.
+    /// The `parent` is marked as dirty once.
+    pub fn replace_children_subtree(
+        &mut self,
+        parent: Node,
+        new_root_children: &[Node],
+    ) -> Result<Vec<Node>, error::InvalidChild> {
+        let node_id = self.find_node(parent).map_err(|e| error::InvalidChild::InvalidParentNode(e.0))?;
+        let children_id = new_root_children
+            .iter()
+            .map(|child| self.find_node(*child).map_err(|e| error::InvalidChild::InvalidChildNode(e.0)))
+            .collect::<Result<ChildrenVec<_>, _>>()?;
+
+        let mut detached = Vec::new();
+        for child in &self.forest.children[node_id] {
+            self.forest.parents[*child].retain(|p| *p != node_id);
+            if !children_id.contains(child) && !detached.contains(&self.ids_to_nodes[child]) {
+                detached.push(self.ids_to_nodes[child]);
+            }
+        }
+
+        for child in &children_id {
+            self.forest.parents[*child].push(node_id);
+        }
+        self.forest.children[node_id] = children_id;
+
+        self.forest.mark_structurally_changed(node_id);
+        self.forest.mark_dirty(node_id);
+        Ok(detached)
+    }
+
+    /// Changes the `children` of the supplied `parent` to the `target` list, with as few changes as possible
+    ///
+    /// This gives the same result as [`Taffy::set_children`], but the children that stay under the `parent`,
+    /// even in another position, are left untouched, and only those that enter or leave it are linked or unlinked.
+    /// The `parent` is marked as dirty once, and only if its children have changed.
+    pub fn reconcile_children(&mut self, parent: Node, target: &[Node]) -> Result<(), error::InvalidChild> {
+        let node_id = self.find_node(parent).map_err(|e| error::InvalidChild::InvalidParentNode(e.0))?;
+        let target = target
+            .iter()
+            .map(|child| self.find_node(*child).map_err(|e| error::InvalidChild::InvalidChildNode(e.0)))
+            .collect::<Result<ChildrenVec<_>, _>>()?;
+        self.forest.reconcile_children(node_id, target);
+        Ok(())
+    }
+
     /// Removes the `child` of the parent `node`
     ///
     /// The child is not removed from the forest entirely, it is simply no longer attached to its previous parent.
+    /// Returns [`InvalidNode`](error::InvalidNode) for the `child` if it is not a child of the `parent`.
     pub fn remove_child(&mut self, parent: Node, child: Node) -> Result<Node, error::InvalidNode> {
         let node_id = self.find_node(parent)?;
         let child_id = self.find_node(child)?;
 
-        let prev_id = self.forest.remove_child(node_id, child_id);
-        Ok(self.ids_to_nodes[&prev_id])
+        match self.forest.remove_child(node_id, child_id) {
+            Some(prev_id) => Ok(self.ids_to_nodes[&prev_id]),
+            None => Err(error::InvalidNode(child)),
+        }
     }
 
     /// Removes the child at the given `index` from the `parent`
@@ -229,13 +576,34 @@ impl Taffy {
 
         self.forest.parents[child_id].push(node_id);
         let old_child = core::mem::replace(&mut self.forest.children[node_id][child_index], child_id);
-        self.forest.parents[old_child].retain(|p| *p != node_id);
+        self.forest.unlink_parent(old_child, node_id);
 
+        self.forest.mark_structurally_changed(node_id);
         self.forest.mark_dirty(node_id);
 
         Ok(self.ids_to_nodes[&old_child])
     }
 
+    /// Swaps the children of the `parent` at the indices `a` and `b`
+    ///
+    /// Both children stay attached, so only the order of the children changes. Swapping an index with itself does nothing.
+    pub fn swap_children(&mut self, parent: Node, a: usize, b: usize) -> Result<(), error::InvalidChild> {
+        let node_id = self.find_node(parent).map_err(|e| error::InvalidChild::InvalidParentNode(e.0))?;
+
+        let child_count = self.forest.children[node_id].len();
+        if let Some(child_index) = [a, b].into_iter().find(|index| *index >= child_count) {
+            return Err(error::InvalidChild::ChildIndexOutOfBounds { parent, child_index, child_count });
+        }
+        if a == b {
+            return Ok(());
+        }
+
+        self.forest.children[node_id].swap(a, b);
+        self.forest.mark_structurally_changed(node_id);
+        self.forest.mark_dirty(node_id);
+        Ok(())
+    }
+
     /// Returns the child [`Node`] of the parent `node` at the provided `child_index`
     pub fn child_at_index(&self, parent: Node, child_index: usize) -> Result<Node, error::InvalidChild> {
         let id = self.find_node(parent).map_err(|e| error::InvalidChild::InvalidParentNode(e.0))?;
@@ -254,17 +622,181 @@ impl Taffy {
         Ok(self.forest.children[id].len())
     }
 
+    /// Returns whether the `node` has no children
+    ///
+    /// A node with a measure function is usually a leaf, but any node without children counts as one.
+    pub fn is_leaf(&self, node: Node) -> Result<bool, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.children[id].is_empty())
+    }
+
+    /// Returns the number of children of each of the `parents`, in the same order
+    pub fn child_counts(&self, parents: &[Node]) -> Result<Vec<usize>, error::InvalidNode> {
+        parents.iter().map(|parent| Ok(self.forest.children[self.find_node(*parent)?].len())).collect()
+    }
+
     /// Returns a list of children that belong to the [`Parent`]
+    ///
+    /// Children are always listed in the order they were inserted in, as changed by the methods that reorder them
+    /// such as [`Taffy::swap_children`]. This is the order in which they are laid out and painted, so the
+    /// [`Layout::order`] of each child is its index here, and every accessor and traversal of the children
+    /// of a node, such as [`Taffy::children_iter`] and [`Taffy::child_at_index`], follows the same order.
     pub fn children(&self, parent: Node) -> Result<Vec<Node>, error::InvalidNode> {
         let id = self.find_node(parent)?;
         Ok(self.forest.children[id].iter().map(|child| self.ids_to_nodes[child]).collect())
     }
 
+    /// Returns an iterator over the children that belong to the [`Parent`], without allocating
+    ///
+    /// Children are stored internally by their position in the forest rather than as [`Nodes`](Node),
+    /// so they cannot be borrowed as a slice of nodes: each one is looked up as the iterator advances.
+    pub fn children_iter(&self, parent: Node) -> Result<impl Iterator<Item = Node> + '_, error::InvalidNode> {
+        let id = self.find_node(parent)?;
+        Ok(self.forest.children[id].iter().map(|child| self.ids_to_nodes[child]))
+    }
+
+    /// Returns the children that belong to the [`Parent`], each paired with its index
+    pub fn children_enumerated(&self, parent: Node) -> Result<Vec<(usize, Node)>, error::InvalidNode> {
+        let id = self.find_node(parent)?;
+        Ok(self.forest.children[id].iter().map(|child| self.ids_to_nodes[child]).enumerate().collect())
+    }
+
+    /// Returns the first [`Node`] in the tree below `root` (including `root` itself) for which `pred` returns `true`
+    ///
+    /// Nodes are visited in preorder: each node is tested before its children, and children are tested in order.
+    /// Each node is tested at most once, even if it can be reached through several parents or through a cycle.
+    pub fn find<F: Fn(Node, &FlexboxLayout) -> bool>(
+        &self,
+        root: Node,
+        pred: F,
+    ) -> Result<Option<Node>, error::InvalidNode> {
+        let root = self.find_node(root)?;
+        let mut visited: Vec<bool> = self.forest.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<NodeId> = new_vec_with_capacity(self.forest.nodes.len());
+        stack.push(root);
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            let node = self.ids_to_nodes[&id];
+            if pred(node, &self.forest.nodes[id].style) {
+                return Ok(Some(node));
+            }
+            stack.extend(self.forest.children[id].iter().rev().filter(|child| !visited[**child]).copied());
+        }
+        Ok(None)
+    }
+
+    /// Returns every [`Node`] in the tree below `root` (including `root` itself) as a set
+    ///
+    /// This makes it cheap to intersect a selection of nodes with a subtree.
+    /// Nodes that can be reached through several parents, or through a cycle, are only included once.
+    #[cfg(feature = "std")]
+    pub fn subtree_nodes(&self, root: Node) -> Result<std::collections::HashSet<Node>, error::InvalidNode> {
+        let root = self.find_node(root)?;
+        let mut nodes = std::collections::HashSet::new();
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            if nodes.insert(self.ids_to_nodes[&id]) {
+                stack.extend(self.forest.children[id].iter().copied());
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// Calls `f` with every [`Node`] in the tree below `root` (including `root` itself), visiting children before their parents
+    ///
+    /// Children are visited in order, so `root` is always visited last.
+    /// Each node is visited at most once, even if it can be reached through several parents or through a cycle.
+    pub fn traverse_postorder<F: FnMut(Node)>(&self, root: Node, mut f: F) -> Result<(), error::InvalidNode> {
+        let root = self.find_node(root)?;
+        let mut visited: Vec<bool> = self.forest.nodes.iter().map(|_| false).collect();
+        // Each entry is a node, and whether its children have already been pushed
+        let mut stack: Vec<(NodeId, bool)> = new_vec_with_capacity(self.forest.nodes.len());
+        stack.push((root, false));
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                f(self.ids_to_nodes[&id]);
+            } else if !visited[id] {
+                visited[id] = true;
+                stack.push((id, true));
+                stack.extend(self.forest.children[id].iter().rev().map(|child| (*child, false)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls `f` with every [`Node`] in the tree below `root` (including `root` itself) in breadth-first order,
+    /// along with its depth below `root`
+    ///
+    /// `root` has a depth of 0, and the children of each node are visited in order.
+    /// Each node is visited at most once, at the shallowest depth it can be reached, even if the tree contains a cycle.
+    pub fn traverse_bfs<F: FnMut(Node, usize)>(&self, root: Node, mut f: F) -> Result<(), error::InvalidNode> {
+        let root = self.find_node(root)?;
+        let mut visited: Vec<bool> = self.forest.nodes.iter().map(|_| false).collect();
+        // Nodes are only ever queued once, so the queue never needs more room than there are nodes
+        let mut queue: Vec<(NodeId, usize)> = new_vec_with_capacity(self.forest.nodes.len());
+        visited[root] = true;
+        queue.push((root, 0));
+        let mut next = 0;
+        while let Some(&(id, depth)) = queue.get(next) {
+            next += 1;
+            f(self.ids_to_nodes[&id], depth);
+            for child in &self.forest.children[id] {
+                if !visited[*child] {
+                    visited[*child] = true;
+                    queue.push((*child, depth + 1));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Sets the [`Style`] of the provided `node`
-    pub fn set_style(&mut self, node: Node, style: FlexboxLayout) -> Result<(), error::InvalidNode> {
+    ///
+    /// Returns [`StyleError::NonFinite`](error::StyleError::NonFinite) if any numeric field of the `style` is NaN or infinite,
+    /// in which case the style is left unchanged.
+    pub fn set_style(&mut self, node: Node, style: FlexboxLayout) -> Result<(), error::StyleError> {
         let id = self.find_node(node)?;
-        self.forest.nodes[id].style = style;
-        self.forest.mark_dirty(id);
+        Self::validate_style(&style)?;
+        self.forest.set_style(id, style);
+        self.lay_out_eagerly(&[id]);
+        Ok(())
+    }
+
+    /// Sets only the [`gap`](FlexboxLayout::gap) of the provided `node`, leaving the rest of its style unchanged
+    ///
+    /// This is a shorthand for animating the spacing between children, and dirties the `node` like [`Taffy::set_style`].
+    /// Returns [`StyleError::NonFinite`](error::StyleError::NonFinite) if the `gap` is NaN or infinite,
+    /// in which case the gap is left unchanged.
+    pub fn set_gap(&mut self, node: Node, gap: Size<Dimension>) -> Result<(), error::StyleError> {
+        let id = self.find_node(node)?;
+        let style = FlexboxLayout { gap, ..self.forest.nodes[id].style };
+        Self::validate_style(&style)?;
+        self.forest.set_style(id, style);
+        self.lay_out_eagerly(&[id]);
+        Ok(())
+    }
+
+    /// Sets the [`Style`] of each node in `styles`, dirtying the affected nodes in a single pass
+    ///
+    /// This is equivalent to calling [`Taffy::set_style`] for each entry, but each ancestor is only dirtied once.
+    /// Every node and style is validated first: if any of them is rejected, no style is changed.
+    #[cfg(feature = "std")]
+    pub fn apply_styles(
+        &mut self,
+        styles: &std::collections::HashMap<Node, FlexboxLayout>,
+    ) -> Result<(), error::StyleError> {
+        let mut validated = Vec::with_capacity(styles.len());
+        for (node, style) in styles {
+            let id = self.find_node(*node)?;
+            Self::validate_style(style)?;
+            validated.push((id, *style));
+        }
+        let ids: Vec<NodeId> = validated.iter().map(|(id, _)| *id).collect();
+        self.forest.set_styles(validated);
+        self.lay_out_eagerly(&ids);
         Ok(())
     }
 
@@ -274,57 +806,944 @@ impl Taffy {
         Ok(&self.forest.nodes[id].style)
     }
 
+    /// Returns the styles of the `node` and of each of its ancestors, starting with the `node` and ending with the root
+    ///
+    /// Where a node has several parents, the first one is followed.
+    /// This is useful for resolving inherited properties without looking up each ancestor separately.
+    pub fn styles_along_ancestry(&self, node: Node) -> Result<Vec<&FlexboxLayout>, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(core::iter::once(id).chain(self.forest.ancestors(id)).map(|id| &self.forest.nodes[id].style).collect())
+    }
+
     /// Return this node layout relative to its parent
     pub fn layout(&self, node: Node) -> Result<&Layout, error::InvalidNode> {
         let id = self.find_node(node)?;
         Ok(&self.forest.nodes[id].layout)
     }
 
+    /// Returns the [`Layout`] of the designated root node, if there is one
+    #[must_use]
+    pub fn layout_root(&self) -> Option<&Layout> {
+        self.root.map(|root| &self.forest.nodes[self.nodes_to_ids[&root]].layout)
+    }
+
+    /// Returns the layout of the `parent` node, along with each of its children and their layouts, relative to the `parent`
+    pub fn layout_with_children(&self, parent: Node) -> Result<(Layout, Vec<(Node, Layout)>), error::InvalidNode> {
+        let id = self.find_node(parent)?;
+        let children = self.forest.children[id]
+            .iter()
+            .map(|child| (self.ids_to_nodes[child], self.forest.nodes[*child].layout))
+            .collect();
+        Ok((self.forest.nodes[id].layout, children))
+    }
+
+    /// Returns the layout of this node relative to the root of its tree, translated by `origin`
+    ///
+    /// This is useful when the whole tree is embedded at an offset inside a larger scene.
+    /// Where a node has several parents, the location is computed through the first one.
+    pub fn layout_absolute_offset(&self, node: Node, origin: Point<f32>) -> Result<Layout, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        let location = self.forest.absolute_location(id);
+        Ok(Layout {
+            location: Point { x: origin.x + location.x, y: origin.y + location.y },
+            ..self.forest.nodes[id].layout
+        })
+    }
+
+    /// Returns the layout of this node relative to one of its ancestors, such as the root of a scrolled subtree
+    ///
+    /// The locations of the `node` and of each ancestor below the `ancestor` are added up, following the first parent
+    /// of each node. Returns [`InvalidChild::NotAnAncestor`](error::InvalidChild::NotAnAncestor) if the `ancestor`
+    /// is not reached this way. A node is located at the origin relative to itself.
+    pub fn layout_relative_to(&self, node: Node, ancestor: Node) -> Result<Layout, error::InvalidChild> {
+        let id = self.find_node(node).map_err(|e| error::InvalidChild::InvalidChildNode(e.0))?;
+        let ancestor_id = self.find_node(ancestor).map_err(|e| error::InvalidChild::InvalidParentNode(e.0))?;
+        match self.forest.location_relative_to(id, ancestor_id) {
+            Some(location) => Ok(Layout { location, ..self.forest.nodes[id].layout }),
+            None => Err(error::InvalidChild::NotAnAncestor { node, ancestor }),
+        }
+    }
+
+    /// Returns the smallest rect containing every one of the `nodes`, relative to the root of their tree
+    ///
+    /// The `start` and `end` of the rect are the left and right x coordinates, and its `top` and `bottom` are
+    /// the y coordinates, as computed by the last call to [`Taffy::compute_layout`]. Each node is validated
+    /// before anything is computed. An empty slice produces a rect of zero size at the origin.
+    pub fn bounds_of(&self, nodes: &[Node]) -> Result<Rect<f32>, error::InvalidNode> {
+        let ids = nodes.iter().map(|node| self.find_node(*node)).collect::<Result<Vec<_>, _>>()?;
+        let bounds = ids.into_iter().fold(None, |bounds: Option<Rect<f32>>, id| {
+            let location = self.forest.absolute_location(id);
+            let size = self.forest.nodes[id].layout.size;
+            let rect = Rect {
+                start: location.x,
+                end: location.x + size.width,
+                top: location.y,
+                bottom: location.y + size.height,
+            };
+            Some(match bounds {
+                Some(bounds) => Rect {
+                    start: bounds.start.min(rect.start),
+                    end: bounds.end.max(rect.end),
+                    top: bounds.top.min(rect.top),
+                    bottom: bounds.bottom.max(rect.bottom),
+                },
+                None => rect,
+            })
+        });
+        Ok(bounds.unwrap_or(Rect { start: 0.0, end: 0.0, top: 0.0, bottom: 0.0 }))
+    }
+
+    /// Returns the rect of the `node` expanded by its margins, relative to the root of its tree
+    ///
+    /// The rect is laid out like that of [`Taffy::bounds_of`], and the margins are those resolved by the last call
+    /// to [`Taffy::compute_layout`], so auto margins cover the space they took. Negative margins shrink the rect.
+    pub fn margin_box(&self, node: Node) -> Result<Rect<f32>, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        let location = self.forest.absolute_location(id);
+        let size = self.forest.nodes[id].layout.size;
+        let margin = self.forest.nodes[id].margin;
+        Ok(Rect {
+            start: location.x - margin.start,
+            end: location.x + size.width + margin.end,
+            top: location.y - margin.top,
+            bottom: location.y + size.height + margin.bottom,
+        })
+    }
+
+    /// Returns how far the alignment properties of its parent moved the `node` during the last layout
+    ///
+    /// This is the offset of the node from where it would have been placed if `justify_content`, `align_items`,
+    /// `align_self` and `align_content` all aligned to the start, and is already included in its [`Layout`].
+    /// Margins, including auto margins, and relative positions are not included.
+    /// Absolutely positioned nodes are only moved by alignment along the axes on which they have no insets.
+    pub fn alignment_offset(&self, node: Node) -> Result<Point<f32>, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.nodes[id].alignment_offset)
+    }
+
+    /// Returns the main-axis space that was left over after placing the children of the `node` during the last layout
+    ///
+    /// This is negative if the children overflow the node. With several flex lines, the line with the least space
+    /// left over is used. The space is measured before any auto margins or `justify_content` distribute it,
+    /// and is zero for nodes without children.
+    pub fn free_space(&self, node: Node) -> Result<f32, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.nodes[id].free_space)
+    }
+
+    /// Returns how much flexing grew the `node` along the main axis of its parent during the last layout
+    ///
+    /// This is the difference between the final main size of the node and its flex basis, which is negative if
+    /// the node was shrunk. Clamping by `min_size` and `max_size` is included. Roots, absolutely positioned nodes
+    /// and hidden nodes are not flexed, so this is zero for them.
+    pub fn growth_delta(&self, node: Node) -> Result<f32, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.nodes[id].growth_delta)
+    }
+
+    /// Returns the size that the [`MeasureFunc`] of the `node` most recently reported during layout
+    ///
+    /// This can differ from the final size in its [`Layout`], for example when the node is flexed or stretched.
+    /// Returns [`None`] if the `node` has no measure function, or if it has not been measured yet.
+    /// A measure function is not called when both dimensions of the node are already known.
+    pub fn measured_size(&self, node: Node) -> Result<Option<Size<f32>>, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        let data = &self.forest.nodes[id];
+        Ok(data.measure.as_ref().and(data.measured_size))
+    }
+
+    /// Returns the fraction of the main-axis content size of the `parent` that each of its children occupies, in order
+    ///
+    /// Each fraction is the main size of the child in its [`Layout`] divided by the main size of the content box of
+    /// the `parent`, as of the last layout. Margins and gaps are not included, so the fractions can add up to less than one.
+    /// If the content box of the `parent` has no main size, every fraction is zero.
+    pub fn child_fractions(&self, parent: Node) -> Result<Vec<f32>, error::InvalidNode> {
+        let id = self.find_node(parent)?;
+        let data = &self.forest.nodes[id];
+        let dir = data.style.writing_mode.physical_direction(data.style.flex_direction);
+        let available = data.inner_size.main(dir);
+        Ok(self.forest.children[id]
+            .iter()
+            .map(
+                |child| {
+                    if available > 0.0 {
+                        self.forest.nodes[*child].layout.size.main(dir) / available
+                    } else {
+                        0.0
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Returns the position and length of the content of the `node` along its main axis, as of the last layout
+    ///
+    /// The content is the span covered by the children of the `node` that are displayed, and its position is
+    /// relative to the `node` itself. The main axis is mapped to the width or the height through the
+    /// `flex_direction` and `writing_mode` of the `node`, so scrolling code does not need to. Without any displayed
+    /// children, the content has no length and is positioned at zero.
+    pub fn main_axis_layout(&self, node: Node) -> Result<(f32, f32), error::InvalidNode> {
+        let id = self.find_node(node)?;
+        let style = &self.forest.nodes[id].style;
+        let axis = style.writing_mode.physical_direction(style.flex_direction).main_axis();
+
+        let span = self.forest.children[id]
+            .iter()
+            .filter(|child| self.forest.nodes[**child].style.display != Display::None)
+            .map(|child| {
+                let layout = &self.forest.nodes[*child].layout;
+                let start = match axis {
+                    AbsoluteAxis::Horizontal => layout.location.x,
+                    AbsoluteAxis::Vertical => layout.location.y,
+                };
+                (start, start + layout.size.get(axis))
+            })
+            .reduce(|(start, end), (child_start, child_end)| (start.min(child_start), end.max(child_end)));
+        Ok(span.map_or((0.0, 0.0), |(start, end)| (start, end - start)))
+    }
+
+    /// Returns whether the content of the `node` extends beyond its final [`Layout`], along each axis
+    ///
+    /// For a node with a [`MeasureFunc`], the content is the size most recently reported by the measure function,
+    /// as returned by [`Taffy::measured_size`]. For any other node, the content is its children,
+    /// which overflow if any of them extends past an edge of the `node`.
+    pub fn overflows(&self, node: Node) -> Result<Size<bool>, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.overflows(id))
+    }
+
+    /// Returns `false` if the [`Display`](crate::style::Display) of the `node` or of any of its ancestors is `None`
+    ///
+    /// Nodes that are not laid out are given a zero-sized layout, so they can be skipped when rendering.
+    pub fn is_laid_out(&self, node: Node) -> Result<bool, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.is_laid_out(id))
+    }
+
     /// Marks the layout computation of this node and its children as outdated
+    ///
+    /// This also forgets the measurement kept for the node by [`CacheMode::OncePermanent`], if any.
     pub fn mark_dirty(&mut self, node: Node) -> Result<(), error::InvalidNode> {
         let id = self.find_node(node)?;
+        self.forest.nodes[id].permanent_measure = None;
+        self.forest.mark_dirty(id);
+        Ok(())
+    }
+
+    /// Sets how the results of the [`MeasureFunc`] of the `node` are reused, which is [`CacheMode::Always`] by default
+    ///
+    /// The node is marked as dirty, and any result kept by [`CacheMode::OncePermanent`] is forgotten.
+    pub fn set_measure_cache_mode(&mut self, node: Node, mode: CacheMode) -> Result<(), error::InvalidNode> {
+        let id = self.find_node(node)?;
+        self.forest.nodes[id].measure_cache_mode = mode;
+        self.forest.nodes[id].permanent_measure = None;
+        self.forest.mark_dirty(id);
+        Ok(())
+    }
+
+    /// Sets the size that the [`MeasureFunc`] of the `node` is expected to return when no dimension is known
+    ///
+    /// Whenever the node would be measured without any known width or height, such as while finding its hypothetical
+    /// size, the `hint` is used instead, and the measure function is only called once a dimension is known.
+    /// The hint only saves work: it must be the size that the measure function would return for those constraints,
+    /// as a layout computed from a wrong hint can differ from the correct one. Nodes without a measure function
+    /// ignore their hint. The node is marked as dirty.
+    pub fn set_size_hint(&mut self, node: Node, hint: Size<f32>) -> Result<(), error::InvalidNode> {
+        let id = self.find_node(node)?;
+        self.forest.nodes[id].size_hint = Some(hint);
         self.forest.mark_dirty(id);
         Ok(())
     }
 
+    /// Sets the intrinsic aspect ratio of the content measured by the [`MeasureFunc`] of the `node`, as width divided by height
+    ///
+    /// This is how a [`MeasureFunc::Raw`], [`MeasureFunc::Boxed`] or [`MeasureFunc::Shared`] reports a natural ratio,
+    /// such as that of an image. A ratio set here takes precedence over the one implied by a [`MeasureFunc::Fixed`] size,
+    /// while [`FlexboxLayout::aspect_ratio`] takes precedence over both. `None` removes the ratio.
+    /// The ratio is kept when the measure function is replaced, but is ignored while the node has none.
+    /// The node is marked as dirty.
+    ///
+    /// Returns [`StyleError::NonFinite`](error::StyleError::NonFinite) if the `ratio` is NaN or infinite.
+    pub fn set_intrinsic_aspect_ratio(&mut self, node: Node, ratio: Option<f32>) -> Result<(), error::StyleError> {
+        let id = self.find_node(node)?;
+        if matches!(ratio, Some(ratio) if !ratio.is_finite()) {
+            return Err(error::StyleError::NonFinite { field: "aspect_ratio" });
+        }
+        self.forest.nodes[id].intrinsic_aspect_ratio = ratio;
+        self.forest.mark_dirty(id);
+        Ok(())
+    }
+
+    /// Removes the size hint of the `node` set with [`Taffy::set_size_hint`], and marks the node as dirty
+    pub fn clear_size_hint(&mut self, node: Node) -> Result<(), error::InvalidNode> {
+        let id = self.find_node(node)?;
+        self.forest.nodes[id].size_hint = None;
+        self.forest.mark_dirty(id);
+        Ok(())
+    }
+
+    /// Forces the [`MeasureFunc`] of the `node` to be called again, for when the data behind it has changed
+    ///
+    /// Every size cached for the node is keyed by a version, which this increments, so the cached sizes are no
+    /// longer used without replacing the measure function through [`Taffy::set_measure`]. The ancestors of the
+    /// node are marked as dirty, and any result kept by [`CacheMode::OncePermanent`] is forgotten.
+    pub fn bump_measure_version(&mut self, node: Node) -> Result<(), error::InvalidNode> {
+        let id = self.find_node(node)?;
+        self.forest.bump_measure_version(id);
+        Ok(())
+    }
+
+    /// Sets the number of physical pixels per logical pixel, which is 1.0 by default
+    ///
+    /// Styles and layouts remain in logical pixels, but each layout is rounded to the nearest multiple of `1 / scale`
+    /// rather than to the nearest whole number, so that every edge lands on a physical pixel at this scale.
+    /// Changing the scale factor marks every node as dirty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not a positive, finite number.
+    pub fn set_scale_factor(&mut self, scale: f32) {
+        assert!(scale.is_finite() && scale > 0.0, "the scale factor must be positive and finite, but was {}", scale);
+        if scale != self.forest.scale_factor {
+            self.forest.scale_factor = scale;
+            self.forest.mark_all_dirty();
+        }
+    }
+
+    /// Sets the [`LayoutObserver`] that is notified of each node visited while computing layouts, replacing any previous one
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn set_observer(&mut self, observer: Box<dyn LayoutObserver>) {
+        self.forest.observer = Some(Observer { inner: observer, nodes: Vec::new() });
+    }
+
+    /// Removes the current [`LayoutObserver`] and returns it, if there is one
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn take_observer(&mut self) -> Option<Box<dyn LayoutObserver>> {
+        self.forest.observer.take().map(|observer| observer.inner)
+    }
+
+    /// Tells the [`LayoutObserver`] which [`Node`] each [`NodeId`] currently belongs to, before computing layouts
+    fn sync_observer(&mut self) {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if let Some(observer) = &mut self.forest.observer {
+            observer.nodes = (0..self.forest.nodes.len()).map(|id| self.ids_to_nodes[&id]).collect();
+        }
+    }
+
+    /// Sets the [`MeasureFunc`] used to size leaves that do not have a [`MeasureFunc`] of their own, or removes it with `None`
+    ///
+    /// Without a fallback, which is the default, such leaves are only as large as their style makes them.
+    /// This marks every node as dirty, as the size of any leaf may change.
+    pub fn set_measure_fallback(&mut self, measure: Option<MeasureFunc>) {
+        self.forest.measure_fallback = measure;
+        self.forest.mark_all_dirty();
+    }
+
+    /// Enables or disables measure snapping, which is disabled by default
+    ///
+    /// While enabled, each size returned by a [`MeasureFunc`] is rounded up to the next whole physical pixel,
+    /// as set by [`Taffy::set_scale_factor`], before it is used for sizing. Fractional text widths then cannot
+    /// overflow a line by a fraction of a pixel once the layout is rounded, which would otherwise wrap the text.
+    /// Changing this marks every node as dirty.
+    pub fn set_measure_snapping(&mut self, enabled: bool) {
+        if enabled != self.forest.measure_snapping {
+            self.forest.measure_snapping = enabled;
+            self.forest.mark_all_dirty();
+        }
+    }
+
+    /// Enables or disables the content cache, which is disabled by default
+    ///
+    /// While enabled, [`Taffy::set_style`], [`Taffy::apply_styles`] and [`Taffy::set_children`] compare the new
+    /// style or children of a node with its current ones, and leave the node clean if nothing has changed.
+    /// This guards against frameworks that set every property again on each frame from dirtying the whole tree.
+    /// Measure functions cannot be compared, so [`Taffy::set_measure`] and [`Taffy::mark_dirty`] always dirty the node.
+    pub fn set_content_cache(&mut self, enabled: bool) {
+        self.forest.content_cache = enabled;
+    }
+
+    /// Enables or disables size-aware dirtying, which is disabled by default
+    ///
+    /// While enabled, changes to a node that can only affect its contents (such as its children, its [`MeasureFunc`]
+    /// or the properties controlling how its children are laid out) no longer mark its ancestors as dirty.
+    /// Instead, the next call to [`Taffy::compute_layout`] first lays out the node again on its own using the same
+    /// constraints as before, and only lays out its ancestors again if its size has changed.
+    /// Until then, [`Taffy::dirty`] only reports the changed node itself as dirty.
+    ///
+    /// This can greatly reduce the cost of relayout for localized changes in large trees.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn set_size_aware_dirtying(&mut self, enabled: bool) {
+        if !enabled {
+            self.forest.flush_pending();
+        }
+        self.forest.size_aware_dirtying = enabled;
+    }
+
+    /// Enables or disables eager layout, which is disabled by default
+    ///
+    /// While enabled, [`Taffy::set_style`], [`Taffy::set_gap`] and [`Taffy::apply_styles`] lay out the tree containing
+    /// each changed node again straight away, in the available space that its root was last laid out with.
+    /// This keeps every layout up to date as styles arrive, without calling [`Taffy::compute_layout`].
+    /// Trees that have never been laid out are left dirty, as are trees that contain a cycle or exceed a limit,
+    /// so that the next [`Taffy::compute_layout`] can report the error.
+    ///
+    /// Each style change then costs a layout pass. Cached layouts are reused, so only the changed node and the nodes
+    /// that depend on it are laid out again; together with [`Taffy::set_size_aware_dirtying`], the ancestors are only
+    /// laid out again if the size of the changed node changes. Even so, setting many styles one by one is much slower
+    /// than setting them together with [`Taffy::apply_styles`], which lays out each tree once.
+    pub fn set_eager_layout(&mut self, enabled: bool) {
+        self.eager_layout = enabled;
+    }
+
+    /// Lays out the trees containing the `nodes` again in their last available space, if eager layout is enabled
+    fn lay_out_eagerly(&mut self, nodes: &[NodeId]) {
+        if !self.eager_layout {
+            return;
+        }
+        let mut roots: Vec<NodeId> = Vec::new();
+        for node in nodes {
+            let root = self.forest.tree_root(*node);
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        self.sync_observer();
+        for root in roots {
+            let size = match self.forest.nodes[root].last_available_space {
+                Some(size) => size,
+                None => continue,
+            };
+            if self.find_layout_root(self.ids_to_nodes[&root]).is_ok() && self.forest.needs_layout(root, size) {
+                self.forest.compute_layout(root, size);
+            }
+        }
+    }
+
+    /// Saves the dirty flag of every node, along with its caches and its last layout, to be put back by [`Taffy::restore_dirty`]
+    ///
+    /// This allows speculative changes: take a snapshot, change some styles and compute the layout, then,
+    /// if the result is rejected, set the previous styles back and restore the snapshot. The tree is then exactly as
+    /// it was before the experiment, so the next layout does not have to recompute anything.
+    pub fn snapshot_dirty(&self) -> DirtySnapshot {
+        DirtySnapshot {
+            nodes: self
+                .forest
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(id, data)| (self.ids_to_nodes[&id], data.dirty_state()))
+                .collect(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            pending: self.forest.pending.iter().map(|id| self.ids_to_nodes[id]).collect(),
+        }
+    }
+
+    /// Puts back the dirty flags, caches and layouts saved by [`Taffy::snapshot_dirty`]
+    ///
+    /// Nodes that have been removed since the snapshot are skipped, and nodes created since are left as they are.
+    /// Styles are not part of the snapshot, so they must be restored first: restoring a snapshot while the styles, measure
+    /// functions or children differ from when it was taken leaves clean nodes whose layouts do not match the tree.
+    pub fn restore_dirty(&mut self, snapshot: DirtySnapshot) {
+        for (node, state) in snapshot.nodes {
+            if let Some(id) = self.nodes_to_ids.get(&node) {
+                self.forest.nodes[*id].restore_dirty_state(state, &mut self.forest.dirty_count);
+            }
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            self.forest.pending =
+                snapshot.pending.iter().filter_map(|node| self.nodes_to_ids.get(node).copied()).collect();
+        }
+    }
+
     /// Indicates whether the layout of this node (and its children) need to be recomputed
     pub fn dirty(&self, node: Node) -> Result<bool, error::InvalidNode> {
         let id = self.find_node(node)?;
         Ok(self.forest.nodes[id].is_dirty)
     }
 
+    /// Indicates whether the layout of any node in the forest needs to be recomputed
+    ///
+    /// This reads a count of dirty nodes that is kept up to date as nodes are dirtied and laid out,
+    /// so it does not visit the nodes themselves. Every node in the forest counts, including nodes that are not
+    /// part of any tree that is laid out, such as a pool of spare nodes: a new node is dirty until a layout reaches it,
+    /// so such nodes should be laid out once, or removed, for this to become `false`.
+    pub fn any_dirty(&self) -> bool {
+        #[cfg(feature = "debug-checks")]
+        debug_assert_eq!(
+            self.forest.dirty_count,
+            self.forest.nodes.iter().filter(|data| data.is_dirty).count(),
+            "the count of dirty nodes is out of step with their dirty flags"
+        );
+        self.forest.dirty_count > 0
+    }
+
+    /// Returns every node whose list of children has changed since the last call, and clears their changes
+    ///
+    /// This tracks structure separately from the dirty flags: adding, removing, replacing or reordering the children
+    /// of a node marks it, as does removing one of its children from the forest, while style changes and layout do not.
+    /// Nodes are returned in no particular order, and nodes that have been removed since are left out.
+    pub fn structurally_changed_nodes(&self) -> Vec<Node> {
+        self.forest.take_structurally_changed().into_iter().map(|id| self.ids_to_nodes[&id]).collect()
+    }
+
+    /// Returns the deepest nesting of node visits reached by the last call to [`Taffy::compute_layout`]
+    ///
+    /// A lone root has a depth of 1, and each level of the tree that had to be laid out again adds one more.
+    /// Subtrees whose cached layouts were reused are not descended into, so a deep tree with a small change
+    /// reports a depth close to the level of the change. Returns 0 if no layout has been computed yet.
+    /// This applies to every method that computes the layout of a whole tree, such as [`Taffy::compute_layout_root`].
+    pub fn last_layout_depth(&self) -> usize {
+        self.forest.max_layout_depth
+    }
+
+    /// Returns how many times measure functions were called by the last call to [`Taffy::compute_layout`]
+    ///
+    /// Sizes that were reused from a cache, kept by [`CacheMode::OncePermanent`] or taken from a size hint
+    /// are not counted, so this measures how well measurements are cached. Returns 0 if no layout has been computed yet.
+    /// As with [`Taffy::last_layout_depth`], this applies to every method that computes the layout of a whole tree.
+    pub fn last_measure_count(&self) -> usize {
+        self.forest.measure_count
+    }
+
+    /// Returns the available space that the `node` was last laid out with as a root, if it ever was
+    ///
+    /// Negative dimensions are reported as zero, as they were clamped when the layout was computed.
+    pub fn last_available_space(&self, node: Node) -> Result<Option<Size<Option<f32>>>, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.nodes[id].last_available_space)
+    }
+
+    /// Limits the number of levels that a tree can have and still be laid out, which is unlimited by default
+    ///
+    /// A lone node has a depth of 1. Laying out a deeper tree returns [`LayoutError::LimitExceeded`](error::LayoutError::LimitExceeded)
+    /// without laying out any of it, which guards against untrusted trees that are deep enough to exhaust the stack.
+    /// The limit applies to every method that returns a [`LayoutError`](error::LayoutError).
+    pub fn set_max_depth(&mut self, depth: usize) {
+        self.max_depth = depth;
+    }
+
+    /// Limits the number of nodes that a tree can contain and still be laid out, which is unlimited by default
+    ///
+    /// A node with several parents is counted once for each of them, as it is laid out once for each.
+    /// Laying out a larger tree returns [`LayoutError::LimitExceeded`](error::LayoutError::LimitExceeded)
+    /// without laying out any of it. The limit applies to every method that returns a [`LayoutError`](error::LayoutError).
+    pub fn set_max_nodes_per_pass(&mut self, nodes: usize) {
+        self.max_nodes_per_pass = nodes;
+    }
+
+    /// Finds the `node`, checking that its tree has no cycles and that laying it out stays within the limits set on this instance
+    fn find_layout_root(&self, node: Node) -> Result<NodeId, error::LayoutError> {
+        let id = self.find_node(node)?;
+        if let Some(cycle) = self.forest.find_cycle(id) {
+            return Err(error::LayoutError::CycleDetected { node: self.ids_to_nodes[&cycle] });
+        }
+        match self.forest.exceeded_limit(id, self.max_depth, self.max_nodes_per_pass) {
+            Some(limit) => Err(error::LayoutError::LimitExceeded { limit }),
+            None => Ok(id),
+        }
+    }
+
+    /// Returns a node below `node` (possibly `node` itself) that is one of its own ancestors, if there is one
+    ///
+    /// This lets conversions of whole trees reject a cycle before walking it.
+    #[cfg(any(feature = "binary", all(feature = "serde", feature = "std")))]
+    pub(crate) fn find_cycle(&self, node: Node) -> Result<Option<Node>, error::InvalidNode> {
+        let id = self.find_node(node)?;
+        Ok(self.forest.find_cycle(id).map(|cycle| self.ids_to_nodes[&cycle]))
+    }
+
     /// Updates the stored layout of the provided `node` and its children
-    pub fn compute_layout(&mut self, node: Node, size: Size<Option<f32>>) -> Result<(), error::InvalidNode> {
+    ///
+    /// A negative available `size`, such as one produced by a window resize animation, is treated as zero.
+    pub fn compute_layout(&mut self, node: Node, size: Size<Option<f32>>) -> Result<(), error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        self.sync_observer();
+        self.forest.compute_layout(id, size);
+        Ok(())
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, resolving the percentages of its size against
+    /// `percentage_base` rather than the available `size`
+    ///
+    /// This applies to the `size`, `min_size` and `max_size` of the `node` itself, so that they can be relative to
+    /// a known page size even when the available space is indefinite on some axis. Everything else is laid out
+    /// as with [`Taffy::compute_layout`], within the available `size`. Negative bases are treated as zero.
+    pub fn compute_layout_with_percentage_base(
+        &mut self,
+        node: Node,
+        size: Size<Option<f32>>,
+        percentage_base: Size<f32>,
+    ) -> Result<(), error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        self.sync_observer();
+        self.forest.compute_layout_with_percentage_base(id, size, percentage_base.map(Some));
+        Ok(())
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, spreading the work over several calls if need be
+    ///
+    /// With size-aware dirtying enabled, each queued node is laid out again by itself, and these are laid out while
+    /// the nodes in their subtrees fit in `max_nodes`. The tree is then laid out as with [`Taffy::compute_layout`]
+    /// if the nodes that it has to lay out again fit in what is left. Otherwise, the work stops there, and
+    /// [`LayoutStatus::Partial`] is returned so that it can be finished by [`Taffy::resume_layout`], typically on
+    /// the next frame. Stored layouts can be read in between, but may be out of date until the layout is complete.
+    ///
+    /// At least one step is taken by each call, even if it does not fit, so that the layout is always finished eventually.
+    /// The final layout of the tree cannot be split, so without size-aware dirtying the whole layout is done at once.
+    pub fn compute_layout_budgeted(
+        &mut self,
+        node: Node,
+        size: Size<Option<f32>>,
+        max_nodes: usize,
+    ) -> Result<LayoutStatus, error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        self.sync_observer();
+        let partial = LayoutStatus::Partial { resume_token: ResumeToken { node, size } };
+
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        let (budget, laid_out) = {
+            let mut budget = max_nodes;
+            let laid_out = self.forest.layout_pending_within(&mut budget);
+            if !self.forest.pending.is_empty() {
+                return Ok(partial);
+            }
+            (budget, laid_out)
+        };
+        #[cfg(not(any(feature = "std", feature = "alloc")))]
+        let (budget, laid_out) = (max_nodes, 0);
+
+        if laid_out > 0 && self.forest.dirty_len(id) > budget {
+            return Ok(partial);
+        }
+        self.forest.compute_layout(id, size);
+        Ok(LayoutStatus::Complete)
+    }
+
+    /// Continues the layout that a call to [`Taffy::compute_layout_budgeted`] could not finish, within a new budget
+    ///
+    /// This is the same as calling [`Taffy::compute_layout_budgeted`] again with the same node and available space,
+    /// and the layout may take several more calls to finish.
+    pub fn resume_layout(&mut self, token: ResumeToken, max_nodes: usize) -> Result<LayoutStatus, error::LayoutError> {
+        self.compute_layout_budgeted(token.node, token.size, max_nodes)
+    }
+
+    /// Applies the style of the widest breakpoint that fits the available width to `node`, then updates its layout
+    ///
+    /// Each breakpoint pairs a minimum available width with the style that applies from that width on. The style of
+    /// the largest breakpoint that is at most the available width is set with [`Taffy::set_style`], and the layout is
+    /// then computed as with [`Taffy::compute_layout`]. An indefinite width fits every breakpoint. If no breakpoint
+    /// fits, the style of the `node` is left unchanged. Returns the index of the applied breakpoint, if any.
+    ///
+    /// Setting the style that is already applied does not dirty the `node`, so this can be called every frame.
+    /// Returns [`LayoutError::NonFinite`](error::LayoutError::NonFinite) if any numeric field of the applied style
+    /// is NaN or infinite, in which case nothing is changed or laid out.
+    pub fn compute_layout_responsive(
+        &mut self,
+        node: Node,
+        size: Size<Option<f32>>,
+        breakpoints: &[(f32, FlexboxLayout)],
+    ) -> Result<Option<usize>, error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        let width = size.width.unwrap_or(f32::INFINITY);
+        let mut applied: Option<usize> = None;
+        for (index, (breakpoint, _)) in breakpoints.iter().enumerate() {
+            if *breakpoint <= width && applied.is_none_or(|applied| *breakpoint >= breakpoints[applied].0) {
+                applied = Some(index);
+            }
+        }
+
+        if let Some(index) = applied {
+            let style = breakpoints[index].1;
+            Self::validate_style(&style)?;
+            if self.forest.nodes[id].style != style {
+                self.forest.set_style(id, style);
+            }
+        }
+        self.sync_observer();
+        self.forest.compute_layout(id, size);
+        Ok(applied)
+    }
+
+    /// Returns the layout of the provided `node`, first updating it with [`Taffy::compute_layout`] if it is out of date
+    ///
+    /// The layout is out of date if the `node` or any of its descendants is dirty, or if the `node` was not
+    /// last laid out as a root in the same available `size`. Otherwise, the stored layout is returned without any work.
+    pub fn layout_computed(&mut self, node: Node, size: Size<Option<f32>>) -> Result<&Layout, error::LayoutError> {
         let id = self.find_node(node)?;
+        if self.forest.needs_layout(id, size) {
+            self.compute_layout(node, size)?;
+        }
+        Ok(&self.forest.nodes[id].layout)
+    }
+
+    /// Returns the layout that the `node` would have if its style were `style`, without changing its style
+    ///
+    /// The `node` is laid out as a root within the `available` space, as with [`Taffy::compute_layout`].
+    /// Afterwards, the stored layouts, caches and dirty flags of every node are as they were before the call.
+    ///
+    /// Returns [`LayoutError::NonFinite`](error::LayoutError::NonFinite) if any numeric field of the `style` is NaN or infinite,
+    /// and fails like [`Taffy::compute_layout`] if the tree below the `node` cannot be laid out.
+    pub fn compute_hypothetical(
+        &mut self,
+        node: Node,
+        style: &FlexboxLayout,
+        available: Size<Option<f32>>,
+    ) -> Result<Layout, error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        Self::validate_style(style)?;
+        self.sync_observer();
+        Ok(self.forest.compute_hypothetical(id, *style, available))
+    }
+
+    /// Updates the stored layout of the provided `node` and its children as if no `min_size` or `max_size` were set
+    ///
+    /// This is a diagnostic tool: comparing its results with those of [`Taffy::compute_layout`] shows whether a size
+    /// is caused by a constraint. The styles are left unchanged, and the `node`, its descendants and its ancestors
+    /// are left dirty, so the next [`Taffy::compute_layout`] applies the constraints again.
+    pub fn compute_layout_no_constraints(
+        &mut self,
+        node: Node,
+        size: Size<Option<f32>>,
+    ) -> Result<(), error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        self.sync_observer();
+        self.forest.compute_layout_unconstrained(id, size);
+        Ok(())
+    }
+
+    /// Updates only the stored size of the provided `node`, without positioning its children
+    ///
+    /// This is a faster partial computation for checks that only need to know how large a tree would be,
+    /// such as whether it fits in the available `size`. The location of the `node` and the layouts of its
+    /// descendants are left as they were, and nodes that were dirty remain dirty until [`Taffy::compute_layout`] is called.
+    /// The sizes measured along the way are cached, so a following [`Taffy::compute_layout`] can reuse them.
+    pub fn compute_sizes(&mut self, node: Node, size: Size<Option<f32>>) -> Result<(), error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        self.sync_observer();
+        self.forest.compute_sizes(id, size);
+        Ok(())
+    }
+
+    /// Lays out the provided `node` within the `available` size, and returns whether its size fits within it on both axes
+    ///
+    /// Items shrink as far as their content allows, so this answers whether the tree would overflow the space it is given.
+    /// Only the size of the `node` is computed, as with [`Taffy::compute_sizes`].
+    pub fn fits_within(&mut self, node: Node, available: Size<f32>) -> Result<bool, error::LayoutError> {
+        self.compute_sizes(node, available.map(Some))?;
+        let size = self.forest.nodes[self.find_node(node)?].layout.size;
+        Ok(size.width <= available.width && size.height <= available.height)
+    }
+
+    /// Returns how much larger the `node` would be with unlimited space than in its current layout, on each axis
+    ///
+    /// The `node` is sized as a root with indefinite available space, so it takes its max-content size,
+    /// which is compared with the size it was given by the last call to [`Taffy::compute_layout`].
+    /// Axes on which the `node` would not grow are zero. No layout is changed, as with [`Taffy::compute_sizes`].
+    ///
+    /// Returns a [`LayoutError`](error::LayoutError) rather than an [`InvalidNode`](error::InvalidNode),
+    /// as the tree below the `node` has to be checked before it is sized, like every other layout computation.
+    pub fn content_fit_delta(&mut self, node: Node) -> Result<Size<f32>, error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        self.sync_observer();
+        let current = self.forest.nodes[id].layout.size;
+        let content = self.forest.max_content_size(id);
+        Ok(Size { width: (content.width - current.width).max(0.0), height: (content.height - current.height).max(0.0) })
+    }
+
+    /// Updates the stored layout of the designated root node and its children
+    ///
+    /// Returns `Ok(false)` without doing anything if no root has been designated with [`Taffy::set_root`],
+    /// and fails like [`Taffy::compute_layout`] if the tree below the root cannot be laid out.
+    pub fn compute_layout_root(&mut self, size: Size<Option<f32>>) -> Result<bool, error::LayoutError> {
+        match self.root {
+            Some(root) => {
+                self.compute_layout(root, size)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, then returns the nodes whose rect has changed
+    ///
+    /// A rect is the location of a node relative to the root of its tree, together with its size.
+    /// The returned nodes are those whose rect differs from the one seen by the previous call,
+    /// or that have not been seen by a previous call, paired with their layout relative to the root.
+    /// Only the tree below `node` is compared, in preorder.
+    pub fn compute_layout_damage(
+        &mut self,
+        node: Node,
+        size: Size<Option<f32>>,
+    ) -> Result<Vec<(Node, Layout)>, error::LayoutError> {
+        let id = self.find_layout_root(node)?;
+        self.sync_observer();
         self.forest.compute_layout(id, size);
+        Ok(self.forest.collect_damage(id).into_iter().map(|(id, layout)| (self.ids_to_nodes[&id], layout)).collect())
+    }
+
+    /// Updates the stored layout of the provided `root` and its children, then returns every node of the tree in preorder,
+    /// along with its layout relative to the `root`
+    ///
+    /// The `root` itself comes first, at the origin. Nodes with a [`Display`](crate::style::Display) of `None`
+    /// are skipped, along with their descendants, so only the nodes that are placed are returned.
+    /// This gives a renderer everything it needs to paint the tree in one call.
+    pub fn compute_and_flatten(
+        &mut self,
+        root: Node,
+        size: Size<Option<f32>>,
+    ) -> Result<Vec<(Node, Layout)>, error::LayoutError> {
+        let id = self.find_layout_root(root)?;
+        self.sync_observer();
+        self.forest.compute_layout(id, size);
+        Ok(self.forest.flatten(id).into_iter().map(|(id, layout)| (self.ids_to_nodes[&id], layout)).collect())
+    }
+
+    /// Returns the leaves of the tree below `root` (including `root` itself) in preorder, along with their layouts
+    /// relative to the root of the tree
+    ///
+    /// Nodes with a [`Display`](crate::style::Display) of `None` are skipped, along with their descendants,
+    /// so only the leaves that are laid out are returned. This is useful for painting content such as text,
+    /// which only leaves have. The layouts are as of the last call to [`Taffy::compute_layout`].
+    pub fn leaf_layouts(&self, root: Node) -> Result<Vec<(Node, Layout)>, error::InvalidNode> {
+        let id = self.find_node(root)?;
+        Ok(self.forest.leaf_layouts(id).into_iter().map(|(id, layout)| (self.ids_to_nodes[&id], layout)).collect())
+    }
+
+    /// Updates the stored layout of the provided `node` and its children, then passes each of their final layouts to `sink`
+    ///
+    /// Nodes are passed in preorder, so a parent is always passed before its children, and each node is passed once.
+    /// The layouts are relative to the parent of each node, exactly as returned by [`Taffy::layout`].
+    /// This lets a caller stream the results of a layout without looking up each node afterwards.
+    pub fn compute_layout_with_sink<F: FnMut(Node, Layout)>(
+        &mut self,
+        node: Node,
+        size: Size<Option<f32>>,
+        mut sink: F,
+    ) -> Result<(), error::LayoutError> {
+        let root = self.find_layout_root(node)?;
+        self.sync_observer();
+        self.forest.compute_layout(root, size);
+
+        let mut visited: Vec<bool> = self.forest.nodes.iter().map(|_| false).collect();
+        let mut stack: Vec<NodeId> = new_vec_with_capacity(self.forest.nodes.len());
+        stack.push(root);
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            sink(self.ids_to_nodes[&id], self.forest.nodes[id].layout);
+            stack.extend(self.forest.children[id].iter().rev().copied());
+        }
+        Ok(())
+    }
+
+    /// Updates the stored layout of each of the provided `roots`, using the size paired with each root
+    ///
+    /// All of the `roots` are validated before any layout is computed,
+    /// so either every root is laid out or none of them are.
+    pub fn compute_all(&mut self, roots: &[(Node, Size<Option<f32>>)]) -> Result<(), error::LayoutError> {
+        let ids = roots.iter().map(|(node, _)| self.find_layout_root(*node)).collect::<Result<Vec<_>, _>>()?;
+        self.sync_observer();
+        for (id, (_, size)) in ids.into_iter().zip(roots) {
+            self.forest.compute_layout(id, *size);
+        }
         Ok(())
     }
 }
 
+/// Direct access to the storage of a [`Taffy`], for traversals that cannot afford to look up each [`Node`]
+///
+/// These methods are only available with the `unstable-internals` feature, and may change or be removed in any release.
+/// Each [`NodeId`] is only valid until the next node is removed, so ids should be resolved again after any removal.
+/// Unlike the rest of the API, methods taking a [`NodeId`] panic if it is out of bounds.
+#[cfg(feature = "unstable-internals")]
+impl<S: BuildHasher> Taffy<S> {
+    /// Returns the [`NodeId`] of the `node`, or `None` if it is not in this [`Taffy`]
+    ///
+    /// Unlike other methods, this never panics with the `debug-checks` feature.
+    pub fn resolve(&self, node: Node) -> Option<NodeId> {
+        self.nodes_to_ids.get(&node).copied()
+    }
+
+    /// Returns the [`Node`] stored with the given `id`, or `None` if there is no such node
+    pub fn node_of(&self, id: NodeId) -> Option<Node> {
+        self.ids_to_nodes.get(&id).copied()
+    }
+
+    /// Returns the ids of the children of the node with the given `id`, in order
+    pub fn child_ids(&self, id: NodeId) -> &[NodeId] {
+        &self.forest.children[id]
+    }
+
+    /// Returns the ids of the parents of the node with the given `id`
+    pub fn parent_ids(&self, id: NodeId) -> &[NodeId] {
+        &self.forest.parents[id]
+    }
+
+    /// Returns the ids of the ancestors of the node with the given `id`, starting with its parent and ending with the root
+    ///
+    /// Where a node has several parents, the first one is followed. The iterator ends after one fewer ancestors than
+    /// there are nodes, so that a cycle cannot make it run forever.
+    pub fn ancestor_ids(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.forest.ancestors(id)
+    }
+
+    /// Returns the layout of the node with the given `id`, as of the last call to [`Taffy::compute_layout`]
+    pub fn layout_of(&self, id: NodeId) -> &Layout {
+        &self.forest.nodes[id].layout
+    }
+}
+
 /// Internal node id.
+#[cfg(not(feature = "unstable-internals"))]
 pub(crate) type NodeId = usize;
 
+/// Internal node id, which is the index of a node in the storage of its [`Taffy`]
+///
+/// This is only public with the `unstable-internals` feature, and may change in any release.
+/// A [`NodeId`] is only valid until the next node is removed, as removing a node moves another one into its place.
+#[cfg(feature = "unstable-internals")]
+pub type NodeId = usize;
+
+/// The integer type backing an [`Id`]
+///
+/// With the `small-ids` feature this is a `u32`, which halves the size of a [`Node`] on 64-bit targets,
+/// at the cost of limiting each [`Taffy`] (and the number of [`Taffy`] instances) to about 4 billion allocations.
+#[cfg(feature = "small-ids")]
+type RawId = u32;
+/// The integer type backing an [`Id`]
+#[cfg(not(feature = "small-ids"))]
+type RawId = usize;
+
 /// The identifier of a [`Node`]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(not(any(feature = "std", feature = "alloc")), derive(hash32_derive::Hash32))]
-pub(crate) struct Id(usize);
+pub(crate) struct Id(RawId);
 
 /// An bump-allocator index that tracks how many [`Nodes`](Node) have been allocated in a [`Taffy`].
 pub(crate) struct Allocator {
     /// The last reserved [`NodeId`]
-    last_id: AtomicUsize,
+    last_id: AtomicRawId,
 }
 
 impl Allocator {
     /// Creates a fresh [`Allocator`]
     #[must_use]
     pub const fn new() -> Self {
-        Self { last_id: AtomicUsize::new(0) }
+        Self { last_id: AtomicRawId::new(0) }
     }
 
     /// Allocates space for one more [`Node`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if every [`Id`] has been allocated, rather than wrapping around and reusing them.
     pub fn allocate(&self) -> Id {
-        Id(self.last_id.fetch_add(1, Ordering::Relaxed))
+        let id = self.last_id.fetch_add(1, Ordering::Relaxed);
+        assert!(id != RawId::MAX, "ran out of node ids");
+        Id(id)
     }
 }
 
@@ -332,6 +1751,50 @@ impl Allocator {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "small-ids")]
+    #[test]
+    fn small_ids_halve_node_size() {
+        assert_eq!(core::mem::size_of::<Node>(), 2 * core::mem::size_of::<u32>());
+    }
+
+    #[cfg(feature = "unstable-internals")]
+    #[test]
+    fn node_ids_follow_the_tree() {
+        let mut taffy = Taffy::new();
+        let grandchild = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[grandchild]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let ids = [root, child, grandchild].map(|node| taffy.resolve(node).unwrap());
+        assert_eq!(ids.map(|id| taffy.node_of(id).unwrap()), [root, child, grandchild]);
+        assert_eq!(taffy.child_ids(ids[0]), [ids[1]]);
+        assert_eq!(taffy.parent_ids(ids[2]), [ids[1]]);
+        assert!(taffy.parent_ids(ids[0]).is_empty());
+        assert_eq!(taffy.ancestor_ids(ids[2]).collect::<Vec<_>>().as_slice(), &[ids[1], ids[0]]);
+        assert!(core::ptr::eq(taffy.layout_of(ids[1]), taffy.layout(child).unwrap()));
+        assert_eq!(taffy.node_of(3), None);
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "has been removed or cleared")]
+    fn debug_checks_report_removed_nodes() {
+        let mut taffy = Taffy::new();
+        let node = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.remove(node).unwrap();
+        let _ = taffy.layout(node);
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "belongs to a different Taffy instance")]
+    fn debug_checks_report_nodes_of_other_instances() {
+        let mut other = Taffy::new();
+        let node = other.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let _ = Taffy::new().layout(node);
+    }
+
     #[test]
     fn measure_func_is_send_and_sync() {
         fn is_send_and_sync<T: Send + Sync>() {}
@@ -0,0 +1,150 @@
+//! Parallel layout of independent subtrees, enabled by the `parallel` feature
+//!
+//! Flexbox sizing has dependencies between siblings, so only the final layout of each flex item is parallelized:
+//! by that point the size of every item is known, and each subtree can be laid out on its own.
+use core::mem;
+
+use rayon::prelude::*;
+
+use crate::forest::{Forest, NodeData};
+use crate::geometry::Size;
+use crate::node::NodeId;
+use crate::style::FlexboxLayout;
+use crate::sys::{new_vec_with_capacity, Vec};
+
+/// The root of a subtree, followed by the node size and parent size to lay it out with
+pub(crate) type Subtree = (NodeId, Size<Option<f32>>, Size<Option<f32>>);
+
+impl Forest {
+    /// Lays out each of the `subtrees` on the rayon thread pool, storing the results in each node's cache
+    ///
+    /// If any node is reachable from more than one subtree (or more than once from the same subtree),
+    /// the subtrees are not independent and nothing is done: the regular sequential layout handles them instead.
+    pub(crate) fn layout_subtrees_in_parallel(&mut self, subtrees: &[Subtree]) {
+        // The fallback measure function cannot be shared with the forests of the subtrees
+        if !self.parallel || subtrees.len() < 2 || self.measure_fallback.is_some() {
+            return;
+        }
+
+        // The position of each claimed node within the subtree that claimed it
+        let mut local_ids: Vec<Option<NodeId>> = vec![None; self.nodes.len()];
+        let mut members: Vec<Vec<NodeId>> = new_vec_with_capacity(subtrees.len());
+
+        for (root, _, _) in subtrees {
+            let mut ids = Vec::new();
+            let mut stack = vec![*root];
+            while let Some(id) = stack.pop() {
+                if local_ids[id].is_some() {
+                    return;
+                }
+                local_ids[id] = Some(ids.len());
+                ids.push(id);
+                stack.extend(self.children[id].iter());
+            }
+            members.push(ids);
+        }
+
+        // Move each subtree into its own forest, so that they can be mutated concurrently
+        let mut forests: Vec<Forest> = members
+            .iter()
+            .map(|ids| {
+                let mut forest = Forest::with_capacity(ids.len());
+                forest.parallel = false;
+                forest.size_aware_dirtying = self.size_aware_dirtying;
+                forest.measure_snapping = self.measure_snapping;
+                forest.scale_factor = self.scale_factor;
+                for id in ids {
+                    if self.nodes[*id].is_dirty {
+                        self.dirty_count -= 1;
+                        forest.dirty_count += 1;
+                    }
+                    forest.nodes.push(mem::replace(&mut self.nodes[*id], NodeData::new(FlexboxLayout::default())));
+                    forest.children.push(self.children[*id].iter().map(|child| local_ids[*child].unwrap()).collect());
+                    forest.parents.push(new_vec_with_capacity(0));
+                }
+                forest
+            })
+            .collect();
+
+        forests.par_iter_mut().zip(subtrees.par_iter()).for_each(|(forest, (_, node_size, parent_size))| {
+            forest.compute_preliminary(0, *node_size, *parent_size, true, false);
+        });
+
+        for (forest, ids) in forests.iter_mut().zip(&members) {
+            for (id, data) in ids.iter().zip(forest.nodes.drain(..)) {
+                self.nodes[*id] = data;
+            }
+            self.max_layout_depth = self.max_layout_depth.max(self.layout_depth + forest.max_layout_depth);
+            self.measure_count += forest.measure_count;
+            self.dirty_count += forest.dirty_count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::{Point, Size};
+    use crate::node::Taffy;
+    use crate::style::{Dimension, FlexboxLayout};
+
+    #[test]
+    fn independent_subtrees_are_laid_out() {
+        let mut taffy = Taffy::new();
+        let leaf_style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        let columns: Vec<_> = (0..8)
+            .map(|_| {
+                let leaves: Vec<_> = (0..4).map(|_| taffy.new_with_children(leaf_style, &[]).unwrap()).collect();
+                taffy.new_with_children(FlexboxLayout { flex_grow: 1.0, ..Default::default() }, &leaves).unwrap()
+            })
+            .collect();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(800.0), height: Dimension::Points(600.0) },
+                    ..Default::default()
+                },
+                &columns,
+            )
+            .unwrap();
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        for (i, column) in columns.iter().enumerate() {
+            let layout = taffy.layout(*column).unwrap();
+            assert_eq!(layout.size, Size { width: 100.0, height: 600.0 });
+            assert_eq!(layout.location, Point { x: 100.0 * i as f32, y: 0.0 });
+
+            for (j, leaf) in taffy.children(*column).unwrap().into_iter().enumerate() {
+                let layout = taffy.layout(leaf).unwrap();
+                assert_eq!(layout.size, Size { width: 10.0, height: 10.0 });
+                assert_eq!(layout.location, Point { x: 10.0 * j as f32, y: 0.0 });
+            }
+        }
+    }
+
+    #[test]
+    fn shared_subtrees_fall_back_to_sequential_layout() {
+        let mut taffy = Taffy::new();
+        let shared = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let first = taffy.new_with_children(FlexboxLayout::default(), &[shared]).unwrap();
+        let second = taffy.new_with_children(FlexboxLayout::default(), &[shared]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[first, second]).unwrap();
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(first).unwrap().size, Size { width: 10.0, height: 10.0 });
+        assert_eq!(taffy.layout(second).unwrap().location, Point { x: 10.0, y: 0.0 });
+        assert_eq!(taffy.layout(shared).unwrap().size, Size { width: 10.0, height: 10.0 });
+    }
+}
@@ -1,6 +1,7 @@
 //! A representation of [CSS layout properties](https://css-tricks.com/snippets/css/a-guide-to-flexbox/) in Rust, used for flexbox layout
 
-use crate::geometry::{Rect, Size};
+use crate::geometry::{AbsoluteAxis, Rect, Size};
+use crate::math::MaybeMath;
 
 /// How [`Nodes`](crate::node::Node) are aligned relative to the cross axis
 ///
@@ -20,6 +21,24 @@ pub enum AlignItems {
     Baseline,
     /// Stretch to fill the container
     Stretch,
+    /// Items are packed toward the start edge of the container on the cross axis, even with [`FlexWrap::WrapReverse`]
+    ///
+    /// Unlike [`AlignItems::FlexStart`], this does not depend on the direction of the cross axis.
+    /// Under [`Direction::Rtl`], the start edge of a column is its right edge.
+    Start,
+    /// Items are packed toward the end edge of the container on the cross axis, even with [`FlexWrap::WrapReverse`]
+    ///
+    /// Unlike [`AlignItems::FlexEnd`], this does not depend on the direction of the cross axis.
+    /// Under [`Direction::Rtl`], the end edge of a column is its left edge.
+    End,
+    /// Items are aligned such as their last baselines align, and packed toward the end of the cross axis
+    ///
+    /// The last baseline of an item is that of its last child, or its bottom edge if it has no children,
+    /// so for items with a single line of content this aligns them like [`AlignItems::Baseline`].
+    /// Measure functions cannot report a baseline, so a measured leaf, such as a paragraph of text,
+    /// is aligned by its bottom edge however many lines it has.
+    /// In a column, items are aligned like [`AlignItems::FlexEnd`] instead.
+    LastBaseline,
 }
 
 impl Default for AlignItems {
@@ -50,6 +69,8 @@ pub enum AlignSelf {
     Baseline,
     /// Distribute items evenly, but stretch them to fill the container
     Stretch,
+    /// Items are aligned such as their last baselines align, and packed toward the end of the cross axis
+    LastBaseline,
 }
 
 impl Default for AlignSelf {
@@ -161,6 +182,24 @@ impl FlexDirection {
     pub(crate) fn is_reverse(self) -> bool {
         matches!(self, Self::RowReverse | Self::ColumnReverse)
     }
+
+    #[inline]
+    /// The physical axis along which items are laid out: horizontal for rows, and vertical for columns
+    #[must_use]
+    pub fn main_axis(self) -> AbsoluteAxis {
+        if self.is_row() {
+            AbsoluteAxis::Horizontal
+        } else {
+            AbsoluteAxis::Vertical
+        }
+    }
+
+    #[inline]
+    /// The physical axis perpendicular to the [`main_axis`](FlexDirection::main_axis)
+    #[must_use]
+    pub fn cross_axis(self) -> AbsoluteAxis {
+        self.main_axis().other()
+    }
 }
 
 /// Sets the distribution of space between and around content items along the main-axis
@@ -185,6 +224,16 @@ pub enum JustifyContent {
     /// Distribute items evenly,
     /// such that the space between items is the same as the space between the first and last item and the edges
     SpaceEvenly,
+    /// Items are packed toward the start edge of the container on the main axis, even in a reversed [`FlexDirection`]
+    ///
+    /// Unlike [`JustifyContent::FlexStart`], this does not depend on the direction of the main axis.
+    /// Under [`Direction::Rtl`], the start edge of a row is its right edge.
+    Start,
+    /// Items are packed toward the end edge of the container on the main axis, even in a reversed [`FlexDirection`]
+    ///
+    /// Unlike [`JustifyContent::FlexEnd`], this does not depend on the direction of the main axis.
+    /// Under [`Direction::Rtl`], the end edge of a row is its left edge.
+    End,
 }
 
 impl Default for JustifyContent {
@@ -193,6 +242,23 @@ impl Default for JustifyContent {
     }
 }
 
+impl JustifyContent {
+    /// Converts [`JustifyContent::Start`] and [`JustifyContent::End`] into the equivalent value relative to the main axis
+    /// of a container with the flex direction `dir` and the given `direction`
+    ///
+    /// Every other value is returned unchanged.
+    pub(crate) fn resolve(self, dir: FlexDirection, direction: Direction) -> Self {
+        let reverse = dir.is_reverse() != direction.reverses_main_axis(dir);
+        match self {
+            Self::Start if reverse => Self::FlexEnd,
+            Self::Start => Self::FlexStart,
+            Self::End if reverse => Self::FlexStart,
+            Self::End => Self::FlexEnd,
+            other => other,
+        }
+    }
+}
+
 /// The positioning strategy for this item.
 ///
 /// This controls both how the origin is determined for the [`Style::position`] field,
@@ -202,6 +268,11 @@ impl Default for JustifyContent {
 /// which can be unintuitive.
 ///
 /// [`PositionType::Relative`] is the default value, in contrast to the default behavior in CSS.
+/// This is deliberate: like Yoga, and like the base stylesheet of the test fixtures, every node is positioned unless
+/// it opts out, so absolutely positioned children are placed relative to their parent by default.
+/// Defaulting to [`PositionType::Static`] would move the absolutely positioned children of every node that does not
+/// set its position type to the nearest positioned ancestor, changing existing layouts without any change to their styles.
+/// Set [`PositionType::Static`] explicitly where the CSS behavior is wanted.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PositionType {
@@ -214,6 +285,10 @@ pub enum PositionType {
     ///
     /// WARNING: to opt-out of layouting entirely, you must use [`Display::None`] instead on your [`Style`] object.
     Absolute,
+    /// The offsets are ignored, and the item is laid out by the flexbox algorithm like a relative item.
+    /// Unlike other items, it is not positioned: its absolutely positioned children are placed relative to its
+    /// closest positioned ancestor instead, or relative to the root if there is none.
+    Static,
 }
 
 impl Default for PositionType {
@@ -244,6 +319,115 @@ impl Default for FlexWrap {
     }
 }
 
+/// Controls whether the [`size`](FlexboxLayout::size), [`min_size`](FlexboxLayout::min_size)
+/// and [`max_size`](FlexboxLayout::max_size) of a node include its padding and border.
+///
+/// Defaults to [`BoxSizing::BorderBox`]
+///
+/// [Specification](https://www.w3.org/TR/css-sizing-3/#box-sizing)
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BoxSizing {
+    /// Sizes include the padding and border of the node
+    BorderBox,
+    /// Sizes only describe the content of the node, and the padding and border are added on top
+    ContentBox,
+}
+
+impl Default for BoxSizing {
+    fn default() -> Self {
+        Self::BorderBox
+    }
+}
+
+/// Controls whether the lines of content of a node run horizontally or vertically, and in which direction they stack
+///
+/// The writing mode of a container changes which physical axis its [`FlexDirection`] refers to:
+/// in a vertical writing mode, [`FlexDirection::Row`] flows from top to bottom and [`FlexDirection::Column`] runs horizontally.
+/// The `size`, `margin`, `padding`, `border` and `position` of nodes remain physical.
+/// Unlike in CSS, the writing mode is not inherited, and only affects the layout of the children of the node it is set on.
+///
+/// Defaults to [`WritingMode::HorizontalTb`]
+///
+/// [Specification](https://www.w3.org/TR/css-writing-modes-3/#block-flow)
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WritingMode {
+    /// Lines run horizontally, and stack from top to bottom
+    HorizontalTb,
+    /// Lines run vertically from top to bottom, and stack from right to left
+    VerticalRl,
+    /// Lines run vertically from top to bottom, and stack from left to right
+    VerticalLr,
+}
+
+impl Default for WritingMode {
+    fn default() -> Self {
+        Self::HorizontalTb
+    }
+}
+
+impl WritingMode {
+    /// The physical direction that the `dir` of a container with this writing mode flows in
+    pub(crate) fn physical_direction(self, dir: FlexDirection) -> FlexDirection {
+        match (self, dir) {
+            (Self::HorizontalTb, dir) => dir,
+            (_, FlexDirection::Row) => FlexDirection::Column,
+            (_, FlexDirection::RowReverse) => FlexDirection::ColumnReverse,
+            (Self::VerticalRl, FlexDirection::Column) => FlexDirection::RowReverse,
+            (Self::VerticalRl, FlexDirection::ColumnReverse) => FlexDirection::Row,
+            (Self::VerticalLr, FlexDirection::Column) => FlexDirection::Row,
+            (Self::VerticalLr, FlexDirection::ColumnReverse) => FlexDirection::RowReverse,
+        }
+    }
+
+    /// Does the cross axis of a container with this writing mode and direction `dir` run against the physical axis?
+    ///
+    /// This is the case when the lines of a row stack from right to left.
+    pub(crate) fn reverses_cross_axis(self, dir: FlexDirection) -> bool {
+        self == Self::VerticalRl && dir.is_row()
+    }
+}
+
+/// The direction that inline content, such as the items of a row, runs in
+///
+/// The direction of a container decides which edge [`JustifyContent::Start`], [`JustifyContent::End`],
+/// [`AlignItems::Start`] and [`AlignItems::End`] align its children to along the inline axis:
+/// the main axis of a row, or the cross axis of a column. Under [`Direction::Rtl`], the start of a horizontal row is its right edge.
+/// The values relative to the flex lines, such as [`JustifyContent::FlexStart`], are not affected,
+/// and a [`FlexDirection::Row`] still flows from left to right.
+/// Unlike in CSS, the direction is not inherited, and only affects the layout of the children of the node it is set on.
+///
+/// Defaults to [`Direction::Ltr`]
+///
+/// [Specification](https://www.w3.org/TR/css-writing-modes-3/#direction)
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    /// Inline content runs from left to right
+    Ltr,
+    /// Inline content runs from right to left
+    Rtl,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Self::Ltr
+    }
+}
+
+impl Direction {
+    /// Does this direction swap the start and end edges of the main axis of a container with direction `dir`?
+    pub(crate) fn reverses_main_axis(self, dir: FlexDirection) -> bool {
+        self == Self::Rtl && dir.is_row()
+    }
+
+    /// Does this direction swap the start and end edges of the cross axis of a container with direction `dir`?
+    pub(crate) fn reverses_cross_axis(self, dir: FlexDirection) -> bool {
+        self == Self::Rtl && dir.is_column()
+    }
+}
+
 /// A unit of linear measurement
 ///
 /// This is commonly combined with [`Rect`], [`Point`](crate::geometry::Point) and [`Size<T>`].
@@ -261,6 +445,11 @@ pub enum Dimension {
     Points(f32),
     /// The dimension is stored in percentage relative to the parent item.
     Percent(f32),
+    /// The dimension is based on the content of the item, ignoring its specified size
+    ///
+    /// This is only meaningful for [`flex_basis`](FlexboxLayout::flex_basis), where it is `flex-basis: content`:
+    /// the flex base size is measured as if the main size of the item were auto. Elsewhere it behaves like [`Dimension::Auto`].
+    Content,
 }
 
 impl Default for Dimension {
@@ -269,7 +458,49 @@ impl Default for Dimension {
     }
 }
 
+/// The unit of a CSS length or percentage, as read by [`Dimension::from_css`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Unit {
+    /// The value is a number of points, such as `10px`
+    Points,
+    /// The value is a percentage out of 100, such as `50%`
+    Percent,
+}
+
 impl Dimension {
+    /// Creates a [`Dimension::Points`] of `value` points
+    #[must_use]
+    pub const fn points(value: f32) -> Self {
+        Self::Points(value)
+    }
+
+    /// Creates a [`Dimension::Percent`] of the given `fraction` of the parent, where `1.0` is all of it
+    #[must_use]
+    pub const fn percent(fraction: f32) -> Self {
+        Self::Percent(fraction)
+    }
+
+    /// Creates a [`Dimension`] from a number and its unit, as written in CSS
+    ///
+    /// Percentages are out of 100, as in CSS, so `from_css(50.0, Unit::Percent)` is `Dimension::Percent(0.5)`.
+    #[must_use]
+    pub fn from_css(value: f32, unit: Unit) -> Self {
+        match unit {
+            Unit::Points => Self::Points(value),
+            Unit::Percent => Self::Percent(value / 100.0),
+        }
+    }
+
+    /// Converts this [`Dimension`] into points, relative to the `parent` size for percentages, or returns zero
+    ///
+    /// Zero is returned for [`Dimension::Auto`], [`Dimension::Undefined`] and [`Dimension::Content`],
+    /// and for a percentage of an unknown `parent` size, as for margins, padding and borders during layout.
+    #[must_use]
+    pub fn resolved_or_zero(self, parent: Option<f32>) -> f32 {
+        self.resolve(parent).unwrap_or(0.0)
+    }
+
     /// Converts the given [`Dimension`] into a concrete value of points
     pub(crate) fn resolve(self, parent_dim: Option<f32>) -> Option<f32> {
         match self {
@@ -284,6 +515,14 @@ impl Dimension {
     pub(crate) fn is_defined(self) -> bool {
         matches!(self, Dimension::Points(_) | Dimension::Percent(_))
     }
+
+    /// Is this value free of NaN and infinities?
+    pub(crate) fn is_finite(self) -> bool {
+        match self {
+            Dimension::Points(value) | Dimension::Percent(value) => value.is_finite(),
+            _ => true,
+        }
+    }
 }
 
 impl Default for Rect<Dimension> {
@@ -322,6 +561,10 @@ pub struct FlexboxLayout {
     pub position_type: PositionType,
     /// Which direction does the main axis flow in?
     pub flex_direction: FlexDirection,
+    /// Does the main axis of a row run horizontally or vertically?
+    pub writing_mode: WritingMode,
+    /// Do [`JustifyContent::Start`] and [`AlignItems::Start`] refer to the left or the right edge of inline content?
+    pub direction: Direction,
     /// Should elements wrap, or stay in a single line?
     pub flex_wrap: FlexWrap,
     /// How should items be aligned relative to the cross axis?
@@ -340,6 +583,12 @@ pub struct FlexboxLayout {
     pub padding: Rect<Dimension>,
     /// How large should the border be on each side?
     pub border: Rect<Dimension>,
+    /// How large should the gaps between items be?
+    ///
+    /// The `width` is the gap between columns, and the `height` is the gap between rows.
+    /// Percentages are resolved against the inner size of the node along the same axis.
+    /// Any free space distributed by [`JustifyContent`] or [`AlignContent`] is added on top of the gaps.
+    pub gap: Size<Dimension>,
     /// The relative rate at which this item grows when it is expanding to fill space
     ///
     /// 0.0 is the default value, and this value must be positive.
@@ -354,12 +603,20 @@ pub struct FlexboxLayout {
     // TODO: why does this exist as distinct from flex_basis? How do they interact?
     pub size: Size<Dimension>,
     /// Controls the minimum size of the item
+    ///
+    /// Along the main axis, `Dimension::Auto` keeps a measured item from shrinking below its min-content size.
     pub min_size: Size<Dimension>,
     /// Controls the maximum size of the item
     pub max_size: Size<Dimension>,
+    /// Do the `size`, `min_size` and `max_size` include the padding and border?
+    pub box_sizing: BoxSizing,
     /// Sets the preferred aspect ratio for the item
     ///
     /// The ratio is calculated as width divided by height.
+    /// If this is [`None`], the intrinsic aspect ratio of the [`MeasureFunc`](crate::node::MeasureFunc) of the node
+    /// is used instead, if it has one: the ratio set with
+    /// [`Taffy::set_intrinsic_aspect_ratio`](crate::node::Taffy::set_intrinsic_aspect_ratio), or failing that,
+    /// the ratio of the size of a [`MeasureFunc::Fixed`](crate::node::MeasureFunc::Fixed).
     pub aspect_ratio: Option<f32>,
 }
 
@@ -369,6 +626,8 @@ impl Default for FlexboxLayout {
             display: Default::default(),
             position_type: Default::default(),
             flex_direction: Default::default(),
+            writing_mode: Default::default(),
+            direction: Default::default(),
             flex_wrap: Default::default(),
             align_items: Default::default(),
             align_self: Default::default(),
@@ -378,33 +637,90 @@ impl Default for FlexboxLayout {
             margin: Default::default(),
             padding: Default::default(),
             border: Default::default(),
+            gap: Size { width: Dimension::Points(0.0), height: Dimension::Points(0.0) },
             flex_grow: 0.0,
             flex_shrink: 1.0,
             flex_basis: Dimension::Auto,
             size: Default::default(),
             min_size: Default::default(),
             max_size: Default::default(),
+            box_sizing: Default::default(),
             aspect_ratio: Default::default(),
         }
     }
 }
 
 impl FlexboxLayout {
-    /// If the `direction` is row-oriented, the min width. Otherwise the min height
-    pub(crate) fn min_main_size(&self, direction: FlexDirection) -> Dimension {
-        if direction.is_row() {
-            self.min_size.width
-        } else {
-            self.min_size.height
+    /// Creates a default style with the flexibility given by the CSS shorthand `flex: <value>`
+    ///
+    /// This expands to `flex_grow: value`, `flex_shrink: 1.0` and `flex_basis: Dimension::Percent(0.0)`,
+    /// so that the free space is distributed in proportion to `value`, regardless of the content of each item.
+    /// Other fields can be set with struct update syntax: `FlexboxLayout { size, ..FlexboxLayout::flex(1.0) }`.
+    #[must_use]
+    pub fn flex(value: f32) -> Self {
+        Self { flex_grow: value, flex_shrink: 1.0, flex_basis: Dimension::Percent(0.0), ..Default::default() }
+    }
+
+    /// Returns the name of the first numeric field of this style that is NaN or infinite, if any
+    pub(crate) fn non_finite_field(&self) -> Option<&'static str> {
+        let dimensions = [
+            ("position.start", self.position.start),
+            ("position.end", self.position.end),
+            ("position.top", self.position.top),
+            ("position.bottom", self.position.bottom),
+            ("margin.start", self.margin.start),
+            ("margin.end", self.margin.end),
+            ("margin.top", self.margin.top),
+            ("margin.bottom", self.margin.bottom),
+            ("padding.start", self.padding.start),
+            ("padding.end", self.padding.end),
+            ("padding.top", self.padding.top),
+            ("padding.bottom", self.padding.bottom),
+            ("border.start", self.border.start),
+            ("border.end", self.border.end),
+            ("border.top", self.border.top),
+            ("border.bottom", self.border.bottom),
+            ("gap.width", self.gap.width),
+            ("gap.height", self.gap.height),
+            ("flex_basis", self.flex_basis),
+            ("size.width", self.size.width),
+            ("size.height", self.size.height),
+            ("min_size.width", self.min_size.width),
+            ("min_size.height", self.min_size.height),
+            ("max_size.width", self.max_size.width),
+            ("max_size.height", self.max_size.height),
+        ];
+        let factors = [
+            ("flex_grow", Some(self.flex_grow)),
+            ("flex_shrink", Some(self.flex_shrink)),
+            ("aspect_ratio", self.aspect_ratio),
+        ];
+
+        if let Some((name, _)) = dimensions.iter().find(|(_, dimension)| !dimension.is_finite()) {
+            return Some(name);
         }
+        factors.iter().find(|(_, factor)| matches!(factor, Some(factor) if !factor.is_finite())).map(|(name, _)| *name)
     }
 
-    /// If the `direction` is row-oriented, the max width. Otherwise the max height
-    pub(crate) fn max_main_size(&self, direction: FlexDirection) -> Dimension {
-        if direction.is_row() {
-            self.max_size.width
-        } else {
-            self.max_size.height
+    /// Resolves one of the sizes of this node against the `parent_size`, as a border-box size
+    ///
+    /// Under [`BoxSizing::ContentBox`], the padding and border are added to each defined dimension.
+    pub(crate) fn resolve_box_size(&self, size: Size<Dimension>, parent_size: Size<Option<f32>>) -> Size<Option<f32>> {
+        let size = size.resolve(parent_size);
+        match self.box_sizing {
+            BoxSizing::BorderBox => size,
+            BoxSizing::ContentBox => {
+                let resolve = |dim: Dimension| dim.resolve(parent_size.width).unwrap_or(0.0);
+                let horizontal = resolve(self.padding.start)
+                    + resolve(self.padding.end)
+                    + resolve(self.border.start)
+                    + resolve(self.border.end);
+                let vertical = resolve(self.padding.top)
+                    + resolve(self.padding.bottom)
+                    + resolve(self.border.top)
+                    + resolve(self.border.bottom);
+                Size { width: size.width.maybe_add(horizontal), height: size.height.maybe_add(vertical) }
+            }
         }
     }
 
@@ -435,24 +751,6 @@ impl FlexboxLayout {
         }
     }
 
-    /// If the `direction` is row-oriented, the min height. Otherwise the min width
-    pub(crate) fn min_cross_size(&self, direction: FlexDirection) -> Dimension {
-        if direction.is_row() {
-            self.min_size.height
-        } else {
-            self.min_size.width
-        }
-    }
-
-    /// If the `direction` is row-oriented, the max height. Otherwise the max width
-    pub(crate) fn max_cross_size(&self, direction: FlexDirection) -> Dimension {
-        if direction.is_row() {
-            self.max_size.height
-        } else {
-            self.max_size.width
-        }
-    }
-
     /// If the `direction` is row-oriented, the margin top. Otherwise the margin start
     pub(crate) fn cross_margin_start(&self, direction: FlexDirection) -> Dimension {
         if direction.is_row() {
@@ -471,6 +769,17 @@ impl FlexboxLayout {
         }
     }
 
+    /// Is this item aligned by its first or last baseline within the `parent`?
+    pub(crate) fn is_baseline_aligned(&self, parent: &FlexboxLayout) -> bool {
+        matches!(self.align_self(parent), AlignSelf::Baseline | AlignSelf::LastBaseline)
+    }
+
+    /// Do [`AlignItems::Start`] and [`AlignItems::End`] refer to the opposite edges of the cross axis of this container
+    /// to [`AlignItems::FlexStart`] and [`AlignItems::FlexEnd`]?
+    fn reverses_logical_cross_axis(&self) -> bool {
+        (self.flex_wrap == FlexWrap::WrapReverse) != self.direction.reverses_cross_axis(self.flex_direction)
+    }
+
     /// Computes the final alignment of this item based on the parent's [`AlignItems`] and this item's [`AlignSelf`]
     pub(crate) fn align_self(&self, parent: &FlexboxLayout) -> AlignSelf {
         if self.align_self == AlignSelf::Auto {
@@ -480,6 +789,11 @@ impl FlexboxLayout {
                 AlignItems::Center => AlignSelf::Center,
                 AlignItems::Baseline => AlignSelf::Baseline,
                 AlignItems::Stretch => AlignSelf::Stretch,
+                AlignItems::Start if parent.reverses_logical_cross_axis() => AlignSelf::FlexEnd,
+                AlignItems::Start => AlignSelf::FlexStart,
+                AlignItems::End if parent.reverses_logical_cross_axis() => AlignSelf::FlexStart,
+                AlignItems::End => AlignSelf::FlexEnd,
+                AlignItems::LastBaseline => AlignSelf::LastBaseline,
             }
         } else {
             self.align_self
@@ -0,0 +1,96 @@
+//! Export of laid out trees of nodes to SVG for visual debugging, enabled by the `std` feature
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::error::InvalidNode;
+use crate::geometry::Point;
+use crate::node::{Node, Taffy};
+use crate::style::Display;
+use crate::sys::BuildHasher;
+
+/// The colors that nodes are drawn in, indexed by their depth below the root and repeating for deeper trees
+const COLORS: [&str; 6] = ["#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4"];
+
+impl<S: BuildHasher> Taffy<S> {
+    /// Draws the tree below `root` (including `root` itself) as an SVG image of the layout of each node
+    ///
+    /// Each node is drawn as a `<rect>` at its location relative to `root`, colored by its depth and labeled with its id.
+    /// Nodes are drawn in preorder, so children are drawn over their parents, and the same layouts always produce the same output.
+    /// Nodes with a [`Display`] of `None` are skipped, along with their descendants.
+    /// The layouts are as of the last call to [`Taffy::compute_layout`].
+    pub fn to_svg(&self, root: Node) -> Result<String, InvalidNode> {
+        let size = self.layout(root)?.size;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" overflow=\"visible\">\n",
+            width = size.width,
+            height = size.height,
+        );
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![(root, Point::zero(), 0)];
+        while let Some((node, location, depth)) = stack.pop() {
+            if !visited.insert(node) || self.style(node)?.display == Display::None {
+                continue;
+            }
+
+            let size = self.layout(node)?.size;
+            let color = COLORS[depth % COLORS.len()];
+            // Writing to a `String` cannot fail
+            let _ = writeln!(
+                svg,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{color}\" fill-opacity=\"0.2\" stroke=\"{color}\"/>",
+                location.x,
+                location.y,
+                size.width,
+                size.height,
+                color = color,
+            );
+            let _ = writeln!(
+                svg,
+                "  <text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"10\" fill=\"{}\">{}</text>",
+                location.x + 2.0,
+                location.y + 10.0,
+                color,
+                node.local_id(),
+            );
+
+            for child in self.children(node)?.into_iter().rev() {
+                let offset = self.layout(child)?.location;
+                stack.push((child, Point { x: location.x + offset.x, y: location.y + offset.y }, depth + 1));
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Size;
+    use crate::node::Taffy;
+    use crate::style::{Dimension, Display, FlexboxLayout};
+
+    #[test]
+    fn draws_each_node_relative_to_root() {
+        let mut taffy = Taffy::new();
+        let sized = |width, height| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(height) },
+            ..Default::default()
+        };
+        let hidden = taffy.new_with_children(FlexboxLayout { display: Display::None, ..sized(5.0, 5.0) }, &[]).unwrap();
+        let leaf = taffy.new_with_children(sized(10.0, 20.0), &[]).unwrap();
+        let child = taffy.new_with_children(sized(50.0, 40.0), &[leaf]).unwrap();
+        let spacer = taffy.new_with_children(sized(30.0, 10.0), &[]).unwrap();
+        let root = taffy.new_with_children(sized(100.0, 60.0), &[hidden, spacer, child]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let svg = taffy.to_svg(root).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"60\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains("<rect x=\"30\" y=\"0\" width=\"50\" height=\"40\" fill=\"#3cb44b\""));
+        assert!(svg.contains("<rect x=\"30\" y=\"0\" width=\"10\" height=\"20\" fill=\"#4363d8\""));
+        assert_eq!(svg, taffy.to_svg(root).unwrap());
+    }
+}
@@ -2,14 +2,20 @@
 
 // When std is enabled, prefer those types
 #[cfg(feature = "std")]
+pub use self::std::DefaultHashBuilder;
+#[cfg(feature = "std")]
 pub(crate) use self::std::*;
 
 // When alloc but not std is enabled, use those types
 #[cfg(all(feature = "alloc", not(feature = "std")))]
+pub use self::alloc::DefaultHashBuilder;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 pub(crate) use self::alloc::*;
 
 // When neither alloc or std is enabled, use a heapless fallback
 #[cfg(all(not(feature = "alloc"), not(feature = "std")))]
+pub use self::core::DefaultHashBuilder;
+#[cfg(all(not(feature = "alloc"), not(feature = "std")))]
 pub(crate) use self::core::*;
 
 /// For when `std` is enabled
@@ -17,8 +23,14 @@ pub(crate) use self::core::*;
 mod std {
     /// An allocation-backend agnostic [`Box`] type
     pub(crate) type Box<A> = std::boxed::Box<A>;
+    /// An allocation-backend agnostic atomically reference-counted pointer type
+    pub(crate) type Arc<A> = std::sync::Arc<A>;
     /// An allocation-backend agnostic map type
-    pub(crate) type Map<K, V> = std::collections::HashMap<K, V>;
+    pub(crate) type Map<K, V, S> = std::collections::HashMap<K, V, S>;
+    /// The hasher used for the maps of a [`Taffy`](crate::Taffy) unless another one is provided
+    pub type DefaultHashBuilder = std::collections::hash_map::RandomState;
+    /// The trait that the hashers of maps implement
+    pub(crate) use core::hash::BuildHasher;
     /// An allocation-backend agnostic vector type
     pub(crate) type Vec<A> = std::vec::Vec<A>;
     /// A vector of child nodes
@@ -26,13 +38,10 @@ mod std {
     /// A vector of parent nodes
     pub(crate) type ParentsVec<A> = std::vec::Vec<A>;
 
-    /// Creates a new map with the capacity for the specified number of items before it must be resized
+    /// Creates a new map with the capacity for the specified number of items before it must be resized, using the `hasher`
     #[must_use]
-    pub(crate) fn new_map_with_capacity<K, V>(capacity: usize) -> Map<K, V>
-    where
-        K: Eq + std::hash::Hash,
-    {
-        Map::with_capacity(capacity)
+    pub(crate) fn new_map_with_capacity_and_hasher<K, V, S>(capacity: usize, hasher: S) -> Map<K, V, S> {
+        Map::with_capacity_and_hasher(capacity, hasher)
     }
 
     /// Creates a new vector with the capacity for the specified number of items before it must be resized    
@@ -47,6 +56,12 @@ mod std {
         value.round()
     }
 
+    /// Rounds up to the next whole number
+    #[must_use]
+    pub(crate) fn ceil(value: f32) -> f32 {
+        value.ceil()
+    }
+
     /// Computes the absolute value
     #[must_use]
     pub(crate) fn abs(value: f32) -> f32 {
@@ -61,8 +76,14 @@ mod alloc {
 
     /// An allocation-backend agnostic `Box` type
     pub(crate) type Box<A> = alloc::boxed::Box<A>;
+    /// An allocation-backend agnostic atomically reference-counted pointer type
+    pub(crate) type Arc<A> = alloc::sync::Arc<A>;
     /// An allocation-backend agnostic map type
-    pub(crate) type Map<K, V> = hashbrown::HashMap<K, V>;
+    pub(crate) type Map<K, V, S> = hashbrown::HashMap<K, V, S>;
+    /// The hasher used for the maps of a [`Taffy`](crate::Taffy) unless another one is provided
+    pub type DefaultHashBuilder = hashbrown::hash_map::DefaultHashBuilder;
+    /// The trait that the hashers of maps implement
+    pub(crate) use core::hash::BuildHasher;
     /// An allocation-backend agnostic vector type
     pub(crate) type Vec<A> = alloc::vec::Vec<A>;
     /// A vector of child nodes
@@ -70,10 +91,10 @@ mod alloc {
     /// A vector of parent nodes
     pub(crate) type ParentsVec<A> = alloc::vec::Vec<A>;
 
-    /// Creates a new map with the capacity for the specified number of items before it must be resized
+    /// Creates a new map with the capacity for the specified number of items before it must be resized, using the `hasher`
     #[must_use]
-    pub(crate) fn new_map_with_capacity<K, V>(capacity: usize) -> Map<K, V> {
-        Map::with_capacity(capacity)
+    pub(crate) fn new_map_with_capacity_and_hasher<K, V, S>(capacity: usize, hasher: S) -> Map<K, V, S> {
+        Map::with_capacity_and_hasher(capacity, hasher)
     }
 
     /// Creates a new vector with the capacity for the specified number of items before it must be resized
@@ -88,6 +109,12 @@ mod alloc {
         num_traits::float::FloatCore::round(value)
     }
 
+    /// Rounds up to the next whole number
+    #[must_use]
+    pub(crate) fn ceil(value: f32) -> f32 {
+        num_traits::float::FloatCore::ceil(value)
+    }
+
     /// Computes the absolute value
     #[must_use]
     pub(crate) fn abs(value: f32) -> f32 {
@@ -106,7 +133,11 @@ mod core {
     pub const MAX_PARENTS_COUNT: usize = 1;
 
     /// An allocation-backend agnostic map type
-    pub(crate) type Map<K, V> = crate::indexmap::FnvIndexMap<K, V, MAX_NODE_COUNT>;
+    pub(crate) type Map<K, V, S> = crate::indexmap::IndexMap<K, V, S, MAX_NODE_COUNT>;
+    /// The hasher used for the maps of a [`Taffy`](crate::Taffy) unless another one is provided
+    pub type DefaultHashBuilder = hash32::BuildHasherDefault<hash32::FnvHasher>;
+    /// The trait that the hashers of maps implement
+    pub(crate) use hash32::BuildHasher;
     /// An allocation-backend agnostic vector type
     pub(crate) type Vec<A> = arrayvec::ArrayVec<A, MAX_NODE_COUNT>;
     /// A vector of child nodes, whose length cannot exceed [`MAX_CHILD_COUNT`]
@@ -114,15 +145,12 @@ mod core {
     /// A vector of parent nodes, whose length cannot exceed [`MAX_PARENTS_COUNT`]
     pub(crate) type ParentsVec<A> = arrayvec::ArrayVec<A, MAX_PARENTS_COUNT>;
 
-    /// Creates a new map with the capacity for the specified number of items
+    /// Creates a new map with the capacity for the specified number of items, using the `hasher`
     ///
     /// This map cannot be resized.
     #[must_use]
-    pub(crate) fn new_map_with_capacity<K, V>(_capacity: usize) -> Map<K, V>
-    where
-        K: Eq + ::hash32::Hash,
-    {
-        Map::new()
+    pub(crate) fn new_map_with_capacity_and_hasher<K, V, S>(_capacity: usize, hasher: S) -> Map<K, V, S> {
+        Map::with_hasher(hasher)
     }
 
     /// Creates a new map with the capacity for the specified number of items before it must be resized
@@ -140,6 +168,13 @@ mod core {
         num_traits::float::FloatCore::round(value)
     }
 
+    /// Rounds up to the next whole number
+    #[inline]
+    #[must_use]
+    pub(crate) fn ceil(value: f32) -> f32 {
+        num_traits::float::FloatCore::ceil(value)
+    }
+
     /// Computes the absolute value
     #[inline]
     #[must_use]
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod alignment_offset {
+    use taffy::geometry::{Point, Rect, Size};
+    use taffy::node::{Node, Taffy};
+    use taffy::style::*;
+
+    /// Lays out children of 50x20 inside a 300x100 row container with the given `style`
+    fn row(style: FlexboxLayout, children: &[FlexboxLayout]) -> (Taffy, Node, Vec<Node>) {
+        let mut taffy = Taffy::new();
+        let children: Vec<_> = children
+            .iter()
+            .map(|child| {
+                let child = FlexboxLayout {
+                    size: Size { width: Dimension::Points(50.0), height: Dimension::Points(20.0) },
+                    ..*child
+                };
+                taffy.new_with_children(child, &[]).unwrap()
+            })
+            .collect();
+        let style =
+            FlexboxLayout { size: Size { width: Dimension::Points(300.0), height: Dimension::Points(100.0) }, ..style };
+        let node = taffy.new_with_children(style, &children).unwrap();
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        assert_eq!(taffy.alignment_offset(node).unwrap(), Point::zero());
+        (taffy, node, children)
+    }
+
+    #[test]
+    fn centered_items() {
+        let style = FlexboxLayout {
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+        let (taffy, _, children) = row(style, &[FlexboxLayout::default(), FlexboxLayout::default()]);
+
+        assert_eq!(taffy.alignment_offset(children[0]).unwrap(), Point { x: 100.0, y: 40.0 });
+        assert_eq!(taffy.alignment_offset(children[1]).unwrap(), Point { x: 100.0, y: 40.0 });
+        assert_eq!(taffy.layout(children[1]).unwrap().location, Point { x: 150.0, y: 40.0 });
+    }
+
+    #[test]
+    fn space_between_accumulates() {
+        let style = FlexboxLayout { justify_content: JustifyContent::SpaceBetween, ..Default::default() };
+        let (taffy, _, children) = row(style, &[FlexboxLayout::default(); 3]);
+
+        let offsets: Vec<_> = children.iter().map(|child| taffy.alignment_offset(*child).unwrap().x).collect();
+        assert_eq!(offsets, vec![0.0, 75.0, 150.0]);
+    }
+
+    #[test]
+    fn margins_are_not_alignment() {
+        let child = FlexboxLayout {
+            margin: Rect { start: Dimension::Auto, top: Dimension::Points(10.0), ..Default::default() },
+            ..Default::default()
+        };
+        let (taffy, _, children) =
+            row(FlexboxLayout { align_items: AlignItems::FlexEnd, ..Default::default() }, &[child]);
+
+        assert_eq!(taffy.layout(children[0]).unwrap().location, Point { x: 250.0, y: 80.0 });
+        assert_eq!(taffy.alignment_offset(children[0]).unwrap(), Point { x: 0.0, y: 70.0 });
+    }
+
+    #[test]
+    fn absolute_items_are_only_aligned_without_insets() {
+        let absolute = FlexboxLayout { position_type: PositionType::Absolute, ..Default::default() };
+        let inset =
+            FlexboxLayout { position: Rect { start: Dimension::Points(5.0), ..Default::default() }, ..absolute };
+        let style = FlexboxLayout {
+            justify_content: JustifyContent::FlexEnd,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+        let (taffy, _, children) = row(style, &[absolute, inset]);
+
+        assert_eq!(taffy.alignment_offset(children[0]).unwrap(), Point { x: 250.0, y: 40.0 });
+        assert_eq!(taffy.alignment_offset(children[1]).unwrap(), Point { x: 0.0, y: 40.0 });
+    }
+
+    #[test]
+    fn hidden_items_are_not_aligned() {
+        let style = FlexboxLayout { justify_content: JustifyContent::Center, ..Default::default() };
+        let (mut taffy, node, children) = row(style, &[FlexboxLayout::default()]);
+        assert_eq!(taffy.alignment_offset(children[0]).unwrap().x, 125.0);
+
+        taffy.set_style(children[0], FlexboxLayout { display: Display::None, ..Default::default() }).unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        assert_eq!(taffy.alignment_offset(children[0]).unwrap(), Point::zero());
+    }
+}
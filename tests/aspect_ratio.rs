@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod aspect_ratio {
+    use taffy::geometry::Size;
+    use taffy::node::Taffy;
+    use taffy::style::*;
+
+    /// Lays out a child with an aspect ratio of 2 and the given `style` inside a 300x300 container, and returns its size
+    fn ratio_child(flex_direction: FlexDirection, align_items: AlignItems, style: FlexboxLayout) -> Size<f32> {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout { aspect_ratio: Some(2.0), ..style }, &[]).unwrap();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_direction,
+                    align_items,
+                    size: Size { width: Dimension::Points(300.0), height: Dimension::Points(300.0) },
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        taffy.layout(child).unwrap().size
+    }
+
+    #[test]
+    fn min_cross_size_grows_the_transferred_main_size() {
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(50.0), height: Dimension::Auto },
+            min_size: Size { width: Dimension::Points(100.0), height: Dimension::Auto },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ratio_child(FlexDirection::Column, AlignItems::FlexStart, style),
+            Size { width: 100.0, height: 50.0 }
+        );
+    }
+
+    #[test]
+    fn max_cross_size_shrinks_the_transferred_main_size() {
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(200.0), height: Dimension::Auto },
+            max_size: Size { width: Dimension::Points(100.0), height: Dimension::Auto },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ratio_child(FlexDirection::Column, AlignItems::FlexStart, style),
+            Size { width: 100.0, height: 50.0 }
+        );
+    }
+
+    #[test]
+    fn max_cross_size_limits_a_stretched_item() {
+        let style = FlexboxLayout {
+            max_size: Size { width: Dimension::Auto, height: Dimension::Points(40.0) },
+            ..Default::default()
+        };
+
+        assert_eq!(ratio_child(FlexDirection::Row, AlignItems::Stretch, style), Size { width: 80.0, height: 40.0 });
+    }
+
+    #[test]
+    fn main_size_limits_still_apply_after_the_transfer() {
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Auto, height: Dimension::Points(50.0) },
+            max_size: Size { width: Dimension::Points(60.0), height: Dimension::Auto },
+            ..Default::default()
+        };
+
+        assert_eq!(ratio_child(FlexDirection::Row, AlignItems::FlexStart, style), Size { width: 60.0, height: 50.0 });
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod box_sizing {
+    use taffy::geometry::{Point, Rect, Size};
+    use taffy::style::{BoxSizing, Dimension, FlexboxLayout, PositionType};
+
+    /// A 100x100 box with 10 points of padding and 5 points of border on each side
+    fn padded_box(box_sizing: BoxSizing) -> FlexboxLayout {
+        FlexboxLayout {
+            box_sizing,
+            size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+            padding: Rect {
+                start: Dimension::Points(10.0),
+                end: Dimension::Points(10.0),
+                top: Dimension::Points(10.0),
+                bottom: Dimension::Points(10.0),
+            },
+            border: Rect {
+                start: Dimension::Points(5.0),
+                end: Dimension::Points(5.0),
+                top: Dimension::Points(5.0),
+                bottom: Dimension::Points(5.0),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Lays out a [`padded_box`] with a single stretched child, and returns the size of the box and the layout of the child
+    fn layout_padded_box(style: FlexboxLayout) -> (Size<f32>, taffy::layout::Layout) {
+        let mut taffy = taffy::Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout { flex_grow: 1.0, ..Default::default() }, &[]).unwrap();
+        let node = taffy.new_with_children(style, &[child]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[node]).unwrap();
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        (taffy.layout(node).unwrap().size, *taffy.layout(child).unwrap())
+    }
+
+    #[test]
+    fn border_box_includes_padding_and_border() {
+        let (size, child) = layout_padded_box(padded_box(BoxSizing::BorderBox));
+
+        assert_eq!(size, Size { width: 100.0, height: 100.0 });
+        assert_eq!(child.size, Size { width: 70.0, height: 70.0 });
+        assert_eq!(child.location, Point { x: 15.0, y: 15.0 });
+    }
+
+    #[test]
+    fn content_box_excludes_padding_and_border() {
+        let (size, child) = layout_padded_box(padded_box(BoxSizing::ContentBox));
+
+        assert_eq!(size, Size { width: 130.0, height: 130.0 });
+        assert_eq!(child.size, Size { width: 100.0, height: 100.0 });
+        assert_eq!(child.location, Point { x: 15.0, y: 15.0 });
+    }
+
+    #[test]
+    fn content_box_applies_to_min_and_max_size() {
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(500.0) },
+            min_size: Size { width: Dimension::Points(50.0), height: Dimension::Auto },
+            max_size: Size { width: Dimension::Auto, height: Dimension::Points(60.0) },
+            ..padded_box(BoxSizing::ContentBox)
+        };
+        let (size, _) = layout_padded_box(style);
+
+        assert_eq!(size, Size { width: 80.0, height: 90.0 });
+    }
+
+    #[test]
+    fn content_box_applies_to_absolute_children() {
+        let (size, _) = layout_padded_box(FlexboxLayout {
+            position_type: PositionType::Absolute,
+            ..padded_box(BoxSizing::ContentBox)
+        });
+
+        assert_eq!(size, Size { width: 130.0, height: 130.0 });
+    }
+
+    #[test]
+    fn content_box_applies_to_root() {
+        let mut taffy = taffy::Taffy::new();
+        let node = taffy.new_with_children(padded_box(BoxSizing::ContentBox), &[]).unwrap();
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(node).unwrap().size, Size { width: 130.0, height: 130.0 });
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod containing_block {
+    use taffy::geometry::{Point, Rect, Size};
+    use taffy::node::{Node, Taffy};
+    use taffy::style::*;
+
+    /// A 20 by 20 absolutely positioned item with the given insets
+    fn absolute(taffy: &mut Taffy, position: Rect<Dimension>) -> Node {
+        let style = FlexboxLayout {
+            position_type: PositionType::Absolute,
+            position,
+            size: Size { width: Dimension::Points(20.0), height: Dimension::Points(20.0) },
+            ..Default::default()
+        };
+        taffy.new_with_children(style, &[]).unwrap()
+    }
+
+    /// A 200 by 200 grandparent with a border of 5, containing a 100 by 100 parent offset by 50 and 30
+    /// with the given `position_type`, which contains the `item`
+    fn nested(taffy: &mut Taffy, position_type: PositionType, item: Node) -> (Node, Node) {
+        let parent = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    position_type,
+                    margin: Rect { start: Dimension::Points(50.0), top: Dimension::Points(30.0), ..Default::default() },
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &[item],
+            )
+            .unwrap();
+        let grandparent = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    border: Rect {
+                        start: Dimension::Points(5.0),
+                        end: Dimension::Points(5.0),
+                        top: Dimension::Points(5.0),
+                        bottom: Dimension::Points(5.0),
+                    },
+                    size: Size { width: Dimension::Points(200.0), height: Dimension::Points(200.0) },
+                    ..Default::default()
+                },
+                &[parent],
+            )
+            .unwrap();
+        taffy.compute_layout(grandparent, Size::undefined()).unwrap();
+        (grandparent, parent)
+    }
+
+    #[test]
+    fn positioned_grandparent_is_the_containing_block() {
+        let mut taffy = Taffy::new();
+        let position = Rect { start: Dimension::Points(10.0), top: Dimension::Points(10.0), ..Default::default() };
+        let item = absolute(&mut taffy, position);
+        let (_, parent) = nested(&mut taffy, PositionType::Static, item);
+
+        // The parent sits at 55 by 35 within the grandparent, whose padding box starts at 5 by 5
+        assert_eq!(taffy.layout(parent).unwrap().location, Point { x: 55.0, y: 35.0 });
+        assert_eq!(taffy.layout(item).unwrap().location, Point { x: -40.0, y: -20.0 });
+    }
+
+    #[test]
+    fn end_insets_are_relative_to_the_containing_block() {
+        let mut taffy = Taffy::new();
+        let position = Rect { end: Dimension::Points(10.0), bottom: Dimension::Points(10.0), ..Default::default() };
+        let item = absolute(&mut taffy, position);
+        nested(&mut taffy, PositionType::Static, item);
+
+        // 200 - 5 - 10 - 20 = 165 within the grandparent
+        assert_eq!(taffy.layout(item).unwrap().location, Point { x: 110.0, y: 130.0 });
+    }
+
+    #[test]
+    fn positioned_parent_is_the_containing_block() {
+        let mut taffy = Taffy::new();
+        let position = Rect { start: Dimension::Points(10.0), top: Dimension::Points(10.0), ..Default::default() };
+        let item = absolute(&mut taffy, position);
+        nested(&mut taffy, PositionType::Relative, item);
+
+        assert_eq!(taffy.layout(item).unwrap().location, Point { x: 10.0, y: 10.0 });
+    }
+
+    #[test]
+    fn axes_without_insets_keep_the_static_position() {
+        let mut taffy = Taffy::new();
+        let position = Rect { top: Dimension::Points(10.0), ..Default::default() };
+        let item = absolute(&mut taffy, position);
+        nested(&mut taffy, PositionType::Static, item);
+
+        assert_eq!(taffy.layout(item).unwrap().location, Point { x: 0.0, y: -20.0 });
+    }
+
+    #[test]
+    fn sizes_resolve_against_the_containing_block() {
+        let mut taffy = Taffy::new();
+        let item = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    position_type: PositionType::Absolute,
+                    position: Rect { start: Dimension::Points(0.0), ..Default::default() },
+                    size: Size { width: Dimension::Percent(0.5), height: Dimension::Points(20.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        nested(&mut taffy, PositionType::Static, item);
+
+        assert_eq!(taffy.layout(item).unwrap().size.width, 100.0);
+    }
+
+    #[test]
+    fn static_root_is_the_containing_block() {
+        let mut taffy = Taffy::new();
+        let position = Rect { start: Dimension::Points(10.0), top: Dimension::Points(10.0), ..Default::default() };
+        let item = absolute(&mut taffy, position);
+        let parent = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    position_type: PositionType::Static,
+                    margin: Rect { start: Dimension::Points(50.0), ..Default::default() },
+                    ..Default::default()
+                },
+                &[item],
+            )
+            .unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    position_type: PositionType::Static,
+                    size: Size { width: Dimension::Points(200.0), height: Dimension::Points(200.0) },
+                    ..Default::default()
+                },
+                &[parent],
+            )
+            .unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(item).unwrap().location, Point { x: -40.0, y: 10.0 });
+    }
+
+    #[test]
+    fn static_items_ignore_their_offsets() {
+        let mut taffy = Taffy::new();
+        let item = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    position_type: PositionType::Static,
+                    position: Rect {
+                        start: Dimension::Points(10.0),
+                        top: Dimension::Points(10.0),
+                        ..Default::default()
+                    },
+                    size: Size { width: Dimension::Points(20.0), height: Dimension::Points(20.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[item]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(item).unwrap().location, Point { x: 0.0, y: 0.0 });
+    }
+}
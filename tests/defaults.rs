@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod defaults {
+    use taffy::geometry::{Point, Size};
+    use taffy::style::*;
+
+    #[test]
+    fn defaults_match_css() {
+        let style = FlexboxLayout::default();
+
+        assert_eq!(style.display, Display::Flex);
+        assert_eq!(style.position_type, PositionType::Relative);
+        assert_eq!(style.flex_direction, FlexDirection::Row);
+        assert_eq!(style.writing_mode, WritingMode::HorizontalTb);
+        assert_eq!(style.flex_wrap, FlexWrap::NoWrap);
+        assert_eq!(style.align_items, AlignItems::Stretch);
+        assert_eq!(style.align_self, AlignSelf::Auto);
+        assert_eq!(style.align_content, AlignContent::Stretch);
+        assert_eq!(style.justify_content, JustifyContent::FlexStart);
+        assert_eq!(style.flex_grow, 0.0);
+        assert_eq!(style.flex_shrink, 1.0);
+        assert_eq!(style.flex_basis, Dimension::Auto);
+        assert_eq!(style.size, Size { width: Dimension::Auto, height: Dimension::Auto });
+        assert_eq!(style.gap, Size { width: Dimension::Points(0.0), height: Dimension::Points(0.0) });
+    }
+
+    #[test]
+    fn default_container_stretches_and_packs_children_at_start() {
+        let mut taffy = taffy::Taffy::new();
+        let child_style = |width| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Auto },
+            ..Default::default()
+        };
+        let child1 = taffy.new_with_children(child_style(20.0), &[]).unwrap();
+        let child2 = taffy.new_with_children(child_style(30.0), &[]).unwrap();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &[child1, child2],
+            )
+            .unwrap();
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(child1).unwrap().size, Size { width: 20.0, height: 100.0 });
+        assert_eq!(taffy.layout(child1).unwrap().location, Point { x: 0.0, y: 0.0 });
+        assert_eq!(taffy.layout(child2).unwrap().size, Size { width: 30.0, height: 100.0 });
+        assert_eq!(taffy.layout(child2).unwrap().location, Point { x: 20.0, y: 0.0 });
+    }
+
+    #[test]
+    fn default_container_does_not_wrap_and_shrinks_children() {
+        let mut taffy = taffy::Taffy::new();
+        let children: Vec<_> = (0..4)
+            .map(|_| {
+                taffy
+                    .new_with_children(
+                        FlexboxLayout {
+                            size: Size { width: Dimension::Points(50.0), height: Dimension::Points(10.0) },
+                            ..Default::default()
+                        },
+                        &[],
+                    )
+                    .unwrap()
+            })
+            .collect();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &children,
+            )
+            .unwrap();
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        for (i, child) in children.into_iter().enumerate() {
+            let layout = taffy.layout(child).unwrap();
+            assert_eq!(layout.size, Size { width: 25.0, height: 10.0 });
+            assert_eq!(layout.location, Point { x: 25.0 * i as f32, y: 0.0 });
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod dimension {
+    use taffy::style::{Dimension, Unit};
+
+    #[test]
+    fn constructors_match_variants() {
+        assert_eq!(Dimension::points(12.0), Dimension::Points(12.0));
+        assert_eq!(Dimension::percent(0.25), Dimension::Percent(0.25));
+    }
+
+    #[test]
+    fn from_css_reads_percentages_out_of_100() {
+        assert_eq!(Dimension::from_css(10.0, Unit::Points), Dimension::Points(10.0));
+        assert_eq!(Dimension::from_css(50.0, Unit::Percent), Dimension::Percent(0.5));
+        assert_eq!(Dimension::from_css(-20.0, Unit::Percent), Dimension::Percent(-0.2));
+    }
+
+    #[test]
+    fn resolved_or_zero() {
+        assert_eq!(Dimension::Points(10.0).resolved_or_zero(None), 10.0);
+        assert_eq!(Dimension::Percent(0.5).resolved_or_zero(Some(80.0)), 40.0);
+        assert_eq!(Dimension::Percent(0.5).resolved_or_zero(None), 0.0);
+        assert_eq!(Dimension::Auto.resolved_or_zero(Some(80.0)), 0.0);
+        assert_eq!(Dimension::Undefined.resolved_or_zero(Some(80.0)), 0.0);
+        assert_eq!(Dimension::Content.resolved_or_zero(Some(80.0)), 0.0);
+    }
+}
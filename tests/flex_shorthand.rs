@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod flex_shorthand {
+    use taffy::geometry::Size;
+    use taffy::node::MeasureFunc;
+    use taffy::style::{Dimension, FlexboxLayout};
+
+    #[test]
+    fn flex_expands_like_css() {
+        let style = FlexboxLayout::flex(2.0);
+
+        assert_eq!(style.flex_grow, 2.0);
+        assert_eq!(style.flex_shrink, 1.0);
+        assert_eq!(style.flex_basis, Dimension::Percent(0.0));
+        assert_eq!(FlexboxLayout { flex_grow: 0.0, flex_basis: Dimension::Auto, ..style }, FlexboxLayout::default());
+    }
+
+    #[test]
+    fn flex_distributes_space_regardless_of_content() {
+        let mut taffy = taffy::Taffy::new();
+        let wide =
+            taffy.new_leaf(FlexboxLayout::flex(1.0), MeasureFunc::fixed(Size { width: 40.0, height: 10.0 })).unwrap();
+        let narrow =
+            taffy.new_leaf(FlexboxLayout::flex(2.0), MeasureFunc::fixed(Size { width: 10.0, height: 10.0 })).unwrap();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(300.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &[wide, narrow],
+            )
+            .unwrap();
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(wide).unwrap().size.width, 100.0);
+        assert_eq!(taffy.layout(narrow).unwrap().size.width, 200.0);
+    }
+}
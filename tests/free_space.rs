@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod free_space {
+    use taffy::geometry::Size;
+    use taffy::node::{Node, Taffy};
+    use taffy::style::*;
+
+    /// Lays out children of the given widths inside a 100x100 container with the given `style`
+    fn container(style: FlexboxLayout, widths: &[f32]) -> (Taffy, Node) {
+        let mut taffy = Taffy::new();
+        let children: Vec<_> = widths
+            .iter()
+            .map(|width| {
+                let style = FlexboxLayout {
+                    flex_shrink: 0.0,
+                    size: Size { width: Dimension::Points(*width), height: Dimension::Points(10.0) },
+                    ..Default::default()
+                };
+                taffy.new_with_children(style, &[]).unwrap()
+            })
+            .collect();
+        let style =
+            FlexboxLayout { size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) }, ..style };
+        let node = taffy.new_with_children(style, &children).unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        (taffy, node)
+    }
+
+    #[test]
+    fn free_space_after_placing_items() {
+        let style = FlexboxLayout { justify_content: JustifyContent::Center, ..Default::default() };
+        let (taffy, node) = container(style, &[20.0, 30.0]);
+
+        assert_eq!(taffy.free_space(node).unwrap(), 50.0);
+    }
+
+    #[test]
+    fn free_space_is_negative_when_overflowing() {
+        let (taffy, node) = container(FlexboxLayout::default(), &[80.0, 40.0]);
+
+        assert_eq!(taffy.free_space(node).unwrap(), -20.0);
+    }
+
+    #[test]
+    fn free_space_excludes_gaps() {
+        let style = FlexboxLayout {
+            gap: Size { width: Dimension::Points(10.0), height: Dimension::Undefined },
+            ..Default::default()
+        };
+        let (taffy, node) = container(style, &[20.0, 30.0]);
+
+        assert_eq!(taffy.free_space(node).unwrap(), 40.0);
+    }
+
+    #[test]
+    fn free_space_uses_the_fullest_line() {
+        let style = FlexboxLayout { flex_wrap: FlexWrap::Wrap, ..Default::default() };
+        let (taffy, node) = container(style, &[60.0, 30.0, 80.0]);
+
+        assert_eq!(taffy.free_space(node).unwrap(), 10.0);
+    }
+
+    #[test]
+    fn leaves_have_no_free_space() {
+        let mut taffy = Taffy::new();
+        let node = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.compute_layout(node, Size { width: Some(100.0), height: Some(100.0) }).unwrap();
+
+        assert_eq!(taffy.free_space(node).unwrap(), 0.0);
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(all(test, any(feature = "std", feature = "alloc")))]
+mod fuzz {
+    use taffy::error::LayoutError;
+    use taffy::geometry::Size;
+    use taffy::node::{MeasureFunc, Node, Taffy};
+    use taffy::style::{Dimension, FlexDirection, FlexWrap, FlexboxLayout};
+
+    /// A small xorshift generator, so that every run performs the same mutations
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn pick(&mut self, nodes: &[Node]) -> Option<Node> {
+            if nodes.is_empty() {
+                None
+            } else {
+                Some(nodes[self.below(nodes.len())])
+            }
+        }
+    }
+
+    /// A style with a few randomized properties
+    fn style(rng: &mut Rng) -> FlexboxLayout {
+        let dimension = |rng: &mut Rng| match rng.below(3) {
+            0 => Dimension::Auto,
+            1 => Dimension::Points(rng.below(100) as f32),
+            _ => Dimension::Percent(rng.below(100) as f32 / 100.0),
+        };
+        FlexboxLayout {
+            flex_direction: if rng.below(2) == 0 { FlexDirection::Row } else { FlexDirection::Column },
+            flex_wrap: if rng.below(2) == 0 { FlexWrap::NoWrap } else { FlexWrap::Wrap },
+            flex_grow: rng.below(3) as f32,
+            size: Size { width: dimension(rng), height: dimension(rng) },
+            ..Default::default()
+        }
+    }
+
+    /// Applies `steps` random mutations, including ones with removed nodes and out of bounds indices
+    ///
+    /// Removed nodes are only used without the `debug-checks` feature, as it turns their use into a panic.
+    ///
+    /// Every live node is kept in creation order, and children are always created before their parents,
+    /// so the mutations never form a cycle.
+    fn mutate(seed: u64, steps: usize) {
+        let mut rng = Rng(seed);
+        let mut taffy = Taffy::new();
+        // Random chains can grow deep enough to overflow the stack of a test thread, which the limit guards against
+        taffy.set_max_depth(32);
+        let mut live: Vec<Node> = Vec::new();
+        let mut dead: Vec<Node> = Vec::new();
+
+        for _ in 0..steps {
+            if live.is_empty() {
+                live.push(taffy.new_with_children(style(&mut rng), &[]).unwrap());
+            }
+
+            // An older node, a newer node, and a node that may already have been removed
+            let (a, b) = (rng.below(live.len()), rng.below(live.len()));
+            let (older, newer) = (live[a.min(b)], live[a.max(b)]);
+            let stale = !cfg!(feature = "debug-checks") && rng.below(4) == 0;
+            let any = if stale { rng.pick(&dead) } else { rng.pick(&live) }.unwrap_or(older);
+            let index = rng.below(4);
+
+            match rng.below(12) {
+                0 => live.push(taffy.new_with_children(style(&mut rng), &[]).unwrap()),
+                1 => live.push(
+                    taffy.new_leaf(style(&mut rng), MeasureFunc::fixed(Size { width: 10.0, height: 10.0 })).unwrap(),
+                ),
+                2 if older != newer => {
+                    let _ = taffy.add_child(newer, older);
+                }
+                3 => {
+                    let children: Vec<_> = (0..rng.below(4)).filter_map(|_| rng.pick(&live[..a.max(b)])).collect();
+                    let _ = taffy.set_children(newer, &children);
+                }
+                4 => {
+                    let _ = taffy.remove_child(any, older);
+                }
+                5 => {
+                    let _ = taffy.remove_child_at_index(any, index);
+                }
+                6 if older != newer => {
+                    let _ = taffy.replace_child_at_index(newer, index, older);
+                }
+                7 => {
+                    if taffy.remove(any).is_ok() {
+                        live.retain(|node| *node != any);
+                        dead.push(any);
+                    }
+                }
+                8 if rng.below(4) == 0 => {
+                    if taffy.remove_many(&[any, older, any]).is_ok() {
+                        live.retain(|node| *node != any && *node != older);
+                        dead.extend([any, older]);
+                    }
+                }
+                8 => {
+                    let _ = taffy.set_style(any, style(&mut rng));
+                    let _ =
+                        taffy.set_gap(any, Size { width: Dimension::Points(index as f32), height: Dimension::Auto });
+                }
+                9 => {
+                    let _ = taffy.child_at_index(any, index);
+                    let _ = taffy.children(any);
+                    let _ = taffy.layout(any);
+                }
+                10 => {
+                    let _ = taffy.mark_dirty(any);
+                    let _ = taffy.set_measure(any, None);
+                }
+                _ => {
+                    let _ = taffy.compute_layout(any, Size { width: Some(200.0), height: None });
+                }
+            }
+        }
+
+        for node in &live {
+            for child in taffy.children(*node).unwrap() {
+                assert!(live.contains(&child), "{:?} is a child of {:?}, but has been removed", child, node);
+            }
+        }
+        for root in &live {
+            match taffy.compute_layout(*root, Size::undefined()) {
+                Ok(()) | Err(LayoutError::LimitExceeded { .. }) => {}
+                Err(error) => panic!("{:?} is live, but could not be laid out: {:?}", root, error),
+            }
+        }
+    }
+
+    #[test]
+    fn random_mutations_do_not_panic() {
+        for seed in 1..=64u64 {
+            mutate(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15), 300);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod gap {
+    use taffy::geometry::{Point, Size};
+    use taffy::node::{Node, Taffy};
+    use taffy::style::*;
+
+    /// Lays out `count` children with the `child` style inside a container with the `container` style
+    fn layout(container: FlexboxLayout, child: FlexboxLayout, count: usize) -> (Taffy, Node, Vec<Node>) {
+        let mut taffy = Taffy::new();
+        let children: Vec<_> = (0..count).map(|_| taffy.new_with_children(child, &[]).unwrap()).collect();
+        let node = taffy.new_with_children(container, &children).unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        (taffy, node, children)
+    }
+
+    /// The locations of each of the `children`
+    fn locations(taffy: &Taffy, children: &[Node]) -> Vec<Point<f32>> {
+        children.iter().map(|child| taffy.layout(*child).unwrap().location).collect()
+    }
+
+    /// A fixed-size 50x10 item
+    fn item() -> FlexboxLayout {
+        FlexboxLayout {
+            size: Size { width: Dimension::Points(50.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn gap_is_applied_before_justify_content() {
+        let container = FlexboxLayout {
+            justify_content: JustifyContent::SpaceBetween,
+            gap: Size { width: Dimension::Points(10.0), height: Dimension::Points(0.0) },
+            size: Size { width: Dimension::Points(300.0), height: Dimension::Points(100.0) },
+            ..Default::default()
+        };
+        let (taffy, _, children) = layout(container, item(), 3);
+
+        let xs: Vec<_> = locations(&taffy, &children).iter().map(|location| location.x).collect();
+        assert_eq!(xs, vec![0.0, 125.0, 250.0]);
+        assert_eq!(taffy.alignment_offset(children[1]).unwrap().x, 65.0);
+    }
+
+    #[test]
+    fn gap_is_removed_from_flexible_space() {
+        let container = FlexboxLayout {
+            gap: Size { width: Dimension::Points(10.0), height: Dimension::Points(0.0) },
+            size: Size { width: Dimension::Points(320.0), height: Dimension::Points(100.0) },
+            ..Default::default()
+        };
+        let (taffy, _, children) = layout(container, FlexboxLayout::flex(1.0), 3);
+
+        for (i, child) in children.iter().enumerate() {
+            let layout = taffy.layout(*child).unwrap();
+            assert_eq!(layout.size.width, 100.0);
+            assert_eq!(layout.location.x, 110.0 * i as f32);
+        }
+    }
+
+    #[test]
+    fn gap_contributes_to_container_size() {
+        let container = FlexboxLayout {
+            flex_direction: FlexDirection::Column,
+            gap: Size { width: Dimension::Points(0.0), height: Dimension::Points(5.0) },
+            ..Default::default()
+        };
+        let (taffy, node, children) = layout(container, item(), 3);
+
+        assert_eq!(taffy.layout(node).unwrap().size, Size { width: 50.0, height: 40.0 });
+        let ys: Vec<_> = locations(&taffy, &children).iter().map(|location| location.y).collect();
+        assert_eq!(ys, vec![0.0, 15.0, 30.0]);
+    }
+
+    #[test]
+    fn gaps_separate_wrapped_lines() {
+        let container = FlexboxLayout {
+            flex_wrap: FlexWrap::Wrap,
+            align_content: AlignContent::FlexStart,
+            gap: Size { width: Dimension::Points(10.0), height: Dimension::Points(20.0) },
+            size: Size { width: Dimension::Points(110.0), height: Dimension::Auto },
+            ..Default::default()
+        };
+        // Two items with their 10px gap fill the line exactly, so the third wraps
+        let (taffy, node, children) = layout(container, item(), 3);
+
+        assert_eq!(
+            locations(&taffy, &children),
+            vec![Point { x: 0.0, y: 0.0 }, Point { x: 60.0, y: 0.0 }, Point { x: 0.0, y: 30.0 }]
+        );
+        assert_eq!(taffy.layout(node).unwrap().size.height, 40.0);
+    }
+
+    #[test]
+    fn percentage_gap_resolves_against_inner_size() {
+        let container = FlexboxLayout {
+            gap: Size { width: Dimension::Percent(0.1), height: Dimension::Points(0.0) },
+            size: Size { width: Dimension::Points(200.0), height: Dimension::Points(100.0) },
+            ..Default::default()
+        };
+        let (taffy, _, children) = layout(container, item(), 2);
+
+        assert_eq!(taffy.layout(children[1]).unwrap().location.x, 70.0);
+    }
+
+    #[test]
+    fn set_gap() {
+        let container = FlexboxLayout {
+            justify_content: JustifyContent::Center,
+            size: Size { width: Dimension::Points(300.0), height: Dimension::Points(100.0) },
+            ..Default::default()
+        };
+        let (mut taffy, node, children) = layout(container, item(), 2);
+
+        taffy.set_gap(node, Size { width: Dimension::Points(20.0), height: Dimension::Points(0.0) }).unwrap();
+        assert!(taffy.dirty(node).unwrap());
+        assert_eq!(taffy.style(node).unwrap().justify_content, JustifyContent::Center);
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        let xs: Vec<_> = locations(&taffy, &children).iter().map(|location| location.x).collect();
+        assert_eq!(xs, vec![90.0, 160.0]);
+
+        assert!(taffy.set_gap(node, Size { width: Dimension::Points(f32::NAN), height: Dimension::Auto }).is_err());
+        assert_eq!(taffy.style(node).unwrap().gap.width, Dimension::Points(20.0));
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod geometry {
+    use taffy::geometry::{AbsoluteAxis, AvailableSpace, Rect, Size};
+    use taffy::style::{Dimension, FlexDirection};
+
+    #[test]
+    fn size_constructors() {
+        assert_eq!(Size::new(1.0, 2.0), Size { width: 1.0, height: 2.0 });
+        assert_eq!(
+            Size::square(Dimension::Points(5.0)),
+            Size { width: Dimension::Points(5.0), height: Dimension::Points(5.0) }
+        );
+    }
+
+    #[test]
+    fn rect_constructors() {
+        assert_eq!(Rect::new(1, 2, 3, 4), Rect { start: 1, end: 2, top: 3, bottom: 4 });
+        assert_eq!(
+            Rect::splat(Dimension::Percent(0.5)),
+            Rect {
+                start: Dimension::Percent(0.5),
+                end: Dimension::Percent(0.5),
+                top: Dimension::Percent(0.5),
+                bottom: Dimension::Percent(0.5),
+            }
+        );
+    }
+
+    #[test]
+    fn constructors_are_const() {
+        const SIZE: Size<f32> = Size::square(10.0);
+        const PADDING: Rect<Dimension> = Rect::splat(Dimension::Points(4.0));
+
+        assert_eq!(SIZE, Size::new(10.0, 10.0));
+        assert_eq!(PADDING.start, Dimension::Points(4.0));
+    }
+
+    #[test]
+    fn main_axis_follows_flex_direction() {
+        assert_eq!(FlexDirection::Row.main_axis(), AbsoluteAxis::Horizontal);
+        assert_eq!(FlexDirection::RowReverse.main_axis(), AbsoluteAxis::Horizontal);
+        assert_eq!(FlexDirection::Column.main_axis(), AbsoluteAxis::Vertical);
+        assert_eq!(FlexDirection::ColumnReverse.cross_axis(), AbsoluteAxis::Horizontal);
+    }
+
+    #[test]
+    fn size_components_by_axis() {
+        let mut size = Size::new(1.0, 2.0);
+        assert_eq!(size.get(AbsoluteAxis::Horizontal), 1.0);
+        assert_eq!(size.get(FlexDirection::Column.main_axis()), 2.0);
+
+        size.set(AbsoluteAxis::Vertical, 3.0);
+        size.set(AbsoluteAxis::Vertical.other(), 4.0);
+        assert_eq!(size, Size::new(4.0, 3.0));
+    }
+
+    #[test]
+    fn available_space() {
+        assert_eq!(AvailableSpace::Definite(10.0).into_option(), Some(10.0));
+        assert_eq!(AvailableSpace::MinContent.into_option(), None);
+        assert_eq!(AvailableSpace::MaxContent.unwrap_or(5.0), 5.0);
+        assert_eq!(AvailableSpace::Definite(10.0).unwrap_or(5.0), 10.0);
+        assert!(AvailableSpace::MinContent.is_intrinsic());
+        assert!(AvailableSpace::MaxContent.is_intrinsic());
+        assert!(!AvailableSpace::Definite(0.0).is_intrinsic());
+    }
+
+    #[test]
+    fn available_space_converts_from_the_measure_arguments() {
+        assert_eq!(AvailableSpace::from(None), AvailableSpace::MaxContent);
+        assert_eq!(AvailableSpace::from(Some(3.0)), AvailableSpace::Definite(3.0));
+        assert_eq!(AvailableSpace::from(3.0), AvailableSpace::Definite(3.0));
+        assert_eq!(Option::<f32>::from(AvailableSpace::MinContent), None);
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod last_baseline {
+    use taffy::geometry::{Rect, Size};
+    use taffy::node::{MeasureFunc, Node, Taffy};
+    use taffy::style::*;
+
+    /// A leaf of the given height, standing in for a single line of text
+    fn line(taffy: &mut Taffy, height: f32) -> Node {
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(30.0), height: Dimension::Points(height) },
+            ..Default::default()
+        };
+        taffy.new_with_children(style, &[]).unwrap()
+    }
+
+    /// A column of `lines` lines that are 10 high, followed by `padding_bottom` below the last one
+    fn paragraph(taffy: &mut Taffy, lines: usize, padding_bottom: f32) -> Node {
+        let lines: Vec<_> = (0..lines).map(|_| line(taffy, 10.0)).collect();
+        let style = FlexboxLayout {
+            flex_direction: FlexDirection::Column,
+            padding: Rect { bottom: Dimension::Points(padding_bottom), ..Default::default() },
+            ..Default::default()
+        };
+        taffy.new_with_children(style, &lines).unwrap()
+    }
+
+    /// Lays out the `items` in a row aligned by `align_items`, and returns the vertical offset of each of them
+    fn offsets(taffy: &mut Taffy, align_items: AlignItems, items: &[Node]) -> Vec<f32> {
+        let row = taffy.new_with_children(FlexboxLayout { align_items, ..Default::default() }, items).unwrap();
+        taffy.compute_layout(row, Size::undefined()).unwrap();
+        items.iter().map(|item| taffy.layout(*item).unwrap().location.y).collect()
+    }
+
+    #[test]
+    fn multi_line_items_align_by_their_last_lines() {
+        let mut taffy = Taffy::new();
+        let items = [paragraph(&mut taffy, 2, 5.0), paragraph(&mut taffy, 3, 0.0), line(&mut taffy, 12.0)];
+
+        // The last baseline of each item ends up 30 from the top, and the padding of the first item hangs below it
+        assert_eq!(offsets(&mut taffy, AlignItems::LastBaseline, &items), [10.0, 0.0, 18.0]);
+
+        let mut taffy = Taffy::new();
+        let items = [paragraph(&mut taffy, 2, 5.0), paragraph(&mut taffy, 3, 0.0), line(&mut taffy, 12.0)];
+        assert_eq!(offsets(&mut taffy, AlignItems::Baseline, &items), [2.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn lines_fit_the_items_hanging_below_their_last_baselines() {
+        let mut taffy = Taffy::new();
+        let row = taffy
+            .new_with_children(FlexboxLayout { align_items: AlignItems::LastBaseline, ..Default::default() }, &[])
+            .unwrap();
+        let items = [paragraph(&mut taffy, 2, 5.0), paragraph(&mut taffy, 3, 0.0)];
+        taffy.set_children(row, &items).unwrap();
+        taffy.compute_layout(row, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(row).unwrap().size.height, 35.0);
+    }
+
+    #[test]
+    fn single_lines_align_like_first_baselines() {
+        let mut taffy = Taffy::new();
+        let items = [line(&mut taffy, 10.0), line(&mut taffy, 20.0)];
+        let last = offsets(&mut taffy, AlignItems::LastBaseline, &items);
+
+        let mut taffy = Taffy::new();
+        let items = [line(&mut taffy, 10.0), line(&mut taffy, 20.0)];
+        assert_eq!(last, offsets(&mut taffy, AlignItems::Baseline, &items));
+        assert_eq!(last, [10.0, 0.0]);
+    }
+
+    #[test]
+    fn align_self_overrides_align_items() {
+        let mut taffy = Taffy::new();
+        let paragraph = paragraph(&mut taffy, 2, 0.0);
+        let line = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    align_self: AlignSelf::LastBaseline,
+                    size: Size { width: Dimension::Points(30.0), height: Dimension::Points(10.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let style = FlexboxLayout { align_self: AlignSelf::LastBaseline, ..*taffy.style(paragraph).unwrap() };
+        taffy.set_style(paragraph, style).unwrap();
+
+        assert_eq!(offsets(&mut taffy, AlignItems::FlexStart, &[paragraph, line]), [0.0, 10.0]);
+    }
+
+    #[test]
+    fn columns_align_last_baselines_to_the_end() {
+        let mut taffy = Taffy::new();
+        let wide =
+            taffy.new_leaf(FlexboxLayout::default(), MeasureFunc::fixed(Size { width: 50.0, height: 10.0 })).unwrap();
+        let narrow =
+            taffy.new_leaf(FlexboxLayout::default(), MeasureFunc::fixed(Size { width: 20.0, height: 10.0 })).unwrap();
+        let column = FlexboxLayout {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::LastBaseline,
+            ..Default::default()
+        };
+        let column = taffy.new_with_children(column, &[wide, narrow]).unwrap();
+        taffy.compute_layout(column, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(column).unwrap().size.width, 50.0);
+        assert_eq!(taffy.layout(wide).unwrap().location.x, 0.0);
+        assert_eq!(taffy.layout(narrow).unwrap().location.x, 30.0);
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod logical_alignment {
+    use taffy::geometry::{Point, Size};
+    use taffy::node::Taffy;
+    use taffy::style::*;
+
+    /// Lays out a 10x10 child, positioned as `position_type`, inside a 100x100 container with the given `style`,
+    /// and returns the location of the child
+    fn child_location(style: FlexboxLayout, position_type: PositionType) -> Point<f32> {
+        let mut taffy = Taffy::new();
+        let child = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    position_type,
+                    size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..style
+                },
+                &[child],
+            )
+            .unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        taffy.layout(child).unwrap().location
+    }
+
+    fn justified(flex_direction: FlexDirection, justify_content: JustifyContent) -> FlexboxLayout {
+        FlexboxLayout { flex_direction, justify_content, align_items: AlignItems::FlexStart, ..Default::default() }
+    }
+
+    #[test]
+    fn justify_start_ignores_reversed_directions() {
+        let style = justified(FlexDirection::RowReverse, JustifyContent::FlexStart);
+        assert_eq!(child_location(style, PositionType::Relative), Point { x: 90.0, y: 0.0 });
+
+        let style = justified(FlexDirection::RowReverse, JustifyContent::Start);
+        assert_eq!(child_location(style, PositionType::Relative), Point { x: 0.0, y: 0.0 });
+
+        let style = justified(FlexDirection::ColumnReverse, JustifyContent::Start);
+        assert_eq!(child_location(style, PositionType::Relative), Point { x: 0.0, y: 0.0 });
+    }
+
+    #[test]
+    fn justify_end_ignores_reversed_directions() {
+        let style = justified(FlexDirection::RowReverse, JustifyContent::End);
+        assert_eq!(child_location(style, PositionType::Relative), Point { x: 90.0, y: 0.0 });
+
+        let style = justified(FlexDirection::Row, JustifyContent::End);
+        assert_eq!(child_location(style, PositionType::Relative), Point { x: 90.0, y: 0.0 });
+    }
+
+    #[test]
+    fn justify_start_and_end_of_absolute_children() {
+        let style = justified(FlexDirection::Row, JustifyContent::End);
+        assert_eq!(child_location(style, PositionType::Absolute), Point { x: 90.0, y: 0.0 });
+
+        let style = justified(FlexDirection::RowReverse, JustifyContent::Start);
+        assert_eq!(child_location(style, PositionType::Absolute), Point { x: 0.0, y: 0.0 });
+    }
+
+    #[test]
+    fn align_start_and_end_ignore_wrap_reverse() {
+        let aligned =
+            |align_items| FlexboxLayout { flex_wrap: FlexWrap::WrapReverse, align_items, ..Default::default() };
+
+        assert_eq!(child_location(aligned(AlignItems::FlexStart), PositionType::Relative), Point { x: 0.0, y: 90.0 });
+        assert_eq!(child_location(aligned(AlignItems::Start), PositionType::Relative), Point { x: 0.0, y: 0.0 });
+        assert_eq!(child_location(aligned(AlignItems::End), PositionType::Relative), Point { x: 0.0, y: 90.0 });
+
+        let style = FlexboxLayout { align_items: AlignItems::End, ..Default::default() };
+        assert_eq!(child_location(style, PositionType::Relative), Point { x: 0.0, y: 90.0 });
+    }
+
+    #[test]
+    fn justify_start_and_end_follow_the_direction() {
+        let rtl = |flex_direction, justify_content| FlexboxLayout {
+            direction: Direction::Rtl,
+            ..justified(flex_direction, justify_content)
+        };
+
+        assert_eq!(
+            child_location(rtl(FlexDirection::Row, JustifyContent::Start), PositionType::Relative),
+            Point { x: 90.0, y: 0.0 }
+        );
+        assert_eq!(
+            child_location(rtl(FlexDirection::Row, JustifyContent::End), PositionType::Relative),
+            Point { x: 0.0, y: 0.0 }
+        );
+        assert_eq!(
+            child_location(rtl(FlexDirection::RowReverse, JustifyContent::Start), PositionType::Relative),
+            Point { x: 90.0, y: 0.0 }
+        );
+        assert_eq!(
+            child_location(rtl(FlexDirection::Row, JustifyContent::Start), PositionType::Absolute),
+            Point { x: 90.0, y: 0.0 }
+        );
+
+        // The values relative to the flex lines, and the start of a column, are unchanged
+        assert_eq!(
+            child_location(rtl(FlexDirection::Row, JustifyContent::FlexStart), PositionType::Relative),
+            Point { x: 0.0, y: 0.0 }
+        );
+        assert_eq!(
+            child_location(rtl(FlexDirection::Column, JustifyContent::Start), PositionType::Relative),
+            Point { x: 0.0, y: 0.0 }
+        );
+    }
+
+    #[test]
+    fn align_start_and_end_follow_the_direction() {
+        let aligned = |align_items| FlexboxLayout {
+            flex_direction: FlexDirection::Column,
+            direction: Direction::Rtl,
+            align_items,
+            ..Default::default()
+        };
+
+        assert_eq!(child_location(aligned(AlignItems::Start), PositionType::Relative), Point { x: 90.0, y: 0.0 });
+        assert_eq!(child_location(aligned(AlignItems::End), PositionType::Relative), Point { x: 0.0, y: 0.0 });
+        assert_eq!(child_location(aligned(AlignItems::FlexStart), PositionType::Relative), Point { x: 0.0, y: 0.0 });
+        assert_eq!(child_location(aligned(AlignItems::Start), PositionType::Absolute), Point { x: 90.0, y: 0.0 });
+
+        // The cross axis of a row is not the inline axis
+        let style = FlexboxLayout { direction: Direction::Rtl, align_items: AlignItems::Start, ..Default::default() };
+        assert_eq!(child_location(style, PositionType::Relative), Point { x: 0.0, y: 0.0 });
+    }
+}
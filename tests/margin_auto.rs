@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod margin_auto {
+    use taffy::geometry::{Rect, Size};
+    use taffy::style::{AlignSelf, Dimension, FlexboxLayout};
+
+    /// Lays out a single 50x50 child with auto top and bottom margins inside a 200x200 row container
+    fn vertically_centered_child(align_self: AlignSelf, height: Dimension) -> taffy::layout::Layout {
+        let mut taffy = taffy::Taffy::new();
+        let child = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    align_self,
+                    size: Size { width: Dimension::Points(50.0), height },
+                    margin: Rect { top: Dimension::Auto, bottom: Dimension::Auto, ..Default::default() },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(200.0), height: Dimension::Points(200.0) },
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        *taffy.layout(child).unwrap()
+    }
+
+    #[test]
+    fn auto_cross_margins_center_item() {
+        let layout = vertically_centered_child(AlignSelf::Auto, Dimension::Points(50.0));
+
+        assert_eq!(layout.location.y, 75.0);
+        assert_eq!(layout.size.height, 50.0);
+    }
+
+    #[test]
+    fn auto_cross_margins_take_precedence_over_align_self() {
+        for align_self in [AlignSelf::FlexStart, AlignSelf::FlexEnd, AlignSelf::Center, AlignSelf::Baseline] {
+            let layout = vertically_centered_child(align_self, Dimension::Points(50.0));
+
+            assert_eq!(layout.location.y, 75.0, "align_self: {:?}", align_self);
+        }
+    }
+
+    #[test]
+    fn auto_cross_margins_prevent_stretch() {
+        let layout = vertically_centered_child(AlignSelf::Stretch, Dimension::Auto);
+
+        assert_eq!(layout.size.height, 0.0);
+        assert_eq!(layout.location.y, 100.0);
+    }
+}
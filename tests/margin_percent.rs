@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod margin_percent {
+    use taffy::geometry::{Point, Rect, Size};
+    use taffy::style::{Dimension, FlexboxLayout, PositionType};
+
+    /// Lays out a child with `margin.top` of 10% inside a 200x400 container, and returns its location
+    fn child_location(position_type: PositionType) -> Point<f32> {
+        let mut taffy = taffy::Taffy::new();
+        let child = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    position_type,
+                    size: Size { width: Dimension::Points(50.0), height: Dimension::Points(50.0) },
+                    margin: Rect { top: Dimension::Percent(0.1), ..Default::default() },
+                    position: Rect { top: Dimension::Points(0.0), ..Default::default() },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(200.0), height: Dimension::Points(400.0) },
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        taffy.layout(child).unwrap().location
+    }
+
+    #[test]
+    fn vertical_margin_resolves_against_width() {
+        assert_eq!(child_location(PositionType::Relative).y, 20.0);
+    }
+
+    #[test]
+    fn vertical_margin_of_absolute_child_resolves_against_width() {
+        assert_eq!(child_location(PositionType::Absolute).y, 20.0);
+    }
+}
@@ -490,7 +490,15 @@ mod measure {
         let mut taffy = taffy::node::Taffy::new();
         let child = taffy
             .new_leaf(
-                taffy::style::FlexboxLayout { flex_grow: 1.0, ..Default::default() },
+                taffy::style::FlexboxLayout {
+                    flex_grow: 1.0,
+                    // Opt out of the automatic minimum size, which would keep the child at its measured width
+                    min_size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Undefined,
+                        height: taffy::style::Dimension::Undefined,
+                    },
+                    ..Default::default()
+                },
                 MeasureFunc::Raw(|_| taffy::geometry::Size { width: 200.0, height: 200.0 }),
             )
             .unwrap();
@@ -542,4 +550,576 @@ mod measure {
 
         assert_eq!(NUM_MEASURES.load(atomic::Ordering::Relaxed), 2);
     }
+
+    #[test]
+    fn measure_fixed() {
+        let mut taffy = taffy::node::Taffy::new();
+        let child = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout { ..Default::default() },
+                MeasureFunc::fixed(taffy::geometry::Size { width: 100.0, height: 50.0 }),
+            )
+            .unwrap();
+
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(300.0),
+                        height: taffy::style::Dimension::Points(300.0),
+                    },
+                    align_items: taffy::style::AlignItems::FlexStart,
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(child).unwrap().size.width, 100.0);
+        assert_eq!(taffy.layout(child).unwrap().size.height, 50.0);
+    }
+
+    #[test]
+    fn negative_measure_is_clamped() {
+        let mut taffy = taffy::node::Taffy::new();
+        let node = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout { ..Default::default() },
+                MeasureFunc::fixed(taffy::geometry::Size { width: -100.0, height: -50.0 }),
+            )
+            .unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(node).unwrap().size.width, 0.0);
+        assert_eq!(taffy.layout(node).unwrap().size.height, 0.0);
+    }
+
+    #[test]
+    fn negative_measure_absolute_child_is_clamped() {
+        let mut taffy = taffy::node::Taffy::new();
+        let child = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout {
+                    position_type: taffy::style::PositionType::Absolute,
+                    ..Default::default()
+                },
+                MeasureFunc::fixed(taffy::geometry::Size { width: -100.0, height: -50.0 }),
+            )
+            .unwrap();
+
+        let node = taffy.new_with_children(taffy::style::FlexboxLayout { ..Default::default() }, &[child]).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(child).unwrap().size.width, 0.0);
+        assert_eq!(taffy.layout(child).unwrap().size.height, 0.0);
+    }
+
+    /// Lays out a 1600x900 image leaf with the given `aspect_ratio` style inside a 500x90 row, and returns its size
+    fn image_in_row(aspect_ratio: Option<f32>) -> taffy::geometry::Size<f32> {
+        let mut taffy = taffy::node::Taffy::new();
+        let image = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout { aspect_ratio, ..Default::default() },
+                MeasureFunc::fixed(taffy::geometry::Size { width: 1600.0, height: 900.0 }),
+            )
+            .unwrap();
+
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(500.0),
+                        height: taffy::style::Dimension::Points(90.0),
+                    },
+                    ..Default::default()
+                },
+                &[image],
+            )
+            .unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        taffy.layout(image).unwrap().size
+    }
+
+    #[test]
+    fn measured_aspect_ratio() {
+        assert_eq!(image_in_row(None), taffy::geometry::Size { width: 160.0, height: 90.0 });
+    }
+
+    #[test]
+    fn style_aspect_ratio_overrides_measured_aspect_ratio() {
+        assert_eq!(image_in_row(Some(2.0)), taffy::geometry::Size { width: 180.0, height: 90.0 });
+    }
+
+    #[test]
+    fn measured_aspect_ratio_in_column() {
+        let mut taffy = taffy::node::Taffy::new();
+        let image = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout { ..Default::default() },
+                MeasureFunc::fixed(taffy::geometry::Size { width: 1600.0, height: 900.0 }),
+            )
+            .unwrap();
+
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    flex_direction: taffy::style::FlexDirection::Column,
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(160.0),
+                        height: taffy::style::Dimension::Points(500.0),
+                    },
+                    ..Default::default()
+                },
+                &[image],
+            )
+            .unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(image).unwrap().size, taffy::geometry::Size { width: 160.0, height: 90.0 });
+    }
+
+    #[test]
+    fn reported_aspect_ratio() {
+        let mut taffy = taffy::node::Taffy::new();
+        // A 16:9 image that can be scaled to any size, which measures as 1600x900 when unconstrained
+        let image = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout::default(),
+                MeasureFunc::Raw(|constraint| taffy::geometry::Size {
+                    width: constraint.width.unwrap_or(1600.0),
+                    height: constraint.height.unwrap_or(900.0),
+                }),
+            )
+            .unwrap();
+        taffy.set_intrinsic_aspect_ratio(image, Some(16.0 / 9.0)).unwrap();
+        assert!(taffy.set_intrinsic_aspect_ratio(image, Some(f32::NAN)).is_err());
+
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(500.0),
+                        height: taffy::style::Dimension::Points(90.0),
+                    },
+                    ..Default::default()
+                },
+                &[image],
+            )
+            .unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(image).unwrap().size, taffy::geometry::Size { width: 160.0, height: 90.0 });
+
+        // The style takes precedence over the reported ratio
+        taffy.set_style(image, taffy::style::FlexboxLayout { aspect_ratio: Some(2.0), ..Default::default() }).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(image).unwrap().size, taffy::geometry::Size { width: 180.0, height: 90.0 });
+
+        // Without a ratio, the image is as wide as it measures, shrunk to fit the row
+        taffy.set_style(image, taffy::style::FlexboxLayout::default()).unwrap();
+        taffy.set_intrinsic_aspect_ratio(image, None).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(image).unwrap().size, taffy::geometry::Size { width: 500.0, height: 90.0 });
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn shared_measure_func() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let measure: Arc<dyn taffy::node::Measurable> =
+            Arc::new(move |constraint: taffy::geometry::Size<taffy::geometry::AvailableSpace>| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                taffy::geometry::Size { width: constraint.width.unwrap_or(20.0), height: 10.0 }
+            });
+
+        let mut taffy = taffy::node::Taffy::new();
+        let children: Vec<_> =
+            (0..3).map(|_| taffy.new_leaf(Default::default(), MeasureFunc::Shared(measure.clone())).unwrap()).collect();
+        let node = taffy.new_with_children(Default::default(), &children).unwrap();
+        assert_eq!(Arc::strong_count(&measure), 4);
+
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(node).unwrap().size, taffy::geometry::Size { width: 60.0, height: 10.0 });
+        for child in children {
+            assert_eq!(taffy.layout(child).unwrap().size, taffy::geometry::Size { width: 20.0, height: 10.0 });
+        }
+        assert!(calls.load(Ordering::Relaxed) >= 3);
+    }
+
+    #[test]
+    fn measured_size() {
+        let mut taffy = taffy::node::Taffy::new();
+        let child = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout { flex_grow: 1.0, ..Default::default() },
+                MeasureFunc::Raw(|_| taffy::geometry::Size { width: 100.0, height: 50.0 }),
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(300.0),
+                        height: taffy::style::Dimension::Points(80.0),
+                    },
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+        assert_eq!(taffy.measured_size(child).unwrap(), None);
+
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(child).unwrap().size, taffy::geometry::Size { width: 300.0, height: 80.0 });
+        assert_eq!(taffy.measured_size(child).unwrap(), Some(taffy::geometry::Size { width: 100.0, height: 50.0 }));
+        assert_eq!(taffy.measured_size(node).unwrap(), None);
+
+        taffy.set_measure(child, None).unwrap();
+        assert_eq!(taffy.measured_size(child).unwrap(), None);
+    }
+
+    /// A paragraph whose longest word is 40 wide, which is 120 wide on a single line, with 10 high lines
+    fn paragraph(constraint: taffy::geometry::Size<taffy::geometry::AvailableSpace>) -> taffy::geometry::Size<f32> {
+        let width = constraint.width.unwrap_or(120.0).clamp(40.0, 120.0);
+        taffy::geometry::Size { width, height: (120.0 / width).ceil() * 10.0 }
+    }
+
+    /// Lays out a `paragraph` with the given `min_width` next to an unshrinkable 80 wide sibling in a 100 wide row,
+    /// and returns the width of the paragraph, which is `height` high
+    fn paragraph_width(
+        min_width: taffy::style::Dimension,
+        width: taffy::style::Dimension,
+        height: taffy::style::Dimension,
+    ) -> f32 {
+        let mut taffy = taffy::node::Taffy::new();
+        let text = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout {
+                    min_size: taffy::geometry::Size { width: min_width, height: taffy::style::Dimension::Undefined },
+                    size: taffy::geometry::Size { width, height },
+                    ..Default::default()
+                },
+                MeasureFunc::Raw(paragraph),
+            )
+            .unwrap();
+        let sibling = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    flex_shrink: 0.0,
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(80.0),
+                        height: taffy::style::Dimension::Points(10.0),
+                    },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(100.0),
+                        height: taffy::style::Dimension::Auto,
+                    },
+                    ..Default::default()
+                },
+                &[text, sibling],
+            )
+            .unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        taffy.layout(text).unwrap().size.width
+    }
+
+    #[test]
+    fn automatic_min_size_stops_at_min_content() {
+        use taffy::style::Dimension::Auto;
+        assert_eq!(paragraph_width(Auto, Auto, Auto), 40.0);
+    }
+
+    #[test]
+    fn undefined_min_size_shrinks_past_min_content() {
+        use taffy::style::Dimension::{Auto, Undefined};
+        assert_eq!(paragraph_width(Undefined, Auto, Auto), 20.0);
+    }
+
+    #[test]
+    fn automatic_min_size_is_clamped_by_size() {
+        use taffy::style::Dimension::{Auto, Points};
+        assert_eq!(paragraph_width(Auto, Points(30.0), Auto), 30.0);
+    }
+
+    #[test]
+    fn automatic_min_size_of_fixed_height_text() {
+        // Knowing the height of the text must not keep it from being measured for its min-content width
+        use taffy::style::Dimension::{Auto, Points};
+        assert_eq!(paragraph_width(Auto, Auto, Points(20.0)), 40.0);
+    }
+
+    #[test]
+    fn measure_fallback_sizes_unmeasured_leaves() {
+        let mut taffy = taffy::node::Taffy::new();
+        let unmeasured = taffy.new_with_children(taffy::style::FlexboxLayout::default(), &[]).unwrap();
+        let measured = taffy
+            .new_leaf(
+                taffy::style::FlexboxLayout::default(),
+                MeasureFunc::fixed(taffy::geometry::Size { width: 30.0, height: 30.0 }),
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    flex_direction: taffy::style::FlexDirection::Column,
+                    align_items: taffy::style::AlignItems::FlexStart,
+                    ..Default::default()
+                },
+                &[unmeasured, measured],
+            )
+            .unwrap();
+
+        taffy.set_measure_fallback(Some(MeasureFunc::fixed(taffy::geometry::Size { width: 10.0, height: 20.0 })));
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(unmeasured).unwrap().size, taffy::geometry::Size { width: 10.0, height: 20.0 });
+        assert_eq!(taffy.layout(measured).unwrap().size, taffy::geometry::Size { width: 30.0, height: 30.0 });
+        assert_eq!(taffy.layout(node).unwrap().size, taffy::geometry::Size { width: 30.0, height: 50.0 });
+
+        taffy.set_measure_fallback(None);
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(unmeasured).unwrap().size, taffy::geometry::Size { width: 0.0, height: 0.0 });
+    }
+
+    /// Lays out a leaf measured by `measure` inside a row of the given `width`, using the cache `mode`
+    fn leaf_in_row(
+        measure: fn(taffy::geometry::Size<taffy::geometry::AvailableSpace>) -> taffy::geometry::Size<f32>,
+        mode: taffy::node::CacheMode,
+        width: f32,
+    ) -> (taffy::node::Taffy, taffy::node::Node, taffy::node::Node) {
+        let mut taffy = taffy::node::Taffy::new();
+        let leaf = taffy.new_leaf(taffy::style::FlexboxLayout::default(), MeasureFunc::Raw(measure)).unwrap();
+        taffy.set_measure_cache_mode(leaf, mode).unwrap();
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(width),
+                        height: taffy::style::Dimension::Auto,
+                    },
+                    ..Default::default()
+                },
+                &[leaf],
+            )
+            .unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        (taffy, node, leaf)
+    }
+
+    #[test]
+    fn once_permanent_measures_only_once() {
+        use std::sync::atomic;
+        static NUM_MEASURES: atomic::AtomicU32 = atomic::AtomicU32::new(0);
+
+        let (mut taffy, node, leaf) = leaf_in_row(
+            |constraint| {
+                NUM_MEASURES.fetch_add(1, atomic::Ordering::Relaxed);
+                taffy::geometry::Size { width: constraint.width.unwrap_or(100.0).min(100.0), height: 10.0 }
+            },
+            taffy::node::CacheMode::OncePermanent,
+            200.0,
+        );
+        let measures = NUM_MEASURES.load(atomic::Ordering::Relaxed);
+        assert_eq!(measures, 1);
+
+        // The narrower parent is ignored, as the first result is kept
+        let mut style = *taffy.style(node).unwrap();
+        style.size.width = taffy::style::Dimension::Points(50.0);
+        taffy.set_style(node, style).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(NUM_MEASURES.load(atomic::Ordering::Relaxed), 1);
+        assert_eq!(taffy.layout(leaf).unwrap().size.height, 10.0);
+
+        taffy.mark_dirty(leaf).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert!(NUM_MEASURES.load(atomic::Ordering::Relaxed) > 1);
+    }
+
+    #[test]
+    fn never_cache_measures_every_time() {
+        use std::sync::atomic;
+        static NUM_MEASURES: atomic::AtomicU32 = atomic::AtomicU32::new(0);
+
+        let (mut taffy, node, _) = leaf_in_row(
+            |_| {
+                NUM_MEASURES.fetch_add(1, atomic::Ordering::Relaxed);
+                taffy::geometry::Size { width: 10.0, height: 10.0 }
+            },
+            taffy::node::CacheMode::Never,
+            200.0,
+        );
+        let measures = NUM_MEASURES.load(atomic::Ordering::Relaxed);
+
+        // Only the parent is dirty, so the leaf would normally be served from its cache
+        let style = taffy::style::FlexboxLayout { flex_grow: 1.0, ..*taffy.style(node).unwrap() };
+        taffy.set_style(node, style).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert!(NUM_MEASURES.load(atomic::Ordering::Relaxed) > measures);
+    }
+
+    #[test]
+    fn flex_basis_content_ignores_size() {
+        let mut taffy = taffy::node::Taffy::new();
+        let style = |flex_basis| taffy::style::FlexboxLayout {
+            size: taffy::geometry::Size { width: taffy::style::Dimension::Points(200.0), ..Default::default() },
+            flex_basis,
+            ..Default::default()
+        };
+        let measure = || {
+            taffy::node::MeasureFunc::Raw(|known_dimensions| taffy::geometry::Size {
+                width: known_dimensions.width.unwrap_or(50.0),
+                height: known_dimensions.height.unwrap_or(10.0),
+            })
+        };
+        let content = taffy.new_leaf(style(taffy::style::Dimension::Content), measure()).unwrap();
+        let auto = taffy.new_leaf(style(taffy::style::Dimension::Auto), measure()).unwrap();
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size { width: taffy::style::Dimension::Points(500.0), ..Default::default() },
+                    ..Default::default()
+                },
+                &[content, auto],
+            )
+            .unwrap();
+
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(content).unwrap().size.width, 50.0);
+        assert_eq!(taffy.layout(auto).unwrap().size.width, 200.0);
+        assert_eq!(taffy.layout(auto).unwrap().location.x, 50.0);
+    }
+
+    #[test]
+    fn bumping_measure_version_measures_again() {
+        use std::sync::atomic;
+        static LINE_HEIGHT: atomic::AtomicU32 = atomic::AtomicU32::new(10);
+
+        let (mut taffy, node, leaf) = leaf_in_row(
+            |_| taffy::geometry::Size { width: 10.0, height: LINE_HEIGHT.load(atomic::Ordering::Relaxed) as f32 },
+            taffy::node::CacheMode::Always,
+            200.0,
+        );
+        assert_eq!(taffy.layout(leaf).unwrap().size.height, 10.0);
+
+        // The measure function is unchanged, so the cached size is still used
+        LINE_HEIGHT.store(20, atomic::Ordering::Relaxed);
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(leaf).unwrap().size.height, 10.0);
+
+        taffy.bump_measure_version(leaf).unwrap();
+        assert!(taffy.dirty(node).unwrap());
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(leaf).unwrap().size.height, 20.0);
+        assert_eq!(taffy.layout(node).unwrap().size.height, 20.0);
+    }
+
+    #[test]
+    fn measure_snapping_keeps_text_on_one_line() {
+        /// Text that is 33.4 wide on one line, and wraps onto two lines when given less width
+        fn text(known: taffy::geometry::Size<taffy::geometry::AvailableSpace>) -> taffy::geometry::Size<f32> {
+            match known.width {
+                taffy::geometry::AvailableSpace::Definite(width) if width < 33.4 => {
+                    taffy::geometry::Size { width: 20.0, height: 20.0 }
+                }
+                _ => taffy::geometry::Size { width: 33.4, height: 10.0 },
+            }
+        }
+
+        // The rounded layout is narrower than the text, so painting the text within it wraps it
+        let (mut taffy, node, leaf) = leaf_in_row(text, taffy::node::CacheMode::Always, 200.0);
+        let width = taffy.layout(leaf).unwrap().size.width;
+        assert_eq!(width, 33.0);
+        assert_eq!(
+            text(taffy::geometry::Size {
+                width: taffy::geometry::AvailableSpace::Definite(width),
+                height: taffy::geometry::AvailableSpace::MaxContent,
+            })
+            .height,
+            20.0
+        );
+
+        taffy.set_measure_snapping(true);
+        assert!(taffy.dirty(node).unwrap());
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        let width = taffy.layout(leaf).unwrap().size.width;
+        assert_eq!(width, 34.0);
+        assert_eq!(
+            text(taffy::geometry::Size {
+                width: taffy::geometry::AvailableSpace::Definite(width),
+                height: taffy::geometry::AvailableSpace::MaxContent,
+            })
+            .height,
+            10.0
+        );
+
+        // Snapping follows the physical pixel grid
+        taffy.set_scale_factor(2.0);
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(leaf).unwrap().size.width, 33.5);
+    }
+
+    #[test]
+    fn size_hint_replaces_unconstrained_measure() {
+        use std::sync::atomic;
+        static UNCONSTRAINED_MEASURES: atomic::AtomicU32 = atomic::AtomicU32::new(0);
+
+        fn text(known: taffy::geometry::Size<taffy::geometry::AvailableSpace>) -> taffy::geometry::Size<f32> {
+            if known.width.is_intrinsic() && known.height.is_intrinsic() {
+                UNCONSTRAINED_MEASURES.fetch_add(1, atomic::Ordering::SeqCst);
+            }
+            taffy::geometry::Size { width: known.width.unwrap_or(50.0), height: known.height.unwrap_or(20.0) }
+        }
+
+        let (mut taffy, node, leaf) = leaf_in_row(text, taffy::node::CacheMode::Always, 200.0);
+        let unhinted = *taffy.layout(leaf).unwrap();
+        assert!(UNCONSTRAINED_MEASURES.load(atomic::Ordering::SeqCst) > 0);
+
+        taffy.set_size_hint(leaf, taffy::geometry::Size { width: 50.0, height: 20.0 }).unwrap();
+        assert!(taffy.dirty(node).unwrap());
+        UNCONSTRAINED_MEASURES.store(0, atomic::Ordering::SeqCst);
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+
+        // An accurate hint leaves the layout as it was
+        assert_eq!(UNCONSTRAINED_MEASURES.load(atomic::Ordering::SeqCst), 0);
+        assert_eq!(taffy.layout(leaf).unwrap().size, unhinted.size);
+        assert_eq!(taffy.layout(leaf).unwrap().location, unhinted.location);
+
+        taffy.clear_size_hint(leaf).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert!(UNCONSTRAINED_MEASURES.load(atomic::Ordering::SeqCst) > 0);
+    }
+
+    #[test]
+    fn last_measure_count() {
+        let (mut taffy, node, leaf) = leaf_in_row(
+            |known| taffy::geometry::Size { width: known.width.unwrap_or(50.0), height: known.height.unwrap_or(20.0) },
+            taffy::node::CacheMode::Always,
+            200.0,
+        );
+        assert!(taffy.last_measure_count() > 0);
+
+        // Every measurement is reused from the cache
+        taffy.mark_dirty(node).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.last_measure_count(), 0);
+
+        taffy.mark_dirty(leaf).unwrap();
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert!(taffy.last_measure_count() > 0);
+    }
 }
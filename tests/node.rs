@@ -97,6 +97,60 @@ mod node {
         assert_eq!(taffy.children(node).unwrap()[0], child2);
     }
 
+    #[test]
+    fn swap_children() {
+        let mut taffy = Taffy::new();
+        let children: Vec<_> =
+            (0..3).map(|_| taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap()).collect();
+        let node = taffy.new_with_children(FlexboxLayout::default(), &children).unwrap();
+
+        taffy.swap_children(node, 0, 2).unwrap();
+        assert_eq!(taffy.children(node).unwrap()[..], [children[2], children[1], children[0]]);
+        assert!(taffy.dirty(node).unwrap());
+
+        taffy.swap_children(node, 1, 1).unwrap();
+        assert_eq!(taffy.children(node).unwrap()[..], [children[2], children[1], children[0]]);
+        assert!(taffy.swap_children(node, 0, 3).is_err());
+    }
+
+    #[test]
+    fn children_are_ordered_consistently() {
+        let mut taffy = Taffy::new();
+        let leaf = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        let mut children: Vec<_> = (0..3).map(|_| taffy.new_with_children(leaf, &[]).unwrap()).collect();
+        let absolute =
+            taffy.new_with_children(FlexboxLayout { position_type: PositionType::Absolute, ..leaf }, &[]).unwrap();
+        let node = taffy.new_with_children(FlexboxLayout::default(), &children).unwrap();
+        taffy.add_child(node, absolute).unwrap();
+        children.push(absolute);
+        taffy.swap_children(node, 0, 3).unwrap();
+        children.swap(0, 3);
+        taffy.swap_children(node, 1, 2).unwrap();
+        children.swap(1, 2);
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.children(node).unwrap()[..], children[..]);
+        assert_eq!(taffy.children_iter(node).unwrap().collect::<Vec<_>>(), children);
+        let mut visited = Vec::new();
+        taffy.traverse_bfs(node, |child, _| visited.push(child)).unwrap();
+        assert_eq!(visited[1..], children[..]);
+        let mut visited = Vec::new();
+        taffy.traverse_postorder(node, |child| visited.push(child)).unwrap();
+        assert_eq!(visited[..children.len()], children[..]);
+        for (index, child) in taffy.children_enumerated(node).unwrap() {
+            assert_eq!(child, children[index]);
+            assert_eq!(taffy.child_at_index(node, index).unwrap(), child);
+            assert_eq!(taffy.layout(child).unwrap().order, index as u32);
+        }
+
+        // Items in the flow are placed in the same order
+        let x = |index: usize| taffy.layout(children[index]).unwrap().location.x;
+        assert!(x(1) < x(2) && x(2) < x(3));
+    }
+
     #[test]
     fn remove() {
         let mut taffy = Taffy::new();
@@ -113,6 +167,7 @@ mod node {
         // Disconnect the tree: <0> <2>
         taffy.remove(node1).unwrap();
 
+        #[cfg(not(feature = "debug-checks"))]
         assert!(taffy.style(node1).is_err());
 
         assert!(taffy.children(node0).unwrap().is_empty());
@@ -189,4 +244,1559 @@ mod node {
         taffy.remove(child).unwrap();
         taffy.remove(parent).unwrap();
     }
+
+    #[test]
+    fn compute_all() {
+        let mut taffy = Taffy::new();
+
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Percent(1.0), height: Dimension::Percent(1.0) },
+            ..FlexboxLayout::default()
+        };
+        let root1 = taffy.new_with_children(style, &[]).unwrap();
+        let root2 = taffy.new_with_children(style, &[]).unwrap();
+
+        taffy
+            .compute_all(&[
+                (root1, Size { width: Some(100.0), height: Some(50.0) }),
+                (root2, Size { width: Some(200.0), height: Some(80.0) }),
+            ])
+            .unwrap();
+
+        assert_eq!(taffy.layout(root1).unwrap().size, Size { width: 100.0, height: 50.0 });
+        assert_eq!(taffy.layout(root2).unwrap().size, Size { width: 200.0, height: 80.0 });
+    }
+
+    #[test]
+    fn compute_all_validates_before_computing() {
+        let mut taffy = Taffy::new();
+
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let removed = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.remove(removed).unwrap();
+
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.compute_all(&[(root, Size::undefined()), (removed, Size::undefined())]).is_err());
+        assert!(taffy.dirty(root).unwrap());
+    }
+
+    #[test]
+    fn node_count_and_capacity() {
+        let mut taffy = Taffy::with_capacity(4);
+        assert_eq!(taffy.node_count(), 0);
+        assert!(taffy.capacity() >= 4);
+
+        let nodes: Vec<_> = (0..6).map(|_| taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap()).collect();
+        assert_eq!(taffy.node_count(), 6);
+        let capacity = taffy.capacity();
+        assert!(capacity >= 6);
+
+        taffy.remove(nodes[0]).unwrap();
+        assert_eq!(taffy.node_count(), 5);
+        assert_eq!(taffy.capacity(), capacity);
+    }
+
+    #[test]
+    fn layout_absolute_offset() {
+        let mut taffy = Taffy::new();
+
+        let leaf_style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+            ..FlexboxLayout::default()
+        };
+        let spacer = taffy.new_with_children(leaf_style, &[]).unwrap();
+        let leaf = taffy.new_with_children(leaf_style, &[]).unwrap();
+        let inner = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    padding: Rect { start: Dimension::Points(5.0), ..Default::default() },
+                    ..Default::default()
+                },
+                &[leaf],
+            )
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[spacer, inner]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let layout = taffy.layout_absolute_offset(leaf, Point { x: 100.0, y: 50.0 }).unwrap();
+        assert_eq!(layout.location, Point { x: 115.0, y: 50.0 });
+        assert_eq!(layout.size, Size { width: 10.0, height: 10.0 });
+
+        let layout = taffy.layout_absolute_offset(root, Point { x: 100.0, y: 50.0 }).unwrap();
+        assert_eq!(layout.location, Point { x: 100.0, y: 50.0 });
+    }
+
+    #[test]
+    fn layout_relative_to() {
+        let mut taffy = Taffy::new();
+
+        let leaf_style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+            ..FlexboxLayout::default()
+        };
+        let spacer = taffy.new_with_children(leaf_style, &[]).unwrap();
+        let leaf = taffy.new_with_children(leaf_style, &[]).unwrap();
+        let inner = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    padding: Rect { start: Dimension::Points(5.0), ..Default::default() },
+                    ..Default::default()
+                },
+                &[leaf],
+            )
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[spacer, inner]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let layout = taffy.layout_relative_to(leaf, inner).unwrap();
+        assert_eq!(layout.location, Point { x: 5.0, y: 0.0 });
+        assert_eq!(layout.size, Size { width: 10.0, height: 10.0 });
+        assert_eq!(taffy.layout_relative_to(leaf, root).unwrap().location, Point { x: 15.0, y: 0.0 });
+        assert_eq!(taffy.layout_relative_to(leaf, leaf).unwrap().location, Point::zero());
+
+        assert!(matches!(
+            taffy.layout_relative_to(leaf, spacer),
+            Err(taffy::error::InvalidChild::NotAnAncestor { node, ancestor }) if node == leaf && ancestor == spacer
+        ));
+        assert!(matches!(taffy.layout_relative_to(root, leaf), Err(taffy::error::InvalidChild::NotAnAncestor { .. })));
+    }
+
+    #[test]
+    fn find() {
+        let mut taffy = Taffy::new();
+
+        let tagged = FlexboxLayout { flex_grow: 1.0, ..FlexboxLayout::default() };
+        let grandchild = taffy.new_with_children(tagged, &[]).unwrap();
+        let child1 = taffy.new_with_children(FlexboxLayout::default(), &[grandchild]).unwrap();
+        let child2 = taffy.new_with_children(tagged, &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+
+        assert_eq!(taffy.find(root, |_, style| style.flex_grow == 1.0).unwrap(), Some(grandchild));
+        assert_eq!(taffy.find(root, |node, _| node == root).unwrap(), Some(root));
+        assert_eq!(taffy.find(child2, |node, _| node == grandchild).unwrap(), None);
+
+        // A cycle is only followed once, so a search that matches nothing still ends
+        taffy.add_child(grandchild, root).unwrap();
+        assert_eq!(taffy.find(root, |_, style| style.flex_grow == 2.0).unwrap(), None);
+        assert_eq!(taffy.find(grandchild, |node, _| node == child2).unwrap(), Some(child2));
+        taffy.remove_child(grandchild, root).unwrap();
+
+        taffy.remove(child2).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.find(child2, |_, _| true).is_err());
+    }
+
+    #[test]
+    fn child_counts() {
+        let mut taffy = Taffy::new();
+
+        let child1 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child2 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child3 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent1 = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+        let parent2 = taffy.new_with_children(FlexboxLayout::default(), &[child3]).unwrap();
+
+        assert_eq!(taffy.child_counts(&[parent1, parent2, child1]).unwrap().as_slice(), &[2, 1, 0]);
+        assert!(taffy.child_counts(&[]).unwrap().is_empty());
+
+        taffy.remove(child2).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.child_counts(&[parent1, child2]).is_err());
+    }
+
+    #[test]
+    fn is_leaf() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+
+        assert!(taffy.is_leaf(child).unwrap());
+        assert!(!taffy.is_leaf(parent).unwrap());
+
+        taffy.remove_child(parent, child).unwrap();
+        assert!(taffy.is_leaf(parent).unwrap());
+
+        taffy.remove(child).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.is_leaf(child).is_err());
+    }
+
+    #[test]
+    fn structurally_changed_nodes() {
+        let mut taffy = Taffy::new();
+        let first = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let second = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[first]).unwrap();
+        let other = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        assert!(taffy.structurally_changed_nodes().is_empty());
+
+        taffy.add_child(parent, second).unwrap();
+        taffy.set_style(other, FlexboxLayout { flex_grow: 1.0, ..Default::default() }).unwrap();
+        assert_eq!(taffy.structurally_changed_nodes()[..], [parent]);
+        assert!(taffy.structurally_changed_nodes().is_empty());
+
+        // Reconciling to the same children changes nothing
+        taffy.reconcile_children(parent, &[first, second]).unwrap();
+        assert!(taffy.structurally_changed_nodes().is_empty());
+        taffy.reconcile_children(parent, &[second, first]).unwrap();
+        assert_eq!(taffy.structurally_changed_nodes()[..], [parent]);
+
+        taffy.remove(first).unwrap();
+        taffy.replace_child_at_index(parent, 0, other).unwrap();
+        assert_eq!(taffy.structurally_changed_nodes()[..], [parent]);
+    }
+
+    #[test]
+    fn layout_with_children() {
+        let mut taffy = Taffy::new();
+
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(20.0) },
+            ..FlexboxLayout::default()
+        };
+        let child1 = taffy.new_with_children(style, &[]).unwrap();
+        let child2 = taffy.new_with_children(style, &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+        taffy.compute_layout(parent, Size::undefined()).unwrap();
+
+        let (layout, children) = taffy.layout_with_children(parent).unwrap();
+        assert_eq!(layout.size, Size { width: 20.0, height: 20.0 });
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].0, child1);
+        assert_eq!(children[0].1.location, Point { x: 0.0, y: 0.0 });
+        assert_eq!(children[1].0, child2);
+        assert_eq!(children[1].1.location, Point { x: 10.0, y: 0.0 });
+        assert_eq!(children[1].1.size, Size { width: 10.0, height: 20.0 });
+    }
+
+    #[test]
+    fn set_style_rejects_non_finite_values() {
+        let mut taffy = Taffy::new();
+        let node = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        let style = FlexboxLayout {
+            margin: Rect { top: Dimension::Points(f32::NAN), ..Default::default() },
+            ..FlexboxLayout::default()
+        };
+        assert!(matches!(
+            taffy.set_style(node, style),
+            Err(taffy::error::StyleError::NonFinite { field: "margin.top" })
+        ));
+
+        let style = FlexboxLayout { flex_grow: f32::INFINITY, ..FlexboxLayout::default() };
+        assert!(matches!(
+            taffy.set_style(node, style),
+            Err(taffy::error::StyleError::NonFinite { field: "flex_grow" })
+        ));
+
+        let style = FlexboxLayout { aspect_ratio: Some(f32::NAN), ..FlexboxLayout::default() };
+        assert!(taffy.set_style(node, style).is_err());
+
+        assert_eq!(taffy.style(node).unwrap(), &FlexboxLayout::default());
+        assert!(!taffy.dirty(node).unwrap());
+    }
+
+    #[test]
+    fn new_nodes_reject_non_finite_values() {
+        let mut taffy = Taffy::new();
+
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Percent(f32::NEG_INFINITY), height: Dimension::Auto },
+            ..FlexboxLayout::default()
+        };
+        assert!(matches!(
+            taffy.new_leaf(style, MeasureFunc::fixed(Size::zero())),
+            Err(taffy::error::StyleError::NonFinite { field: "size.width" })
+        ));
+        assert!(matches!(
+            taffy.new_with_children(style, &[]),
+            Err(taffy::error::StyleError::NonFinite { field: "size.width" })
+        ));
+        assert_eq!(taffy.node_count(), 0);
+    }
+
+    #[test]
+    fn children_enumerated() {
+        let mut taffy = Taffy::new();
+
+        let child1 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child2 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+
+        assert_eq!(taffy.children_enumerated(parent).unwrap().as_slice(), &[(0, child1), (1, child2)]);
+        assert!(taffy.children_enumerated(child1).unwrap().is_empty());
+
+        taffy.remove(child1).unwrap();
+        assert_eq!(taffy.children_enumerated(parent).unwrap().as_slice(), &[(0, child2)]);
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.children_enumerated(child1).is_err());
+    }
+
+    #[test]
+    fn compute_layout_damage() {
+        let mut taffy = Taffy::new();
+        let fixed = |width, height| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(height) },
+            ..Default::default()
+        };
+
+        let first = taffy.new_with_children(fixed(10.0, 10.0), &[]).unwrap();
+        let grandchild = taffy.new_with_children(fixed(5.0, 5.0), &[]).unwrap();
+        let second = taffy.new_with_children(fixed(20.0, 20.0), &[grandchild]).unwrap();
+        let root = taffy.new_with_children(fixed(100.0, 100.0), &[first, second]).unwrap();
+
+        let damage = taffy.compute_layout_damage(root, Size::undefined()).unwrap();
+        let nodes: Vec<_> = damage.iter().map(|(node, _)| *node).collect();
+        assert_eq!(nodes, vec![root, first, second, grandchild]);
+        assert_eq!(damage[3].1.location, Point { x: 10.0, y: 0.0 });
+
+        taffy.set_style(first, fixed(30.0, 10.0)).unwrap();
+        let damage = taffy.compute_layout_damage(root, Size::undefined()).unwrap();
+        let nodes: Vec<_> = damage.iter().map(|(node, _)| *node).collect();
+        assert_eq!(nodes, vec![first, second, grandchild]);
+        assert_eq!(damage[0].1.size, Size { width: 30.0, height: 10.0 });
+        assert_eq!(damage[2].1.location, Point { x: 30.0, y: 0.0 });
+        assert_eq!(taffy.layout(grandchild).unwrap().location, Point { x: 0.0, y: 0.0 });
+
+        assert!(taffy.compute_layout_damage(root, Size::undefined()).unwrap().is_empty());
+
+        taffy.remove(first).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.compute_layout_damage(first, Size::undefined()).is_err());
+    }
+
+    #[test]
+    fn is_laid_out() {
+        let mut taffy = Taffy::new();
+
+        let grandchild = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[grandchild]).unwrap();
+        let sibling = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child, sibling]).unwrap();
+
+        assert!(taffy.is_laid_out(grandchild).unwrap());
+
+        taffy.set_style(child, FlexboxLayout { display: Display::None, ..Default::default() }).unwrap();
+        assert!(!taffy.is_laid_out(child).unwrap());
+        assert!(!taffy.is_laid_out(grandchild).unwrap());
+        assert!(taffy.is_laid_out(sibling).unwrap());
+        assert!(taffy.is_laid_out(root).unwrap());
+
+        taffy.remove(sibling).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.is_laid_out(sibling).is_err());
+    }
+
+    #[test]
+    fn append_new_leaf() {
+        let mut taffy = Taffy::new();
+        let existing = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[existing]).unwrap();
+
+        let leaf = taffy
+            .append_new_leaf(parent, FlexboxLayout::default(), MeasureFunc::fixed(Size { width: 10.0, height: 20.0 }))
+            .unwrap();
+
+        assert_eq!(taffy.children(parent).unwrap().as_slice(), &[existing, leaf]);
+        assert!(taffy.dirty(parent).unwrap());
+
+        taffy.compute_layout(parent, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(leaf).unwrap().size, Size { width: 10.0, height: 20.0 });
+    }
+
+    #[test]
+    fn append_new_leaf_validates_before_creating() {
+        let mut taffy = Taffy::new();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let removed = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.remove(removed).unwrap();
+        let measure = || MeasureFunc::fixed(Size { width: 10.0, height: 20.0 });
+
+        #[cfg(not(feature = "debug-checks"))]
+        {
+            let result = taffy.append_new_leaf(removed, FlexboxLayout::default(), measure());
+            assert!(matches!(result, Err(taffy::error::StyleError::InvalidNode(node)) if node == removed));
+        }
+
+        let style = FlexboxLayout { flex_grow: f32::NAN, ..Default::default() };
+        let result = taffy.append_new_leaf(parent, style, measure());
+        assert!(matches!(result, Err(taffy::error::StyleError::NonFinite { field: "flex_grow" })));
+
+        assert_eq!(taffy.node_count(), 1);
+        assert_eq!(taffy.child_count(parent).unwrap(), 0);
+    }
+
+    #[test]
+    fn traverse_postorder() {
+        let mut taffy = Taffy::new();
+
+        let grandchild = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child1 = taffy.new_with_children(FlexboxLayout::default(), &[grandchild]).unwrap();
+        let child2 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+
+        let mut visited = Vec::new();
+        taffy.traverse_postorder(root, |node| visited.push(node)).unwrap();
+        assert_eq!(visited, vec![grandchild, child1, child2, root]);
+
+        let mut visited = Vec::new();
+        taffy.traverse_postorder(child2, |node| visited.push(node)).unwrap();
+        assert_eq!(visited, vec![child2]);
+
+        taffy.remove(child2).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.traverse_postorder(child2, |_| {}).is_err());
+    }
+
+    #[test]
+    fn last_available_space() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+
+        assert_eq!(taffy.last_available_space(root).unwrap(), None);
+
+        let size = Size { width: Some(100.0), height: None };
+        taffy.compute_layout(root, size).unwrap();
+        assert_eq!(taffy.last_available_space(root).unwrap(), Some(size));
+        assert_eq!(taffy.last_available_space(child).unwrap(), None);
+
+        taffy.compute_layout(root, Size { width: Some(-10.0), height: Some(50.0) }).unwrap();
+        assert_eq!(taffy.last_available_space(root).unwrap(), Some(Size { width: Some(0.0), height: Some(50.0) }));
+
+        taffy.remove(child).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.last_available_space(child).is_err());
+    }
+
+    #[test]
+    fn designated_root() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout::flex(1.0), &[]).unwrap();
+        let root_style = FlexboxLayout {
+            size: Size { width: Dimension::Percent(1.0), height: Dimension::Percent(1.0) },
+            ..Default::default()
+        };
+        let root = taffy.new_with_children(root_style, &[child]).unwrap();
+
+        assert_eq!(taffy.root(), None);
+        assert!(taffy.layout_root().is_none());
+        assert!(!taffy.compute_layout_root(Size::undefined()).unwrap());
+
+        taffy.set_root(root).unwrap();
+        assert_eq!(taffy.root(), Some(root));
+        assert!(taffy.compute_layout_root(Size { width: Some(100.0), height: Some(50.0) }).unwrap());
+        assert_eq!(taffy.layout_root().unwrap().size, Size { width: 100.0, height: 50.0 });
+        assert_eq!(taffy.layout(child).unwrap().size, Size { width: 100.0, height: 50.0 });
+
+        // Removing another node moves the root within the forest, but it stays designated
+        taffy.remove(child).unwrap();
+        assert_eq!(taffy.root(), Some(root));
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.set_root(child).is_err());
+        assert!(taffy.compute_layout_root(Size::undefined()).unwrap());
+
+        taffy.remove(root).unwrap();
+        assert_eq!(taffy.root(), None);
+    }
+
+    #[test]
+    fn compute_sizes() {
+        let mut taffy = Taffy::new();
+        let fixed = |width| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        let child1 = taffy.new_with_children(fixed(30.0), &[]).unwrap();
+        let child2 = taffy.new_with_children(fixed(20.0), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+
+        taffy.compute_sizes(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(root).unwrap().size, Size { width: 50.0, height: 10.0 });
+        assert_eq!(taffy.layout(child2).unwrap().size, Size::zero());
+        assert!(taffy.dirty(root).unwrap());
+        assert!(taffy.dirty(child2).unwrap());
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(child2).unwrap().location, Point { x: 30.0, y: 0.0 });
+        assert!(!taffy.dirty(root).unwrap());
+
+        taffy.remove(child1).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.compute_sizes(child1, Size::undefined()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn apply_styles() {
+        let mut taffy = Taffy::new();
+        let first = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let second = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let untouched = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[first, second, untouched]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(20.0) },
+            ..Default::default()
+        };
+        let styles = [(first, style), (second, style)].into_iter().collect();
+        taffy.apply_styles(&styles).unwrap();
+
+        assert!(taffy.dirty(first).unwrap());
+        assert!(taffy.dirty(second).unwrap());
+        assert!(taffy.dirty(root).unwrap());
+        assert!(!taffy.dirty(untouched).unwrap());
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(first).unwrap().size, Size { width: 10.0, height: 20.0 });
+        assert_eq!(taffy.layout(second).unwrap().location.x, 10.0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn apply_styles_validates_before_applying() {
+        let mut taffy = Taffy::new();
+        let first = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let second = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let style = FlexboxLayout { display: Display::None, ..Default::default() };
+
+        let mut styles = std::collections::HashMap::new();
+        styles.insert(first, style);
+        styles.insert(second, FlexboxLayout { flex_grow: f32::NAN, ..Default::default() });
+        assert!(taffy.apply_styles(&styles).is_err());
+
+        taffy.remove(second).unwrap();
+        styles.insert(second, style);
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.apply_styles(&styles).is_err());
+
+        assert_eq!(taffy.style(first).unwrap().display, Display::Flex);
+    }
+
+    #[test]
+    fn layout_for_ffi() {
+        let mut taffy = Taffy::new();
+        let child = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(30.0), height: Dimension::Points(40.0) },
+                    margin: Rect { start: Dimension::Points(10.0), top: Dimension::Points(20.0), ..Default::default() },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let layout = *taffy.layout(child).unwrap();
+        assert_eq!(layout.as_xywh(), [10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(
+            taffy::layout::LayoutC::from(layout),
+            taffy::layout::LayoutC { x: 10.0, y: 20.0, width: 30.0, height: 40.0, order: 0 }
+        );
+    }
+
+    #[test]
+    fn compute_hypothetical() {
+        let mut taffy = Taffy::new();
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        let leaf = taffy.new_with_children(style, &[]).unwrap();
+        let node = taffy.new_with_children(FlexboxLayout { flex_grow: 1.0, ..Default::default() }, &[leaf]).unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &[node],
+            )
+            .unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        let parts = |layout: &taffy::layout::Layout| (layout.order, layout.size, layout.location);
+        let before_node = parts(taffy.layout(node).unwrap());
+        let before_leaf = parts(taffy.layout(leaf).unwrap());
+
+        let hypothetical = FlexboxLayout {
+            size: Size { width: Dimension::Points(50.0), height: Dimension::Points(40.0) },
+            justify_content: JustifyContent::FlexEnd,
+            ..Default::default()
+        };
+        let layout = taffy.compute_hypothetical(node, &hypothetical, Size::undefined()).unwrap();
+        assert_eq!(layout.size, Size { width: 50.0, height: 40.0 });
+
+        // The stored layouts are those of the real style, and nothing needs laying out again
+        assert_eq!(taffy.style(node).unwrap().flex_grow, 1.0);
+        assert_eq!(parts(taffy.layout(node).unwrap()), before_node);
+        assert_eq!(parts(taffy.layout(leaf).unwrap()), before_leaf);
+        assert!(!taffy.dirty(root).unwrap());
+        assert!(!taffy.dirty(node).unwrap());
+        assert!(!taffy.dirty(leaf).unwrap());
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(node).unwrap().size, Size { width: 100.0, height: 100.0 });
+        assert_eq!(taffy.layout(leaf).unwrap().location, Point { x: 0.0, y: 0.0 });
+    }
+
+    #[test]
+    fn snapshot_and_restore_dirty() {
+        let mut taffy = Taffy::new();
+        let style = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        let child = taffy.new_with_children(style, &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        let snapshot = taffy.snapshot_dirty();
+
+        let wider = FlexboxLayout {
+            size: Size { width: Dimension::Points(50.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        taffy.set_style(child, wider).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(root).unwrap().size.width, 50.0);
+
+        // Rejecting the change puts the tree back as it was, without needing a new layout
+        taffy.set_style(child, style).unwrap();
+        assert!(taffy.dirty(root).unwrap());
+        taffy.restore_dirty(snapshot.clone());
+        assert!(!taffy.dirty(root).unwrap());
+        assert!(!taffy.dirty(child).unwrap());
+        assert_eq!(taffy.layout(root).unwrap().size.width, 10.0);
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(child).unwrap().size.width, 10.0);
+
+        // Nodes removed since the snapshot are skipped
+        taffy.remove(child).unwrap();
+        taffy.restore_dirty(snapshot);
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.dirty(child).is_err());
+    }
+
+    #[test]
+    fn compute_layout_budgeted_without_queued_nodes() {
+        let mut taffy = Taffy::new();
+        let leaf = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        let children: Vec<_> = (0..3).map(|_| taffy.new_with_children(leaf, &[]).unwrap()).collect();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &children).unwrap();
+
+        // The layout of the tree cannot be split, so it is finished at once
+        assert_eq!(
+            taffy.compute_layout_budgeted(root, Size::undefined(), 1).unwrap(),
+            taffy::node::LayoutStatus::Complete
+        );
+        assert_eq!(taffy.layout(root).unwrap().size, Size { width: 30.0, height: 10.0 });
+        assert!(!taffy.dirty(root).unwrap());
+    }
+
+    #[test]
+    fn compute_layout_no_constraints() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(50.0), height: Dimension::Points(10.0) },
+                    min_size: Size { width: Dimension::Undefined, height: Dimension::Points(30.0) },
+                    max_size: Size { width: Dimension::Points(20.0), height: Dimension::Undefined },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    align_items: AlignItems::FlexStart,
+                    ..Default::default()
+                },
+                &[leaf],
+            )
+            .unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(leaf).unwrap().size, Size { width: 20.0, height: 30.0 });
+
+        taffy.compute_layout_no_constraints(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(leaf).unwrap().size, Size { width: 50.0, height: 10.0 });
+        assert_eq!(taffy.style(leaf).unwrap().max_size.width, Dimension::Points(20.0));
+        assert!(taffy.dirty(root).unwrap());
+        assert!(taffy.dirty(leaf).unwrap());
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(leaf).unwrap().size, Size { width: 20.0, height: 30.0 });
+    }
+
+    #[test]
+    fn traverse_bfs() {
+        let mut taffy = Taffy::new();
+
+        let grandchild = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child1 = taffy.new_with_children(FlexboxLayout::default(), &[grandchild]).unwrap();
+        let child2 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+
+        let mut visited = Vec::new();
+        taffy.traverse_bfs(root, |node, depth| visited.push((node, depth))).unwrap();
+        assert_eq!(visited, vec![(root, 0), (child1, 1), (child2, 1), (grandchild, 2)]);
+
+        let removed = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.remove(removed).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.traverse_bfs(removed, |_, _| {}).is_err());
+
+        let mut visited = Vec::new();
+        taffy.traverse_bfs(child1, |node, depth| visited.push((node, depth))).unwrap();
+        assert_eq!(visited, vec![(child1, 0), (grandchild, 1)]);
+    }
+
+    #[test]
+    fn compute_layout_with_sink() {
+        let mut taffy = Taffy::new();
+        let fixed = |width, height| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(height) },
+            ..Default::default()
+        };
+
+        let first = taffy.new_with_children(fixed(10.0, 10.0), &[]).unwrap();
+        let grandchild = taffy.new_with_children(fixed(5.0, 5.0), &[]).unwrap();
+        let second = taffy.new_with_children(fixed(20.0, 20.0), &[grandchild]).unwrap();
+        let root = taffy.new_with_children(fixed(100.0, 100.0), &[first, second]).unwrap();
+
+        let mut layouts = Vec::new();
+        taffy.compute_layout_with_sink(root, Size::undefined(), |node, layout| layouts.push((node, layout))).unwrap();
+
+        let nodes: Vec<_> = layouts.iter().map(|(node, _)| *node).collect();
+        assert_eq!(nodes, vec![root, first, second, grandchild]);
+        for (node, layout) in layouts {
+            assert_eq!(layout.as_xywh(), taffy.layout(node).unwrap().as_xywh());
+        }
+
+        taffy.remove(first).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.compute_layout_with_sink(first, Size::undefined(), |_, _| {}).is_err());
+    }
+
+    #[test]
+    fn with_instance_id() {
+        let mut first = Taffy::with_instance_id(7);
+        let mut second = Taffy::with_instance_id(7);
+
+        let first_node = first.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let second_node = second.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        assert_eq!(first_node, second_node);
+        assert_eq!(format!("{:?}", first_node), "Node { instance: Id(7), local: Id(0) }");
+    }
+
+    #[test]
+    fn remove_many() {
+        let mut taffy = Taffy::new();
+        let nodes: Vec<_> = (0..5).map(|_| taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap()).collect();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &nodes[3..]).unwrap();
+
+        assert_eq!(taffy.remove_many(&[nodes[1], nodes[4], nodes[1]]).unwrap()[..], [1, 4, 1]);
+        assert_eq!(taffy.node_count(), 4);
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.style(nodes[1]).is_err());
+        assert_eq!(taffy.children(parent).unwrap()[..], [nodes[3]]);
+        for node in [nodes[0], nodes[2], nodes[3], parent] {
+            assert!(taffy.style(node).is_ok());
+        }
+
+        #[cfg(not(feature = "debug-checks"))]
+        {
+            assert!(taffy.remove_many(&[nodes[0], nodes[4]]).is_err());
+            assert!(taffy.style(nodes[0]).is_ok());
+        }
+    }
+
+    #[test]
+    fn child_fractions() {
+        let mut taffy = Taffy::new();
+        let first = taffy.new_with_children(FlexboxLayout::flex(1.0), &[]).unwrap();
+        let second = taffy.new_with_children(FlexboxLayout::flex(3.0), &[]).unwrap();
+        let parent = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_direction: FlexDirection::Column,
+                    padding: Rect {
+                        top: Dimension::Points(30.0),
+                        bottom: Dimension::Points(10.0),
+                        ..Default::default()
+                    },
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(240.0) },
+                    ..Default::default()
+                },
+                &[first, second],
+            )
+            .unwrap();
+        assert_eq!(taffy.child_fractions(parent).unwrap()[..], [0.0, 0.0]);
+
+        taffy.compute_layout(parent, Size::undefined()).unwrap();
+        assert_eq!(taffy.child_fractions(parent).unwrap()[..], [0.25, 0.75]);
+        assert!(taffy.child_fractions(first).unwrap().is_empty());
+    }
+
+    #[test]
+    fn overflows() {
+        let mut taffy = Taffy::new();
+        let text = taffy
+            .new_leaf(
+                FlexboxLayout {
+                    max_size: Size { width: Dimension::Points(60.0), height: Dimension::Auto },
+                    ..Default::default()
+                },
+                MeasureFunc::fixed(Size { width: 100.0, height: 20.0 }),
+            )
+            .unwrap();
+        let wide = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_shrink: 0.0,
+                    size: Size { width: Dimension::Points(150.0), height: Dimension::Points(10.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexStart,
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &[text, wide],
+            )
+            .unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.layout(text).unwrap().size, Size { width: 60.0, height: 20.0 });
+        assert_eq!(taffy.overflows(text).unwrap(), Size { width: true, height: false });
+        assert_eq!(taffy.overflows(node).unwrap(), Size { width: true, height: false });
+        assert_eq!(taffy.overflows(wide).unwrap(), Size { width: false, height: false });
+    }
+
+    #[test]
+    fn styles_along_ancestry() {
+        let mut taffy = Taffy::new();
+        let style = |flex_grow| FlexboxLayout { flex_grow, ..Default::default() };
+
+        let leaf = taffy.new_with_children(style(1.0), &[]).unwrap();
+        let child = taffy.new_with_children(style(2.0), &[leaf]).unwrap();
+        let root = taffy.new_with_children(style(3.0), &[child]).unwrap();
+
+        let grows: Vec<_> = taffy.styles_along_ancestry(leaf).unwrap().iter().map(|style| style.flex_grow).collect();
+        assert_eq!(grows, vec![1.0, 2.0, 3.0]);
+        assert_eq!(taffy.styles_along_ancestry(root).unwrap().len(), 1);
+
+        taffy.remove(leaf).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.styles_along_ancestry(leaf).is_err());
+    }
+
+    #[test]
+    fn layout_lerp() {
+        let a = taffy::layout::Layout {
+            order: 0,
+            size: Size { width: 10.0, height: 20.0 },
+            location: Point { x: 0.0, y: 100.0 },
+        };
+        let b = taffy::layout::Layout {
+            order: 1,
+            size: Size { width: 30.0, height: 20.0 },
+            location: Point { x: 50.0, y: 0.0 },
+        };
+
+        let halfway = taffy::layout::Layout::lerp(&a, &b, 0.5);
+        assert_eq!(halfway.as_xywh(), [25.0, 50.0, 20.0, 20.0]);
+        assert_eq!(halfway.order, 1);
+        assert_eq!(taffy::layout::Layout::lerp(&a, &b, 0.25).order, 0);
+
+        assert_eq!(taffy::layout::Layout::lerp(&a, &b, -1.0).as_xywh(), a.as_xywh());
+        assert_eq!(taffy::layout::Layout::lerp(&a, &b, 2.0).as_xywh(), b.as_xywh());
+    }
+
+    #[test]
+    fn content_cache() {
+        let mut taffy = Taffy::new();
+        let style = FlexboxLayout { flex_grow: 1.0, ..Default::default() };
+        let child = taffy.new_with_children(style, &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        // Setting identical values dirties the tree unless the content cache is enabled
+        taffy.set_style(child, style).unwrap();
+        assert!(taffy.dirty(root).unwrap());
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        taffy.set_content_cache(true);
+        taffy.set_style(child, style).unwrap();
+        taffy.set_children(root, &[child]).unwrap();
+        assert!(!taffy.dirty(child).unwrap());
+        assert!(!taffy.dirty(root).unwrap());
+
+        taffy.set_style(child, FlexboxLayout::default()).unwrap();
+        assert!(taffy.dirty(root).unwrap());
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        taffy.set_children(root, &[]).unwrap();
+        assert!(taffy.dirty(root).unwrap());
+    }
+
+    #[test]
+    fn children_iter() {
+        let mut taffy = Taffy::new();
+        let child1 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let child2 = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let node = taffy.new_with_children(FlexboxLayout::default(), &[child1, child2]).unwrap();
+
+        assert!(taffy.children_iter(node).unwrap().eq([child1, child2]));
+        assert_eq!(taffy.children_iter(child1).unwrap().count(), 0);
+
+        taffy.remove(child1).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.children_iter(child1).is_err());
+    }
+
+    #[test]
+    fn leaf_layouts() {
+        let mut taffy = Taffy::new();
+        let fixed = |width, height| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(height) },
+            ..Default::default()
+        };
+
+        let first = taffy.new_with_children(fixed(10.0, 10.0), &[]).unwrap();
+        let grandchild = taffy.new_with_children(fixed(5.0, 5.0), &[]).unwrap();
+        let second = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    padding: Rect { start: Dimension::Points(3.0), ..Default::default() },
+                    ..fixed(20.0, 20.0)
+                },
+                &[grandchild],
+            )
+            .unwrap();
+        let hidden = taffy.new_with_children(FlexboxLayout { display: Display::None, ..fixed(5.0, 5.0) }, &[]).unwrap();
+        let root = taffy.new_with_children(fixed(100.0, 100.0), &[first, second, hidden]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let leaves = taffy.leaf_layouts(root).unwrap();
+        let nodes: Vec<_> = leaves.iter().map(|(node, _)| *node).collect();
+        assert_eq!(nodes, vec![first, grandchild]);
+        assert_eq!(leaves[1].1.as_xywh(), [13.0, 0.0, 5.0, 5.0]);
+
+        assert_eq!(taffy.leaf_layouts(grandchild).unwrap()[0].1.location, Point { x: 13.0, y: 0.0 });
+    }
+
+    #[test]
+    fn compute_and_flatten() {
+        let mut taffy = Taffy::new();
+        let fixed = |width, height| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(height) },
+            ..Default::default()
+        };
+
+        let first = taffy.new_with_children(fixed(10.0, 10.0), &[]).unwrap();
+        let grandchild = taffy.new_with_children(fixed(5.0, 5.0), &[]).unwrap();
+        let second = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    padding: Rect { start: Dimension::Points(3.0), ..Default::default() },
+                    ..fixed(20.0, 20.0)
+                },
+                &[grandchild],
+            )
+            .unwrap();
+        let hidden = taffy.new_with_children(FlexboxLayout { display: Display::None, ..fixed(5.0, 5.0) }, &[]).unwrap();
+        let root = taffy.new_with_children(fixed(100.0, 100.0), &[first, second, hidden]).unwrap();
+        let outer = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    padding: Rect { start: Dimension::Points(7.0), ..Default::default() },
+                    ..Default::default()
+                },
+                &[root],
+            )
+            .unwrap();
+        taffy.compute_layout(outer, Size::undefined()).unwrap();
+
+        // The layouts are relative to the provided root, even when it has a parent
+        let layouts = taffy.compute_and_flatten(root, Size::undefined()).unwrap();
+        let nodes: Vec<_> = layouts.iter().map(|(node, _)| *node).collect();
+        assert_eq!(nodes, vec![root, first, second, grandchild]);
+        assert_eq!(layouts[0].1.as_xywh(), [0.0, 0.0, 100.0, 100.0]);
+        assert_eq!(layouts[2].1.as_xywh(), [10.0, 0.0, 20.0, 20.0]);
+        assert_eq!(layouts[3].1.as_xywh(), [13.0, 0.0, 5.0, 5.0]);
+        assert!(!taffy.dirty(root).unwrap());
+    }
+
+    /// A chain of `depth` nested nodes, returning the outermost one
+    fn chain(taffy: &mut Taffy, depth: usize) -> taffy::node::Node {
+        let mut node = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        for _ in 1..depth {
+            node = taffy.new_with_children(FlexboxLayout::default(), &[node]).unwrap();
+        }
+        node
+    }
+
+    #[test]
+    fn max_depth_rejects_deeper_trees() {
+        let mut taffy = Taffy::new();
+        let root = chain(&mut taffy, 4);
+
+        taffy.set_max_depth(3);
+        assert!(matches!(
+            taffy.compute_layout(root, Size::undefined()),
+            Err(taffy::error::LayoutError::LimitExceeded { limit: "max_depth" })
+        ));
+        assert!(taffy.dirty(root).unwrap());
+
+        taffy.set_max_depth(4);
+        assert!(taffy.compute_layout(root, Size::undefined()).is_ok());
+        assert!(!taffy.dirty(root).unwrap());
+    }
+
+    #[test]
+    fn max_nodes_per_pass_rejects_larger_trees() {
+        let mut taffy = Taffy::new();
+        let children: Vec<_> =
+            (0..3).map(|_| taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap()).collect();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &children).unwrap();
+
+        taffy.set_max_nodes_per_pass(3);
+        assert!(matches!(
+            taffy.compute_all(&[(children[0], Size::undefined()), (root, Size::undefined())]),
+            Err(taffy::error::LayoutError::LimitExceeded { limit: "max_nodes_per_pass" })
+        ));
+        assert!(taffy.dirty(children[0]).unwrap());
+
+        taffy.set_max_nodes_per_pass(4);
+        assert!(taffy.compute_layout(root, Size::undefined()).is_ok());
+    }
+
+    #[test]
+    fn bounds_of_unions_absolute_rects() {
+        let mut taffy = Taffy::new();
+        let leaf = |width: f32, height: f32| FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(height) },
+            ..Default::default()
+        };
+        let first = taffy.new_with_children(leaf(10.0, 10.0), &[]).unwrap();
+        let nested = taffy.new_with_children(leaf(20.0, 30.0), &[]).unwrap();
+        let second = taffy
+            .new_with_children(
+                FlexboxLayout { padding: Rect::splat(Dimension::Points(5.0)), ..Default::default() },
+                &[nested],
+            )
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[first, second]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.bounds_of(&[first]).unwrap(), Rect { start: 0.0, end: 10.0, top: 0.0, bottom: 10.0 });
+        assert_eq!(taffy.bounds_of(&[nested]).unwrap(), Rect { start: 15.0, end: 35.0, top: 5.0, bottom: 35.0 });
+        assert_eq!(taffy.bounds_of(&[first, nested]).unwrap(), Rect { start: 0.0, end: 35.0, top: 0.0, bottom: 35.0 });
+        assert_eq!(taffy.bounds_of(&[]).unwrap(), Rect { start: 0.0, end: 0.0, top: 0.0, bottom: 0.0 });
+
+        taffy.remove(second).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.bounds_of(&[first, second]).is_err());
+    }
+
+    #[test]
+    fn margin_box_includes_resolved_margins() {
+        let mut taffy = Taffy::new();
+        let child = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+                    margin: Rect {
+                        start: Dimension::Points(5.0),
+                        end: Dimension::Auto,
+                        top: Dimension::Percent(0.1),
+                        bottom: Dimension::Points(0.0),
+                    },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    align_items: AlignItems::FlexStart,
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        // The auto margin takes the remaining 85 points, and the percentage resolves against the width of the root
+        assert_eq!(taffy.margin_box(child).unwrap(), Rect { start: 0.0, end: 100.0, top: 0.0, bottom: 20.0 });
+        assert_eq!(taffy.margin_box(root).unwrap(), Rect { start: 0.0, end: 100.0, top: 0.0, bottom: 100.0 });
+
+        taffy.remove(child).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.margin_box(child).is_err());
+    }
+
+    #[test]
+    fn main_axis_layout_follows_flex_direction() {
+        let mut taffy = Taffy::new();
+        let leaf = |size: f32| FlexboxLayout {
+            size: Size { width: Dimension::Points(size), height: Dimension::Points(size) },
+            ..Default::default()
+        };
+        let children = [
+            taffy.new_with_children(leaf(10.0), &[]).unwrap(),
+            taffy.new_with_children(leaf(20.0), &[]).unwrap(),
+            taffy.new_with_children(FlexboxLayout { display: Display::None, ..leaf(40.0) }, &[]).unwrap(),
+        ];
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_direction: FlexDirection::Column,
+                    padding: Rect::splat(Dimension::Points(5.0)),
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &children,
+            )
+            .unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        assert_eq!(taffy.main_axis_layout(node).unwrap(), (5.0, 30.0));
+
+        let mut style = *taffy.style(node).unwrap();
+        style.flex_direction = FlexDirection::RowReverse;
+        taffy.set_style(node, style).unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        assert_eq!(taffy.main_axis_layout(node).unwrap(), (65.0, 30.0));
+
+        let leaf = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        assert_eq!(taffy.main_axis_layout(leaf).unwrap(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn remove_child_that_is_not_a_child() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+
+        assert!(taffy.remove_child(parent, child).is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn removing_one_entry_of_a_repeated_child_keeps_the_others_linked() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let other = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[child, child]).unwrap();
+
+        taffy.remove_child_at_index(parent, 0).unwrap();
+        taffy.remove(child).unwrap();
+
+        assert_eq!(taffy.child_count(parent).unwrap(), 0);
+        assert_eq!(taffy.child_count(other).unwrap(), 0);
+    }
+
+    #[test]
+    fn reconcile_children_matches_target() {
+        let mut taffy = Taffy::new();
+        let [a, b, c, d] = [(); 4].map(|_| taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap());
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[a, b, c]).unwrap();
+        taffy.compute_layout(parent, Size::undefined()).unwrap();
+
+        taffy.reconcile_children(parent, &[a, b, c]).unwrap();
+        assert!(!taffy.dirty(parent).unwrap());
+
+        taffy.reconcile_children(parent, &[c, a, d]).unwrap();
+        assert_eq!(taffy.children(parent).unwrap()[..], [c, a, d]);
+        assert!(taffy.dirty(parent).unwrap());
+        assert_eq!(taffy.styles_along_ancestry(b).unwrap().len(), 1);
+        assert_eq!(taffy.styles_along_ancestry(c).unwrap().len(), 2);
+        assert_eq!(taffy.styles_along_ancestry(d).unwrap().len(), 2);
+
+        taffy.remove(b).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(matches!(
+            taffy.reconcile_children(parent, &[b]),
+            Err(taffy::error::InvalidChild::InvalidChildNode(node)) if node == b
+        ));
+        assert_eq!(taffy.children(parent).unwrap()[..], [c, a, d]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn subtree_nodes_intersects_with_selections() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let inner = taffy.new_with_children(FlexboxLayout::default(), &[leaf]).unwrap();
+        let outside = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[inner]).unwrap();
+
+        let subtree = taffy.subtree_nodes(root).unwrap();
+        assert_eq!(subtree, [root, inner, leaf].into_iter().collect());
+
+        let selection: std::collections::HashSet<_> = [leaf, outside].into_iter().collect();
+        assert_eq!(selection.intersection(&subtree).copied().collect::<Vec<_>>(), [leaf]);
+
+        assert_eq!(taffy.subtree_nodes(outside).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn layout_computed() {
+        let mut taffy = Taffy::new();
+        let child = taffy.new_with_children(FlexboxLayout::flex(1.0), &[]).unwrap();
+        let root_style = FlexboxLayout {
+            size: Size { width: Dimension::Percent(1.0), height: Dimension::Percent(1.0) },
+            ..Default::default()
+        };
+        let root = taffy.new_with_children(root_style, &[child]).unwrap();
+        let size = Size { width: Some(100.0), height: Some(50.0) };
+
+        assert_eq!(taffy.layout_computed(root, size).unwrap().size, Size { width: 100.0, height: 50.0 });
+        assert!(!taffy.dirty(root).unwrap());
+        assert_eq!(taffy.layout(child).unwrap().size.width, 100.0);
+
+        taffy.set_style(child, FlexboxLayout::default()).unwrap();
+        assert!(taffy.dirty(root).unwrap());
+        assert_eq!(taffy.layout_computed(root, size).unwrap().size.width, 100.0);
+        assert_eq!(taffy.layout(child).unwrap().size.width, 0.0);
+
+        // The same tree in a different space is out of date, even though nothing is dirty
+        let size = Size { width: Some(80.0), height: Some(50.0) };
+        assert_eq!(taffy.layout_computed(root, size).unwrap().size.width, 80.0);
+
+        taffy.remove(child).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.layout_computed(child, size).is_err());
+    }
+
+    #[test]
+    fn fits_within() {
+        let mut taffy = Taffy::new();
+        let text = |width| MeasureFunc::fixed(Size { width, height: 10.0 });
+        let first = taffy.new_leaf(FlexboxLayout::default(), text(30.0)).unwrap();
+        let second = taffy.new_leaf(FlexboxLayout::default(), text(20.0)).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[first, second]).unwrap();
+
+        assert!(taffy.fits_within(root, Size { width: 60.0, height: 10.0 }).unwrap());
+        assert!(taffy.fits_within(root, Size { width: 50.0, height: 10.0 }).unwrap());
+        assert!(!taffy.fits_within(root, Size { width: 40.0, height: 10.0 }).unwrap());
+        assert!(!taffy.fits_within(root, Size { width: 60.0, height: 5.0 }).unwrap());
+
+        taffy.remove(first).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.fits_within(first, Size { width: 60.0, height: 10.0 }).is_err());
+    }
+
+    #[test]
+    fn content_fit_delta() {
+        let mut taffy = Taffy::new();
+        let text = |width| MeasureFunc::fixed(Size { width, height: 10.0 });
+        let first = taffy.new_leaf(FlexboxLayout::default(), text(30.0)).unwrap();
+        let second = taffy.new_leaf(FlexboxLayout::default(), text(20.0)).unwrap();
+        let button = taffy.new_with_children(FlexboxLayout::default(), &[first, second]).unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    flex_direction: FlexDirection::Column,
+                    size: Size { width: Dimension::Points(40.0), height: Dimension::Points(40.0) },
+                    ..Default::default()
+                },
+                &[button],
+            )
+            .unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(button).unwrap().size, Size { width: 40.0, height: 10.0 });
+
+        // The button is stretched to the width of the root, and would grow to fit both texts, but is already tall enough
+        assert_eq!(taffy.content_fit_delta(button).unwrap(), Size { width: 10.0, height: 0.0 });
+        // The root is larger than its content
+        assert_eq!(taffy.content_fit_delta(root).unwrap(), Size { width: 0.0, height: 0.0 });
+        assert_eq!(taffy.layout(button).unwrap().size, Size { width: 40.0, height: 10.0 });
+        assert!(!taffy.dirty(root).unwrap());
+    }
+
+    #[test]
+    fn cycles_are_detected() {
+        let mut taffy = Taffy::new();
+        let leaf = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let inner = taffy.new_with_children(FlexboxLayout::default(), &[leaf]).unwrap();
+        let middle = taffy.new_with_children(FlexboxLayout::default(), &[inner]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[middle]).unwrap();
+
+        taffy.add_child(inner, root).unwrap();
+        assert!(matches!(
+            taffy.compute_layout(root, Size::undefined()),
+            Err(taffy::error::LayoutError::CycleDetected { node }) if node == root
+        ));
+        assert!(matches!(
+            taffy.compute_sizes(middle, Size::undefined()),
+            Err(taffy::error::LayoutError::CycleDetected { node }) if node == middle
+        ));
+        assert!(matches!(
+            taffy.compute_hypothetical(middle, &FlexboxLayout::default(), Size::undefined()),
+            Err(taffy::error::LayoutError::CycleDetected { node }) if node == middle
+        ));
+
+        taffy.set_root(root).unwrap();
+        assert!(matches!(
+            taffy.compute_layout_root(Size::undefined()),
+            Err(taffy::error::LayoutError::CycleDetected { node }) if node == root
+        ));
+
+        taffy.remove_child(inner, root).unwrap();
+        assert!(taffy.compute_layout(root, Size::undefined()).is_ok());
+    }
+
+    #[test]
+    fn growth_delta() {
+        let mut taffy = Taffy::new();
+        let item = |flex_grow, flex_shrink, basis, max| FlexboxLayout {
+            flex_grow,
+            flex_shrink,
+            flex_basis: Dimension::Points(basis),
+            max_size: Size { width: max, height: Dimension::Undefined },
+            ..Default::default()
+        };
+        let grown = taffy.new_with_children(item(1.0, 1.0, 20.0, Dimension::Undefined), &[]).unwrap();
+        let clamped = taffy.new_with_children(item(1.0, 1.0, 20.0, Dimension::Points(30.0)), &[]).unwrap();
+        let fixed = taffy.new_with_children(item(0.0, 0.0, 20.0, Dimension::Undefined), &[]).unwrap();
+        let root_style = FlexboxLayout {
+            size: Size { width: Dimension::Points(100.0), height: Dimension::Points(10.0) },
+            ..Default::default()
+        };
+        let root = taffy.new_with_children(root_style, &[grown, clamped, fixed]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.growth_delta(grown).unwrap(), 30.0);
+        assert_eq!(taffy.growth_delta(clamped).unwrap(), 10.0);
+        assert_eq!(taffy.growth_delta(fixed).unwrap(), 0.0);
+        assert_eq!(taffy.growth_delta(root).unwrap(), 0.0);
+
+        let shrunk = taffy.new_with_children(item(0.0, 1.0, 150.0, Dimension::Undefined), &[]).unwrap();
+        taffy.set_children(root, &[shrunk]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.growth_delta(shrunk).unwrap(), -50.0);
+    }
+
+    #[test]
+    fn replace_children_subtree() {
+        let mut taffy = Taffy::new();
+        let grandchild = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let a = taffy.new_with_children(FlexboxLayout::default(), &[grandchild]).unwrap();
+        let b = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let c = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let d = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let parent = taffy.new_with_children(FlexboxLayout::default(), &[a, b, c]).unwrap();
+        taffy.compute_layout(parent, Size::undefined()).unwrap();
+
+        let detached = taffy.replace_children_subtree(parent, &[c, d]).unwrap();
+        assert_eq!(detached[..], [a, b]);
+        assert_eq!(taffy.children(parent).unwrap()[..], [c, d]);
+        assert!(taffy.dirty(parent).unwrap());
+
+        // The detached children keep their own subtrees, and can be attached elsewhere
+        assert_eq!(taffy.children(a).unwrap()[..], [grandchild]);
+        taffy.add_child(d, a).unwrap();
+        assert_eq!(taffy.node_count(), 6);
+
+        let removed = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.remove(removed).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(matches!(
+            taffy.replace_children_subtree(parent, &[removed]),
+            Err(taffy::error::InvalidChild::InvalidChildNode(node)) if node == removed
+        ));
+        assert_eq!(taffy.children(parent).unwrap()[..], [c, d]);
+    }
+
+    #[test]
+    fn compute_layout_responsive() {
+        let mut taffy = Taffy::new();
+        let child = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+        let column = FlexboxLayout { flex_direction: FlexDirection::Column, ..Default::default() };
+        let row = FlexboxLayout { flex_direction: FlexDirection::Row, ..Default::default() };
+        let breakpoints = [(600.0, row), (0.0, column)];
+
+        let size = Size { width: Some(300.0), height: None };
+        assert_eq!(taffy.compute_layout_responsive(root, size, &breakpoints).unwrap(), Some(1));
+        assert_eq!(taffy.style(root).unwrap().flex_direction, FlexDirection::Column);
+        assert_eq!(taffy.layout(root).unwrap().size.height, 10.0);
+
+        // Applying the same breakpoint again leaves the tree clean
+        assert_eq!(taffy.compute_layout_responsive(root, size, &breakpoints).unwrap(), Some(1));
+        assert!(!taffy.dirty(root).unwrap());
+
+        let size = Size { width: Some(800.0), height: None };
+        assert_eq!(taffy.compute_layout_responsive(root, size, &breakpoints).unwrap(), Some(0));
+        assert_eq!(taffy.style(root).unwrap().flex_direction, FlexDirection::Row);
+        assert!(!taffy.dirty(root).unwrap());
+
+        // Without a fitting breakpoint, the style is kept
+        assert_eq!(taffy.compute_layout_responsive(root, size, &[(1000.0, column)]).unwrap(), None);
+        assert_eq!(taffy.style(root).unwrap().flex_direction, FlexDirection::Row);
+
+        let invalid = FlexboxLayout { flex_grow: f32::NAN, ..Default::default() };
+        assert!(matches!(
+            taffy.compute_layout_responsive(root, size, &[(0.0, invalid)]),
+            Err(taffy::error::LayoutError::NonFinite { field: "flex_grow" })
+        ));
+    }
+
+    #[test]
+    fn eager_layout() {
+        let mut taffy = Taffy::new();
+        taffy.set_eager_layout(true);
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+
+        // A tree that has never been laid out is left dirty
+        let sized = FlexboxLayout {
+            size: Size { width: Dimension::Points(10.0), height: Dimension::Points(20.0) },
+            ..Default::default()
+        };
+        taffy.set_style(child, sized).unwrap();
+        assert!(taffy.dirty(root).unwrap());
+
+        taffy.compute_layout(root, Size { width: Some(100.0), height: None }).unwrap();
+        let wide = FlexboxLayout {
+            size: Size { width: Dimension::Points(40.0), height: Dimension::Points(20.0) },
+            ..Default::default()
+        };
+        taffy.set_style(child, wide).unwrap();
+        assert!(!taffy.dirty(root).unwrap());
+        assert_eq!(taffy.layout(child).unwrap().size.width, 40.0);
+
+        taffy.set_gap(root, Size { width: Dimension::Points(5.0), height: Dimension::Points(5.0) }).unwrap();
+        assert!(!taffy.dirty(root).unwrap());
+        assert_eq!(taffy.last_available_space(root).unwrap(), Some(Size { width: Some(100.0), height: None }));
+
+        taffy.set_eager_layout(false);
+        taffy.set_style(child, sized).unwrap();
+        assert!(taffy.dirty(root).unwrap());
+        assert_eq!(taffy.layout(child).unwrap().size.width, 40.0);
+    }
+
+    #[test]
+    fn last_layout_depth() {
+        let mut taffy = Taffy::new();
+        assert_eq!(taffy.last_layout_depth(), 0);
+
+        let leaf = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let inner = taffy.new_with_children(FlexboxLayout::default(), &[leaf]).unwrap();
+        let middle = taffy.new_with_children(FlexboxLayout::default(), &[inner]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[middle]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.last_layout_depth(), 4);
+
+        // Nothing below the root is visited when the tree is clean
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert_eq!(taffy.last_layout_depth(), 1);
+
+        let lone = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        taffy.compute_layout(lone, Size::undefined()).unwrap();
+        assert_eq!(taffy.last_layout_depth(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn custom_hasher() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::BuildHasherDefault;
+
+        let mut taffy: Taffy<BuildHasherDefault<DefaultHasher>> = Taffy::with_hasher(Default::default());
+        let child = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(10.0), height: Dimension::Points(10.0) },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        assert_eq!(taffy.children(root).unwrap(), vec![child]);
+        assert_eq!(taffy.layout(root).unwrap().size, Size { width: 10.0, height: 10.0 });
+        taffy.remove(child).unwrap();
+        #[cfg(not(feature = "debug-checks"))]
+        assert!(taffy.layout(child).is_err());
+
+        let taffy = Taffy::with_capacity_and_hasher(64, BuildHasherDefault::<DefaultHasher>::default());
+        assert_eq!(taffy.node_count(), 0);
+    }
+
+    #[test]
+    fn any_dirty() {
+        let mut taffy = Taffy::new();
+        assert!(!taffy.any_dirty());
+
+        let child = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[child]).unwrap();
+        let other = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        assert!(taffy.any_dirty());
+
+        // The separate tree is still dirty after laying out the first one
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert!(taffy.any_dirty());
+        taffy.compute_layout(other, Size::undefined()).unwrap();
+        assert!(!taffy.any_dirty());
+
+        taffy.set_style(child, FlexboxLayout { flex_grow: 1.0, ..Default::default() }).unwrap();
+        assert!(taffy.any_dirty());
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert!(!taffy.any_dirty());
+
+        // Removing the only dirty node leaves nothing to lay out
+        taffy.mark_dirty(other).unwrap();
+        assert!(taffy.any_dirty());
+        taffy.remove(other).unwrap();
+        assert!(!taffy.any_dirty());
+
+        taffy.clear();
+        assert!(!taffy.any_dirty());
+    }
+
+    #[test]
+    fn any_dirty_with_hidden_subtree() {
+        let mut taffy = Taffy::new();
+        let grandchild = taffy.new_with_children(FlexboxLayout::default(), &[]).unwrap();
+        let hidden = taffy
+            .new_with_children(FlexboxLayout { display: Display::None, ..Default::default() }, &[grandchild])
+            .unwrap();
+        let root = taffy.new_with_children(FlexboxLayout::default(), &[hidden]).unwrap();
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert!(!taffy.any_dirty());
+
+        taffy.mark_dirty(grandchild).unwrap();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        assert!(!taffy.any_dirty());
+    }
 }
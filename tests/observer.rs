@@ -0,0 +1,96 @@
+#[cfg(all(test, any(feature = "std", feature = "alloc")))]
+mod observer {
+    use std::sync::{Arc, Mutex};
+
+    use taffy::geometry::{AvailableSpace, Size};
+    use taffy::node::{LayoutObserver, MeasureFunc, Node, Taffy};
+    use taffy::style::{Dimension, FlexboxLayout};
+
+    /// Something that happened during a layout
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Event {
+        Enter(Node),
+        Measure(Node),
+        Exit(Node),
+    }
+
+    /// Records every event into a list that is shared with the test
+    struct Recorder(Arc<Mutex<Vec<Event>>>);
+
+    impl LayoutObserver for Recorder {
+        fn on_node_enter(&mut self, node: Node) {
+            self.0.lock().unwrap().push(Event::Enter(node));
+        }
+
+        fn on_measure(&mut self, node: Node, _input: Size<AvailableSpace>, _output: Size<f32>) {
+            self.0.lock().unwrap().push(Event::Measure(node));
+        }
+
+        fn on_node_exit(&mut self, node: Node, _size: Size<f32>) {
+            self.0.lock().unwrap().push(Event::Exit(node));
+        }
+    }
+
+    /// A root containing a measured leaf, along with the shared list of recorded events
+    fn observed() -> (Taffy, Node, Node, Arc<Mutex<Vec<Event>>>) {
+        let mut taffy = Taffy::new();
+        let leaf =
+            taffy.new_leaf(FlexboxLayout::default(), MeasureFunc::fixed(Size { width: 10.0, height: 10.0 })).unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..Default::default()
+                },
+                &[leaf],
+            )
+            .unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        taffy.set_observer(Box::new(Recorder(events.clone())));
+        (taffy, root, leaf, events)
+    }
+
+    #[test]
+    fn events_are_nested() {
+        let (mut taffy, root, leaf, events) = observed();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.first(), Some(&Event::Enter(root)));
+        assert_eq!(events.last(), Some(&Event::Exit(root)));
+        assert!(events.contains(&Event::Measure(leaf)));
+
+        let mut open = Vec::new();
+        for event in events.iter() {
+            match event {
+                Event::Enter(node) => open.push(*node),
+                Event::Measure(node) => assert_eq!(open.last(), Some(node)),
+                Event::Exit(node) => assert_eq!(open.pop(), Some(*node)),
+            }
+        }
+        assert!(open.is_empty());
+    }
+
+    #[test]
+    fn clean_trees_are_not_measured_again() {
+        let (mut taffy, root, _, events) = observed();
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+        events.lock().unwrap().clear();
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(*events, [Event::Enter(root), Event::Exit(root)]);
+    }
+
+    #[test]
+    fn taken_observer_is_not_notified() {
+        let (mut taffy, root, _, events) = observed();
+        assert!(taffy.take_observer().is_some());
+        assert!(taffy.take_observer().is_none());
+
+        taffy.compute_layout(root, Size::undefined()).unwrap();
+
+        assert!(events.lock().unwrap().is_empty());
+    }
+}
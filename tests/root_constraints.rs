@@ -58,4 +58,83 @@ mod root_constraints {
         assert_eq!(layout.size.width, 200.0);
         assert_eq!(layout.size.height, 200.0);
     }
+
+    #[test]
+    fn negative_available_size_is_clamped_to_zero() {
+        let mut taffy = taffy::node::Taffy::new();
+        let child = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    flex_shrink: 0.0,
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Points(10.0),
+                        height: taffy::style::Dimension::Points(10.0),
+                    },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    justify_content: taffy::style::JustifyContent::Center,
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Percent(1.0),
+                        height: taffy::style::Dimension::Percent(1.0),
+                    },
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+
+        taffy.compute_layout(node, taffy::geometry::Size { width: Some(-10.0), height: Some(-20.0) }).unwrap();
+
+        assert_eq!(taffy.layout(node).unwrap().size, taffy::geometry::Size { width: 0.0, height: 0.0 });
+        assert_eq!(taffy.layout(child).unwrap().location.x, -5.0);
+    }
+
+    #[test]
+    fn root_percentages_resolve_against_percentage_base() {
+        let mut taffy = taffy::node::Taffy::new();
+        let child = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Percent(1.0),
+                        height: taffy::style::Dimension::Points(10.0),
+                    },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .unwrap();
+        let node = taffy
+            .new_with_children(
+                taffy::style::FlexboxLayout {
+                    size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Percent(0.5),
+                        height: taffy::style::Dimension::Auto,
+                    },
+                    min_size: taffy::geometry::Size {
+                        width: taffy::style::Dimension::Undefined,
+                        height: taffy::style::Dimension::Percent(0.1),
+                    },
+                    ..Default::default()
+                },
+                &[child],
+            )
+            .unwrap();
+
+        let page = taffy::geometry::Size { width: 800.0, height: 600.0 };
+        taffy.compute_layout_with_percentage_base(node, taffy::geometry::Size::undefined(), page).unwrap();
+
+        assert_eq!(taffy.layout(node).unwrap().size, taffy::geometry::Size { width: 400.0, height: 60.0 });
+        assert_eq!(taffy.layout(child).unwrap().size.width, 400.0);
+
+        // Without a base, the percentages of the root have nothing to resolve against
+        taffy.compute_layout(node, taffy::geometry::Size::undefined()).unwrap();
+        assert_eq!(taffy.layout(node).unwrap().size.height, 10.0);
+    }
 }
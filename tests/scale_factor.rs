@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod scale_factor {
+    use taffy::geometry::Size;
+    use taffy::node::{Node, Taffy};
+    use taffy::style::{Dimension, FlexboxLayout};
+
+    /// Creates three growing children which share a 100x20 row container
+    fn thirds() -> (Taffy, Node, Vec<Node>) {
+        let mut taffy = Taffy::new();
+        let children: Vec<_> =
+            (0..3).map(|_| taffy.new_with_children(FlexboxLayout::flex(1.0), &[]).unwrap()).collect();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(20.0) },
+                    ..Default::default()
+                },
+                &children,
+            )
+            .unwrap();
+        (taffy, node, children)
+    }
+
+    /// The x location and width of each of the `children`
+    fn columns(taffy: &Taffy, children: &[Node]) -> Vec<(f32, f32)> {
+        children
+            .iter()
+            .map(|child| {
+                let layout = taffy.layout(*child).unwrap();
+                (layout.location.x, layout.size.width)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn layout_is_rounded_to_whole_pixels_by_default() {
+        let (mut taffy, node, children) = thirds();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        assert_eq!(columns(&taffy, &children), vec![(0.0, 33.0), (33.0, 33.0), (67.0, 33.0)]);
+    }
+
+    #[test]
+    fn layout_is_rounded_to_physical_pixels() {
+        let (mut taffy, node, children) = thirds();
+        taffy.set_scale_factor(2.0);
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        assert_eq!(columns(&taffy, &children), vec![(0.0, 33.5), (33.5, 33.5), (66.5, 33.5)]);
+        assert_eq!(taffy.layout(node).unwrap().size, Size { width: 100.0, height: 20.0 });
+    }
+
+    #[test]
+    fn changing_scale_factor_dirties_layout() {
+        let (mut taffy, node, children) = thirds();
+        taffy.set_scale_factor(2.0);
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        taffy.set_scale_factor(1.0);
+        assert!(taffy.dirty(node).unwrap());
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+
+        assert_eq!(columns(&taffy, &children), vec![(0.0, 33.0), (33.0, 33.0), (67.0, 33.0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_scale_factor_panics() {
+        Taffy::new().set_scale_factor(0.0);
+    }
+}
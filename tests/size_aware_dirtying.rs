@@ -0,0 +1,174 @@
+#[cfg(all(test, any(feature = "std", feature = "alloc")))]
+mod size_aware_dirtying {
+    use taffy::geometry::Size;
+    use taffy::node::{LayoutStatus, Node, Taffy};
+    use taffy::style::{Dimension, FlexDirection, FlexboxLayout, JustifyContent};
+
+    /// A `width` x `height` leaf style
+    fn leaf(width: f32, height: f32) -> FlexboxLayout {
+        FlexboxLayout {
+            size: Size { width: Dimension::Points(width), height: Dimension::Points(height) },
+            ..Default::default()
+        }
+    }
+
+    /// The nodes of a small tree, containing a panel that may or may not have a fixed size
+    struct Tree {
+        taffy: Taffy,
+        root: Node,
+        sibling: Node,
+        panel: Node,
+    }
+
+    /// Builds a column containing a 50x50 sibling, then a `panel_style` panel with `item_count` 20x20 items
+    fn build(panel_style: FlexboxLayout, item_count: usize) -> Tree {
+        let mut taffy = Taffy::new();
+        let items: Vec<_> = (0..item_count).map(|_| taffy.new_with_children(leaf(20.0, 20.0), &[]).unwrap()).collect();
+        let panel = taffy.new_with_children(panel_style, &items).unwrap();
+        let sibling = taffy.new_with_children(leaf(50.0, 50.0), &[]).unwrap();
+        let root = taffy
+            .new_with_children(
+                FlexboxLayout { flex_direction: FlexDirection::Column, ..Default::default() },
+                &[sibling, panel],
+            )
+            .unwrap();
+        Tree { taffy, root, sibling, panel }
+    }
+
+    /// Asserts that `left` and `right` have the same layouts throughout, starting from their roots
+    fn assert_same_layouts(left: &Taffy, left_node: Node, right: &Taffy, right_node: Node) {
+        let (left_layout, right_layout) = (left.layout(left_node).unwrap(), right.layout(right_node).unwrap());
+        assert_eq!(left_layout.size, right_layout.size);
+        assert_eq!(left_layout.location, right_layout.location);
+
+        let (left_children, right_children) = (left.children(left_node).unwrap(), right.children(right_node).unwrap());
+        assert_eq!(left_children.len(), right_children.len());
+        for (left_child, right_child) in left_children.into_iter().zip(right_children) {
+            assert_same_layouts(left, left_child, right, right_child);
+        }
+    }
+
+    /// Adds another 20x20 item to the panel of the `tree`
+    fn add_item(tree: &mut Tree) {
+        let item = tree.taffy.new_with_children(leaf(20.0, 20.0), &[]).unwrap();
+        tree.taffy.add_child(tree.panel, item).unwrap();
+    }
+
+    #[test]
+    fn unchanged_size_does_not_dirty_ancestors() {
+        let mut tree = build(leaf(100.0, 100.0), 2);
+        tree.taffy.set_size_aware_dirtying(true);
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+
+        add_item(&mut tree);
+        assert!(tree.taffy.dirty(tree.panel).unwrap());
+        assert!(!tree.taffy.dirty(tree.root).unwrap());
+
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+        assert!(!tree.taffy.dirty(tree.panel).unwrap());
+
+        let mut expected = build(leaf(100.0, 100.0), 3);
+        expected.taffy.compute_layout(expected.root, Size::undefined()).unwrap();
+        assert_same_layouts(&tree.taffy, tree.root, &expected.taffy, expected.root);
+    }
+
+    #[test]
+    fn changed_size_relays_out_ancestors() {
+        let mut tree = build(FlexboxLayout::default(), 2);
+        tree.taffy.set_size_aware_dirtying(true);
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+        assert_eq!(tree.taffy.layout(tree.root).unwrap().size.width, 50.0);
+
+        add_item(&mut tree);
+        add_item(&mut tree);
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+        assert_eq!(tree.taffy.layout(tree.root).unwrap().size.width, 80.0);
+        assert!(!tree.taffy.dirty(tree.root).unwrap());
+
+        let mut expected = build(FlexboxLayout::default(), 4);
+        expected.taffy.compute_layout(expected.root, Size::undefined()).unwrap();
+        assert_same_layouts(&tree.taffy, tree.root, &expected.taffy, expected.root);
+    }
+
+    #[test]
+    fn style_visible_to_parent_dirties_ancestors() {
+        let mut tree = build(leaf(100.0, 100.0), 2);
+        tree.taffy.set_size_aware_dirtying(true);
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+
+        tree.taffy.set_style(tree.sibling, leaf(80.0, 50.0)).unwrap();
+        assert!(tree.taffy.dirty(tree.root).unwrap());
+
+        let panel_style = FlexboxLayout { justify_content: JustifyContent::Center, ..leaf(100.0, 100.0) };
+        tree.taffy.set_style(tree.panel, panel_style).unwrap();
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+
+        let mut expected = build(panel_style, 2);
+        expected.taffy.set_style(expected.sibling, leaf(80.0, 50.0)).unwrap();
+        expected.taffy.compute_layout(expected.root, Size::undefined()).unwrap();
+        assert_same_layouts(&tree.taffy, tree.root, &expected.taffy, expected.root);
+    }
+
+    #[test]
+    fn disabling_dirties_ancestors() {
+        let mut tree = build(leaf(100.0, 100.0), 2);
+        tree.taffy.set_size_aware_dirtying(true);
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+
+        add_item(&mut tree);
+        assert!(!tree.taffy.dirty(tree.root).unwrap());
+
+        tree.taffy.set_size_aware_dirtying(false);
+        assert!(tree.taffy.dirty(tree.root).unwrap());
+    }
+
+    #[test]
+    fn removed_nodes_are_not_laid_out() {
+        let mut tree = build(leaf(100.0, 100.0), 2);
+        tree.taffy.set_size_aware_dirtying(true);
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+
+        add_item(&mut tree);
+        let items = tree.taffy.children(tree.panel).unwrap();
+        for item in items {
+            tree.taffy.remove_child(tree.panel, item).unwrap();
+            tree.taffy.remove(item).unwrap();
+        }
+        tree.taffy.set_children(tree.panel, &[]).unwrap();
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+
+        let mut expected = build(leaf(100.0, 100.0), 0);
+        expected.taffy.compute_layout(expected.root, Size::undefined()).unwrap();
+        assert_same_layouts(&tree.taffy, tree.root, &expected.taffy, expected.root);
+    }
+
+    #[test]
+    fn budgeted_layout_spreads_queued_nodes_over_calls() {
+        let mut tree = build(leaf(100.0, 100.0), 2);
+        tree.taffy.set_size_aware_dirtying(true);
+        tree.taffy.compute_layout(tree.root, Size::undefined()).unwrap();
+
+        // The panel and its two items, then the sibling alone
+        let panel_style = FlexboxLayout { justify_content: JustifyContent::Center, ..leaf(100.0, 100.0) };
+        tree.taffy.set_style(tree.panel, panel_style).unwrap();
+        let sibling_style = FlexboxLayout { justify_content: JustifyContent::Center, ..leaf(50.0, 50.0) };
+        tree.taffy.set_style(tree.sibling, sibling_style).unwrap();
+
+        let resume_token = match tree.taffy.compute_layout_budgeted(tree.root, Size::undefined(), 3).unwrap() {
+            LayoutStatus::Partial { resume_token } => resume_token,
+            LayoutStatus::Complete => panic!("the sibling does not fit in the budget"),
+        };
+        assert!(!tree.taffy.dirty(tree.panel).unwrap());
+        assert!(tree.taffy.dirty(tree.sibling).unwrap());
+
+        // A step is always taken, even if it does not fit
+        assert_eq!(tree.taffy.resume_layout(resume_token, 0).unwrap(), LayoutStatus::Complete);
+        assert!(!tree.taffy.dirty(tree.sibling).unwrap());
+        assert!(!tree.taffy.dirty(tree.root).unwrap());
+
+        let mut expected = build(panel_style, 2);
+        expected.taffy.set_style(expected.sibling, sibling_style).unwrap();
+        expected.taffy.compute_layout(expected.root, Size::undefined()).unwrap();
+        assert_same_layouts(&tree.taffy, tree.root, &expected.taffy, expected.root);
+    }
+}
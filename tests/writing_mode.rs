@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod writing_mode {
+    use taffy::geometry::{Point, Size};
+    use taffy::node::Taffy;
+    use taffy::style::*;
+
+    /// Lays out three 10x20 children in a 100x100 container with the given `style`, and returns their locations
+    fn locations(style: FlexboxLayout) -> Vec<Point<f32>> {
+        let mut taffy = Taffy::new();
+        let children: Vec<_> = (0..3)
+            .map(|_| {
+                let style = FlexboxLayout {
+                    size: Size { width: Dimension::Points(10.0), height: Dimension::Points(20.0) },
+                    ..Default::default()
+                };
+                taffy.new_with_children(style, &[]).unwrap()
+            })
+            .collect();
+        let node = taffy
+            .new_with_children(
+                FlexboxLayout {
+                    size: Size { width: Dimension::Points(100.0), height: Dimension::Points(100.0) },
+                    ..style
+                },
+                &children,
+            )
+            .unwrap();
+        taffy.compute_layout(node, Size::undefined()).unwrap();
+        children.iter().map(|child| taffy.layout(*child).unwrap().location).collect()
+    }
+
+    fn points(points: &[(f32, f32)]) -> Vec<Point<f32>> {
+        points.iter().map(|(x, y)| Point { x: *x, y: *y }).collect()
+    }
+
+    #[test]
+    fn vertical_rl_row_flows_top_to_bottom_along_the_right_edge() {
+        let style = FlexboxLayout { writing_mode: WritingMode::VerticalRl, ..Default::default() };
+
+        assert_eq!(locations(style), points(&[(90.0, 0.0), (90.0, 20.0), (90.0, 40.0)]));
+    }
+
+    #[test]
+    fn vertical_rl_column_flows_right_to_left() {
+        let style = FlexboxLayout {
+            writing_mode: WritingMode::VerticalRl,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        };
+
+        assert_eq!(locations(style), points(&[(90.0, 0.0), (80.0, 0.0), (70.0, 0.0)]));
+    }
+
+    #[test]
+    fn vertical_lr_row_flows_top_to_bottom_along_the_left_edge() {
+        let style = FlexboxLayout { writing_mode: WritingMode::VerticalLr, ..Default::default() };
+
+        assert_eq!(locations(style), points(&[(0.0, 0.0), (0.0, 20.0), (0.0, 40.0)]));
+    }
+
+    #[test]
+    fn vertical_rl_wraps_lines_from_right_to_left() {
+        let style = FlexboxLayout {
+            writing_mode: WritingMode::VerticalRl,
+            flex_wrap: FlexWrap::Wrap,
+            align_content: AlignContent::FlexStart,
+            max_size: Size { width: Dimension::Auto, height: Dimension::Points(50.0) },
+            ..Default::default()
+        };
+
+        assert_eq!(locations(style), points(&[(90.0, 0.0), (90.0, 20.0), (80.0, 0.0)]));
+    }
+
+    #[test]
+    fn logical_alignment_follows_the_writing_mode() {
+        let style = FlexboxLayout {
+            writing_mode: WritingMode::VerticalRl,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Start,
+            align_items: AlignItems::End,
+            ..Default::default()
+        };
+
+        assert_eq!(locations(style), points(&[(90.0, 80.0), (80.0, 80.0), (70.0, 80.0)]));
+    }
+}